@@ -1,4 +1,5 @@
 extern crate rocket;
+use crate::device_auth::AuthenticatedDevice;
 use std::str::FromStr;
 
 use crate::AssetsClient;
@@ -58,6 +59,7 @@ pub async fn get_assets_search(
 #[get("/assets/by_device_id/<device_id>?<wallet_index>&<from_timestamp>")]
 pub async fn get_assets_ids_by_device_id(
     device_id: &str,
+    _device: AuthenticatedDevice,
     wallet_index: i32,
     from_timestamp: Option<u32>,
     client: &State<Mutex<AssetsClient>>,
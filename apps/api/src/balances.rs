@@ -0,0 +1,52 @@
+extern crate rocket;
+use std::str::FromStr;
+
+use crate::balances_client::BalancesClient;
+use crate::response::ResponseError;
+use primitives::{AddressBalances, Chain};
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::tokio::sync::Mutex;
+use rocket::State;
+
+// each token can be a request to the node, see BalanceProvider::get_tokens_balance
+const BALANCES_TOKEN_IDS_MAX_LIMIT: usize = 100;
+
+// token_ids is a comma separated list of tokens to include even when the node does not list them
+#[get("/balances/<chain>/<address>?<token_ids>")]
+pub async fn get_balances(
+    chain: &str,
+    address: &str,
+    token_ids: Option<String>,
+    client: &State<Mutex<BalancesClient>>,
+) -> Result<Json<AddressBalances>, Custom<Json<ResponseError>>> {
+    let chain = Chain::from_str(chain).map_err(|_| {
+        ResponseError::status(Status::BadRequest, format!("invalid chain: {}", chain))
+    })?;
+    let mut token_ids: Vec<String> = token_ids
+        .unwrap_or_default()
+        .split(',')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_string())
+        .collect();
+    token_ids.sort();
+    token_ids.dedup();
+    if token_ids.len() > BALANCES_TOKEN_IDS_MAX_LIMIT {
+        return Err(ResponseError::status(
+            Status::BadRequest,
+            format!(
+                "too many token ids: {}, limit: {}",
+                token_ids.len(),
+                BALANCES_TOKEN_IDS_MAX_LIMIT
+            ),
+        ));
+    }
+    client
+        .lock()
+        .await
+        .get_balances(chain, address, token_ids)
+        .await
+        .map(Json)
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use primitives::{AddressBalances, Chain};
+use settings::Settings;
+use settings_chain::ProviderFactory;
+
+pub struct BalancesClient {
+    settings: Settings,
+}
+
+impl BalancesClient {
+    pub async fn new(settings: Settings) -> Self {
+        Self { settings }
+    }
+
+    // only the native balance is required, staking and tokens rely on calls that
+    // public nodes often disable, such as getProgramAccounts on solana
+    pub async fn get_balances(
+        &self,
+        chain: Chain,
+        address: &str,
+        token_ids: Vec<String>,
+    ) -> Result<AddressBalances, Box<dyn Error + Send + Sync>> {
+        let url = ProviderFactory::url(chain, &self.settings);
+        let provider = ProviderFactory::new_balance_provider(chain, url);
+
+        let (native, staked, tokens) = futures::join!(
+            provider.get_balance(address),
+            provider.get_staking_balance(address),
+            provider.get_tokens_balance(address, token_ids),
+        );
+        let staked = staked.unwrap_or_else(|err| {
+            println!("balances staking error: {}, chain: {}", err, chain.as_ref());
+            None
+        });
+        let tokens = tokens.unwrap_or_else(|err| {
+            println!("balances tokens error: {}, chain: {}", err, chain.as_ref());
+            vec![]
+        });
+
+        Ok(AddressBalances {
+            native: native?,
+            staked,
+            tokens,
+        })
+    }
+}
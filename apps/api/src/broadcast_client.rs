@@ -0,0 +1,69 @@
+use gem_chain_rpc::broadcast::broadcast_error_code;
+use primitives::{
+    Chain, TransactionBroadcastError, TransactionBroadcastErrorCode, TransactionBroadcastResult,
+};
+use settings::Settings;
+use settings_chain::ProviderFactory;
+use storage::DatabaseClient;
+
+pub struct BroadcastClient {
+    database: DatabaseClient,
+    settings: Settings,
+}
+
+impl BroadcastClient {
+    pub async fn new(database_url: &str, settings: Settings) -> Self {
+        let database = DatabaseClient::new(database_url);
+        Self { database, settings }
+    }
+
+    // active nodes by priority, the configured node is the last resort
+    pub fn get_urls(&mut self, chain: Chain) -> Vec<String> {
+        let mut urls = match self.database.get_nodes_active(chain.as_ref()) {
+            Ok(nodes) => nodes.into_iter().map(|x| x.url).collect(),
+            Err(err) => {
+                println!("broadcast nodes error: {}, chain: {}", err, chain.as_ref());
+                vec![]
+            }
+        };
+        let url = ProviderFactory::url(chain, &self.settings).to_string();
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+        urls
+    }
+}
+
+// only unreachable nodes are retried, a rejection from one node is final.
+// does not need the client, callers release it before waiting on the nodes
+pub async fn broadcast_transaction(
+    chain: Chain,
+    urls: Vec<String>,
+    data: &str,
+) -> Result<TransactionBroadcastResult, TransactionBroadcastError> {
+    let mut error = TransactionBroadcastError {
+        code: TransactionBroadcastErrorCode::NodeUnavailable,
+        message: "no nodes available".to_string(),
+    };
+    for url in urls {
+        let provider = ProviderFactory::new_broadcast_provider(chain, &url);
+        match provider.broadcast_transaction(data).await {
+            Ok(hash) => return Ok(TransactionBroadcastResult { hash }),
+            Err(err) => {
+                let code = broadcast_error_code(err.as_ref());
+                let message = err.to_string();
+                println!(
+                    "broadcast error: {}, chain: {}, url: {}",
+                    message,
+                    chain.as_ref(),
+                    url
+                );
+                error = TransactionBroadcastError { code, message };
+                if code != TransactionBroadcastErrorCode::NodeUnavailable {
+                    break;
+                }
+            }
+        }
+    }
+    Err(error)
+}
@@ -24,18 +24,12 @@ pub async fn get_charts(
         .get_coin_id(asset_id.as_str())
         .unwrap();
 
-    let prices = charts_client
+    let charts = charts_client
         .lock()
         .await
-        .get_charts_prices(coin_id.as_str(), period, currency_value.as_str())
+        .get_charts(coin_id.as_str(), period, currency_value.as_str())
         .await
         .unwrap();
 
-    let response = Charts {
-        prices,
-        market_caps: vec![],
-        total_volumes: vec![],
-    };
-
-    Json(response)
+    Json(charts)
 }
@@ -1,59 +1,189 @@
 extern crate rocket;
+use crate::device_auth::{
+    key_rotation_message, verify_signature, verifying_key, AuthenticatedDevice,
+    DeviceAuthenticator, KeylessDevice, SignedJson,
+};
 use crate::DevicesClient;
 use primitives::device::Device;
+use primitives::DevicePublicKey;
 use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::tokio::sync::Mutex;
 use rocket::State;
 
+// push tokens are only ever sent by the device, responses leave them out
+fn device_response(device: Device) -> Json<Device> {
+    Json(Device {
+        token: String::new(),
+        ..device
+    })
+}
+
+// the first key is bound here or through a pushed challenge, never by a plain update
 #[post("/devices", format = "json", data = "<device>")]
 pub async fn add_device(
     device: Json<Device>,
     client: &State<Mutex<DevicesClient>>,
-) -> Json<Device> {
+) -> Result<Json<Device>, Status> {
+    if let Some(public_key) = &device.public_key {
+        verifying_key(public_key).map_err(|_| Status::BadRequest)?;
+    }
     let device = client.lock().await.add_device(device.0).unwrap();
-    Json(device)
+    Ok(device_response(device))
 }
 
 #[get("/devices/<device_id>")]
 pub async fn get_device(
-    device_id: &str,
+    #[allow(unused)] device_id: &str,
+    device: AuthenticatedDevice,
     client: &State<Mutex<DevicesClient>>,
 ) -> Result<Json<Device>, Status> {
-    let device = client.lock().await.get_device(device_id);
+    let device = client.lock().await.get_device(&device.device_id);
 
     match device {
-        Ok(device) => Ok(Json(device)),
+        Ok(device) => Ok(device_response(device)),
         Err(_) => Err(Status::NotFound),
     }
 }
 
+// unsigned devices keep their push token, it is what their first key challenge is sent to
 #[put("/devices/<device_id>", format = "json", data = "<device>")]
 pub async fn update_device(
-    device: Json<Device>,
+    device: SignedJson<Device>,
+    #[allow(unused)] device_id: &str,
+    authenticated_device: AuthenticatedDevice,
+    client: &State<Mutex<DevicesClient>>,
+) -> Result<Json<Device>, Status> {
+    if device.0.id != authenticated_device.device_id {
+        return Err(Status::Forbidden);
+    }
+    let mut client = client.lock().await;
+    if !authenticated_device.is_signed {
+        let token = client
+            .get_device_token(&authenticated_device.device_id)
+            .map_err(|_| Status::NotFound)?;
+        if device.0.token != token {
+            return Err(Status::Forbidden);
+        }
+    }
+    let device = client.update_device(device.0).unwrap();
+    Ok(device_response(device))
+}
+
+// signed with the current key
+#[put(
+    "/devices/<device_id>/public_key",
+    format = "json",
+    data = "<public_key>"
+)]
+pub async fn update_device_public_key(
+    public_key: SignedJson<DevicePublicKey>,
+    #[allow(unused)] device_id: &str,
+    device: AuthenticatedDevice,
+    client: &State<Mutex<DevicesClient>>,
+) -> Result<Json<usize>, Status> {
+    if !device.is_signed {
+        return Err(Status::Forbidden);
+    }
+    let public_key = public_key.0;
+    let message = key_rotation_message(&device.device_id, &public_key.public_key);
+    verify_signature(&public_key.public_key, &message, &public_key.signature)
+        .map_err(|_| Status::BadRequest)?;
+
+    client
+        .lock()
+        .await
+        .update_device_public_key(&device.device_id, &public_key.public_key)
+        .map(Json)
+        .map_err(|_| Status::InternalServerError)
+}
+
+// pushes the challenge a device registered without a key answers in add_device_public_key
+#[post("/devices/<device_id>/public_key/challenge")]
+pub async fn create_device_public_key_challenge(
     #[allow(unused)] device_id: &str,
+    device: KeylessDevice,
     client: &State<Mutex<DevicesClient>>,
-) -> Json<Device> {
-    let device = client.lock().await.update_device(device.0).unwrap();
-    Json(device)
+    authenticator: &State<Mutex<DeviceAuthenticator>>,
+) -> Result<Json<bool>, Status> {
+    let mut authenticator = authenticator.lock().await.clone();
+    let challenge = authenticator
+        .create_key_challenge(&device.device_id)
+        .await
+        .map_err(|_| Status::ServiceUnavailable)?;
+    client
+        .lock()
+        .await
+        .send_key_challenge(&device.device_id, &challenge)
+        .await
+        .map(Json)
+        .map_err(|_| Status::InternalServerError)
+}
+
+// first key of a device registered without one, proven with the pushed challenge
+#[post(
+    "/devices/<device_id>/public_key",
+    format = "json",
+    data = "<public_key>"
+)]
+pub async fn add_device_public_key(
+    public_key: SignedJson<DevicePublicKey>,
+    #[allow(unused)] device_id: &str,
+    device: KeylessDevice,
+    client: &State<Mutex<DevicesClient>>,
+    authenticator: &State<Mutex<DeviceAuthenticator>>,
+) -> Result<Json<usize>, Status> {
+    let public_key = public_key.0;
+    let message = key_rotation_message(&device.device_id, &public_key.public_key);
+    verify_signature(&public_key.public_key, &message, &public_key.signature)
+        .map_err(|_| Status::BadRequest)?;
+
+    let mut authenticator = authenticator.lock().await.clone();
+    let challenge = authenticator
+        .take_key_challenge(&device.device_id)
+        .await
+        .map_err(|_| Status::ServiceUnavailable)?;
+    match (challenge, public_key.challenge) {
+        (Some(challenge), Some(answer)) if challenge == answer => {}
+        _ => return Err(Status::Forbidden),
+    }
+
+    match client
+        .lock()
+        .await
+        .bind_device_public_key(&device.device_id, &public_key.public_key)
+    {
+        Ok(true) => Ok(Json(1)),
+        Ok(false) => Err(Status::Conflict),
+        Err(_) => Err(Status::InternalServerError),
+    }
 }
 
 #[post("/devices/<device_id>/push-notification")]
 pub async fn send_push_notification_device(
-    device_id: &str,
+    #[allow(unused)] device_id: &str,
+    device: AuthenticatedDevice,
     client: &State<Mutex<DevicesClient>>,
 ) -> Json<bool> {
     let result = client
         .lock()
         .await
-        .send_push_notification_device(device_id)
+        .send_push_notification_device(&device.device_id)
         .await
         .unwrap();
     Json(result)
 }
 
 #[delete("/devices/<device_id>")]
-pub async fn delete_device(device_id: &str, client: &State<Mutex<DevicesClient>>) -> Json<usize> {
-    let result: usize = client.lock().await.delete_device(device_id).unwrap();
+pub async fn delete_device(
+    #[allow(unused)] device_id: &str,
+    device: AuthenticatedDevice,
+    client: &State<Mutex<DevicesClient>>,
+) -> Json<usize> {
+    let result: usize = client
+        .lock()
+        .await
+        .delete_device(&device.device_id)
+        .unwrap();
     Json(result)
 }
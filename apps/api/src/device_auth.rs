@@ -0,0 +1,544 @@
+extern crate rocket;
+
+use std::{error::Error, fmt};
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::Utc;
+use p256::{
+    ecdsa::{signature::Verifier, Signature, VerifyingKey},
+    pkcs8::DecodePublicKey,
+};
+use rocket::data::{self, FromData, Limits};
+use rocket::http::Status;
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest, Request};
+use rocket::tokio::sync::Mutex;
+use rocket::Data;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use cacher::CacherClient;
+
+use crate::DevicesClient;
+
+pub const DEVICE_TIMESTAMP_HEADER: &str = "X-Device-Timestamp";
+pub const DEVICE_SIGNATURE_HEADER: &str = "X-Device-Signature";
+pub const DEVICE_BODY_HASH_HEADER: &str = "X-Device-Body-Hash";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceAuthError {
+    UnknownDevice,
+    MissingPublicKey,
+    PublicKeyBound,
+    MissingSignature,
+    InvalidPublicKey,
+    InvalidSignature,
+    Expired,
+    Replayed,
+    ReplayUnavailable,
+    BodyMismatch,
+    InvalidBody,
+}
+
+impl fmt::Display for DeviceAuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::UnknownDevice => "unknown device",
+            Self::MissingPublicKey => "device has no public key",
+            Self::PublicKeyBound => "device already has a public key",
+            Self::MissingSignature => "missing request signature",
+            Self::InvalidPublicKey => "invalid public key",
+            Self::InvalidSignature => "invalid request signature",
+            Self::Expired => "request timestamp outside of the allowed window",
+            Self::Replayed => "request was already used",
+            Self::ReplayUnavailable => "request could not be checked for replays",
+            Self::BodyMismatch => "request body does not match the signature",
+            Self::InvalidBody => "invalid request body",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for DeviceAuthError {}
+
+// request parts covered by the device signature
+#[derive(Debug, Clone)]
+pub struct SignedRequest {
+    pub method: String,
+    // path with the query string
+    pub path: String,
+    pub timestamp: i64,
+    pub body_hash: String,
+    pub signature: String,
+}
+
+impl SignedRequest {
+    pub fn message(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}",
+            self.method, self.path, self.timestamp, self.body_hash
+        )
+    }
+}
+
+pub fn body_hash(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+pub fn key_rotation_message(device_id: &str, public_key: &str) -> String {
+    format!("{}\n{}", device_id, public_key)
+}
+
+fn key_challenge_key(device_id: &str) -> String {
+    format!("device_auth:challenge:{}", device_id)
+}
+
+// result of claiming a signed message, false when it was already claimed
+fn replay_outcome<E>(claimed: Result<bool, E>) -> Result<(), DeviceAuthError> {
+    match claimed {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(DeviceAuthError::Replayed),
+        Err(_) => Err(DeviceAuthError::ReplayUnavailable),
+    }
+}
+
+// platform keystore keys, base64 of SEC1 (iOS) or SubjectPublicKeyInfo DER (Android)
+pub fn verifying_key(public_key: &str) -> Result<VerifyingKey, DeviceAuthError> {
+    let bytes = general_purpose::STANDARD
+        .decode(public_key)
+        .map_err(|_| DeviceAuthError::InvalidPublicKey)?;
+    VerifyingKey::from_sec1_bytes(&bytes)
+        .or_else(|_| VerifyingKey::from_public_key_der(&bytes))
+        .map_err(|_| DeviceAuthError::InvalidPublicKey)
+}
+
+// base64 of a DER or raw (r || s) ECDSA P-256 SHA-256 signature
+pub fn verify_signature(
+    public_key: &str,
+    message: &str,
+    signature: &str,
+) -> Result<(), DeviceAuthError> {
+    let key = verifying_key(public_key)?;
+    let bytes = general_purpose::STANDARD
+        .decode(signature)
+        .map_err(|_| DeviceAuthError::InvalidSignature)?;
+    let signature = Signature::from_der(&bytes)
+        .or_else(|_| Signature::from_slice(&bytes))
+        .map_err(|_| DeviceAuthError::InvalidSignature)?;
+    key.verify(message.as_bytes(), &signature)
+        .map_err(|_| DeviceAuthError::InvalidSignature)
+}
+
+// cloned out of the managed state before any redis call, the lock is never held across one
+#[derive(Clone)]
+pub struct DeviceAuthenticator {
+    required: bool,
+    window: i64,
+    // signed messages seen by every api instance, ecdsa signatures are malleable so messages are tracked instead
+    cacher_client: CacherClient,
+}
+
+impl DeviceAuthenticator {
+    pub fn new(required: bool, window: u64, cacher_client: CacherClient) -> Self {
+        Self {
+            required,
+            window: window as i64,
+            cacher_client,
+        }
+    }
+
+    // timestamp and signature of the request, returns the key its message is tracked with
+    pub fn check(
+        &self,
+        public_key: &str,
+        request: &SignedRequest,
+        now: i64,
+    ) -> Result<String, DeviceAuthError> {
+        if (now - request.timestamp).abs() > self.window {
+            return Err(DeviceAuthError::Expired);
+        }
+        let message = request.message();
+        verify_signature(public_key, &message, &request.signature)?;
+        Ok(format!(
+            "device_auth:{}",
+            body_hash(format!("{}\n{}", public_key, message).as_bytes())
+        ))
+    }
+
+    pub async fn verify(
+        &mut self,
+        public_key: &str,
+        request: &SignedRequest,
+        now: i64,
+    ) -> Result<(), DeviceAuthError> {
+        let key = self.check(public_key, request, now)?;
+        // timestamps are accepted on both sides of now, messages expire once outside of the window
+        let claimed = self
+            .cacher_client
+            .set_value_if_missing_with_expiration(
+                &key,
+                request.timestamp.to_string(),
+                self.window * 2,
+            )
+            .await
+            .map_err(|err| err.to_string());
+        replay_outcome(claimed)
+    }
+
+    // pushed to the device token, only the device can read it back to bind its first key
+    pub async fn create_key_challenge(
+        &mut self,
+        device_id: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let challenge = hex::encode(rand::random::<[u8; 32]>());
+        self.cacher_client
+            .set_value_with_expiration(
+                &key_challenge_key(device_id),
+                challenge.clone(),
+                self.window,
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(challenge)
+    }
+
+    // a challenge is checked once, a wrong answer needs a new one
+    pub async fn take_key_challenge(
+        &mut self,
+        device_id: &str,
+    ) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+        let key = key_challenge_key(device_id);
+        let challenge = self.cacher_client.get_value(&key).await.ok();
+        if challenge.is_some() {
+            self.cacher_client
+                .delete_value(&key)
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(challenge)
+    }
+}
+
+// body hash a device guard verified, checked again by SignedJson once the body is read
+enum VerifiedBody {
+    Missing,
+    Unsigned,
+    Hash(String),
+}
+
+// device owning the `<device_id>` route segment, verified against its public key
+pub struct AuthenticatedDevice {
+    pub device_id: String,
+    // false for devices without a key while keys are not required
+    pub is_signed: bool,
+}
+
+// device registered without a public key, only allowed to bind its first one
+pub struct KeylessDevice {
+    pub device_id: String,
+}
+
+fn device_id_segment(request: &Request<'_>) -> Option<String> {
+    let index = request
+        .route()?
+        .uri
+        .unmounted()
+        .path()
+        .segments()
+        .position(|x| x == "<device_id>")?;
+    request.routed_segment(index).map(|x| x.to_string())
+}
+
+fn signed_request(request: &Request<'_>) -> Option<SignedRequest> {
+    let headers = request.headers();
+    Some(SignedRequest {
+        method: request.method().as_str().to_string(),
+        path: request.uri().to_string(),
+        timestamp: headers.get_one(DEVICE_TIMESTAMP_HEADER)?.parse().ok()?,
+        body_hash: headers
+            .get_one(DEVICE_BODY_HASH_HEADER)
+            .map(|x| x.to_lowercase())
+            .unwrap_or(body_hash(&[])),
+        signature: headers.get_one(DEVICE_SIGNATURE_HEADER)?.to_string(),
+    })
+}
+
+// device of the `<device_id>` route segment with its public key
+async fn device_public_key(
+    request: &Request<'_>,
+) -> Result<(String, Option<String>), (Status, DeviceAuthError)> {
+    let devices = request
+        .rocket()
+        .state::<Mutex<DevicesClient>>()
+        .ok_or((Status::InternalServerError, DeviceAuthError::UnknownDevice))?;
+    let device_id =
+        device_id_segment(request).ok_or((Status::Unauthorized, DeviceAuthError::UnknownDevice))?;
+    let public_key = devices
+        .lock()
+        .await
+        .get_device_public_key(&device_id)
+        .map_err(|_| (Status::NotFound, DeviceAuthError::UnknownDevice))?;
+    Ok((device_id, public_key))
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthenticatedDevice {
+    type Error = DeviceAuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let mut authenticator = match request.rocket().state::<Mutex<DeviceAuthenticator>>() {
+            Some(authenticator) => authenticator.lock().await.clone(),
+            None => {
+                return Outcome::Error((
+                    Status::InternalServerError,
+                    DeviceAuthError::UnknownDevice,
+                ))
+            }
+        };
+        let (device_id, public_key) = match device_public_key(request).await {
+            Ok(device) => device,
+            Err(err) => return Outcome::Error(err),
+        };
+        // devices without a key bind one through KeylessDevice routes
+        let public_key = match public_key {
+            Some(public_key) => public_key,
+            None if !authenticator.required => {
+                request.local_cache(|| VerifiedBody::Unsigned);
+                return Outcome::Success(AuthenticatedDevice {
+                    device_id,
+                    is_signed: false,
+                });
+            }
+            None => {
+                return Outcome::Error((Status::Unauthorized, DeviceAuthError::MissingPublicKey))
+            }
+        };
+        let signed_request = match signed_request(request) {
+            Some(signed_request) => signed_request,
+            None => {
+                return Outcome::Error((Status::Unauthorized, DeviceAuthError::MissingSignature))
+            }
+        };
+        match authenticator
+            .verify(&public_key, &signed_request, Utc::now().timestamp())
+            .await
+        {
+            Ok(_) => {
+                request.local_cache(|| VerifiedBody::Hash(signed_request.body_hash.clone()));
+                Outcome::Success(AuthenticatedDevice {
+                    device_id,
+                    is_signed: true,
+                })
+            }
+            Err(DeviceAuthError::ReplayUnavailable) => Outcome::Error((
+                Status::ServiceUnavailable,
+                DeviceAuthError::ReplayUnavailable,
+            )),
+            Err(err) => Outcome::Error((Status::Unauthorized, err)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for KeylessDevice {
+    type Error = DeviceAuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match device_public_key(request).await {
+            Ok((device_id, None)) => {
+                request.local_cache(|| VerifiedBody::Unsigned);
+                Outcome::Success(KeylessDevice { device_id })
+            }
+            Ok((_, Some(_))) => Outcome::Error((Status::Conflict, DeviceAuthError::PublicKeyBound)),
+            Err(err) => Outcome::Error(err),
+        }
+    }
+}
+
+// json body of a device scoped route, must match the hash signed for AuthenticatedDevice
+pub struct SignedJson<T>(pub T);
+
+#[rocket::async_trait]
+impl<'r, T: DeserializeOwned> FromData<'r> for SignedJson<T> {
+    type Error = DeviceAuthError;
+
+    async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let limit = request.limits().get("json").unwrap_or(Limits::JSON);
+        let body = match data.open(limit).into_bytes().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => {
+                return Outcome::Error((Status::PayloadTooLarge, DeviceAuthError::InvalidBody))
+            }
+            Err(_) => return Outcome::Error((Status::BadRequest, DeviceAuthError::InvalidBody)),
+        };
+        match request.local_cache(|| VerifiedBody::Missing) {
+            VerifiedBody::Missing => {
+                return Outcome::Error((Status::Unauthorized, DeviceAuthError::MissingSignature))
+            }
+            VerifiedBody::Unsigned => {}
+            VerifiedBody::Hash(hash) => {
+                if *hash != body_hash(&body) {
+                    return Outcome::Error((Status::Unauthorized, DeviceAuthError::BodyMismatch));
+                }
+            }
+        }
+        match serde_json::from_slice(&body) {
+            Ok(value) => Outcome::Success(SignedJson(value)),
+            Err(_) => Outcome::Error((Status::UnprocessableEntity, DeviceAuthError::InvalidBody)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::{signature::Signer, SigningKey};
+
+    fn signing_key(value: u8) -> SigningKey {
+        SigningKey::from_slice(&[value; 32]).unwrap()
+    }
+
+    fn public_key(key: &SigningKey) -> String {
+        general_purpose::STANDARD.encode(key.verifying_key().to_encoded_point(false).as_bytes())
+    }
+
+    fn authenticator() -> DeviceAuthenticator {
+        DeviceAuthenticator::new(true, 300, CacherClient::new("redis://localhost"))
+    }
+
+    fn sign(key: &SigningKey, message: &str) -> String {
+        let signature: Signature = key.sign(message.as_bytes());
+        general_purpose::STANDARD.encode(signature.to_der().as_bytes())
+    }
+
+    fn request(key: &SigningKey, path: &str, body: &[u8], timestamp: i64) -> SignedRequest {
+        let mut request = SignedRequest {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            timestamp,
+            body_hash: body_hash(body),
+            signature: String::new(),
+        };
+        request.signature = sign(key, &request.message());
+        request
+    }
+
+    #[test]
+    fn test_check() {
+        let key = signing_key(1);
+        let request = request(&key, "/v1/subscriptions/1", b"[]", 1718000000);
+
+        assert!(authenticator()
+            .check(&public_key(&key), &request, 1718000100)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_check_replay_key() {
+        let key = signing_key(1);
+        let authenticator = authenticator();
+        let other = request(&key, "/v1/subscriptions/2", b"[]", 1718000000);
+        let request = request(&key, "/v1/subscriptions/1", b"[]", 1718000000);
+        let replay_key = authenticator
+            .check(&public_key(&key), &request, 1718000000)
+            .unwrap();
+
+        assert_eq!(
+            authenticator.check(&public_key(&key), &request, 1718000010),
+            Ok(replay_key.clone())
+        );
+        assert_ne!(
+            authenticator.check(&public_key(&key), &other, 1718000010),
+            Ok(replay_key)
+        );
+        assert_eq!(
+            authenticator.check(&public_key(&key), &request, 1718000400),
+            Err(DeviceAuthError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_check_tampered() {
+        let key = signing_key(1);
+        let authenticator = authenticator();
+        let signed = request(&key, "/v1/subscriptions/1", b"[]", 1718000000);
+
+        let mut tampered = signed.clone();
+        tampered.path = "/v1/subscriptions/2".to_string();
+        assert_eq!(
+            authenticator.check(&public_key(&key), &tampered, 1718000000),
+            Err(DeviceAuthError::InvalidSignature)
+        );
+
+        let mut tampered = signed.clone();
+        tampered.body_hash = body_hash(b"[{}]");
+        assert_eq!(
+            authenticator.check(&public_key(&key), &tampered, 1718000000),
+            Err(DeviceAuthError::InvalidSignature)
+        );
+
+        let mut tampered = signed.clone();
+        tampered.timestamp += 1;
+        assert_eq!(
+            authenticator.check(&public_key(&key), &tampered, 1718000000),
+            Err(DeviceAuthError::InvalidSignature)
+        );
+
+        assert_eq!(
+            authenticator.check(&public_key(&signing_key(2)), &signed, 1718000000),
+            Err(DeviceAuthError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_formats() {
+        let key = signing_key(1);
+        let signature: Signature = key.sign(b"message");
+        let raw = general_purpose::STANDARD.encode(signature.to_bytes());
+        let der_public_key = general_purpose::STANDARD.encode(
+            p256::pkcs8::EncodePublicKey::to_public_key_der(key.verifying_key())
+                .unwrap()
+                .as_bytes(),
+        );
+
+        assert_eq!(verify_signature(&public_key(&key), "message", &raw), Ok(()));
+        assert_eq!(
+            verify_signature(&der_public_key, "message", &sign(&key, "message")),
+            Ok(())
+        );
+        assert_eq!(
+            verify_signature("invalid", "message", &raw),
+            Err(DeviceAuthError::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_replay_outcome() {
+        assert_eq!(replay_outcome::<String>(Ok(true)), Ok(()));
+        // message already cached by an earlier request
+        assert_eq!(
+            replay_outcome::<String>(Ok(false)),
+            Err(DeviceAuthError::Replayed)
+        );
+        assert_eq!(
+            replay_outcome(Err("connection refused".to_string())),
+            Err(DeviceAuthError::ReplayUnavailable)
+        );
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let key = signing_key(1);
+        let new_key = signing_key(2);
+        let message = key_rotation_message("1", &public_key(&new_key));
+
+        assert_eq!(
+            verify_signature(&public_key(&new_key), &message, &sign(&new_key, &message)),
+            Ok(())
+        );
+        assert_eq!(
+            verify_signature(&public_key(&new_key), &message, &sign(&key, &message)),
+            Err(DeviceAuthError::InvalidSignature)
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::error::Error;
 
 use api_connector::pusher::model::Notification;
 use api_connector::PusherClient;
+use primitives::{PushNotificationDeviceKeyChallenge, PushNotificationTypes};
 use storage::{models::UpdateDevice, DatabaseClient};
 
 pub struct DevicesClient {
@@ -35,15 +36,54 @@ impl DevicesClient {
         Ok(device.as_primitive())
     }
 
+    // keys are only changed through rotation and versions through subscription sync,
+    // None leaves the stored values untouched
     pub fn update_device(
         &mut self,
         device: primitives::device::Device,
     ) -> Result<primitives::device::Device, Box<dyn Error>> {
-        let update_device = UpdateDevice::from_primitive(device);
+        let update_device = UpdateDevice {
+            subscriptions_version: None,
+            public_key: None,
+            ..UpdateDevice::from_primitive(device)
+        };
         let device = self.database.update_device(update_device)?;
         Ok(device.as_primitive())
     }
 
+    pub fn get_device_public_key(
+        &mut self,
+        device_id: &str,
+    ) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.database.get_device_public_key(device_id)?)
+    }
+
+    pub fn get_device_token(&mut self, device_id: &str) -> Result<String, Box<dyn Error>> {
+        Ok(self.database.get_device_token(device_id)?)
+    }
+
+    pub fn update_device_public_key(
+        &mut self,
+        device_id: &str,
+        public_key: &str,
+    ) -> Result<usize, Box<dyn Error>> {
+        Ok(self
+            .database
+            .update_device_public_key(device_id, public_key)?)
+    }
+
+    // false when a key was bound in the meantime
+    pub fn bind_device_public_key(
+        &mut self,
+        device_id: &str,
+        public_key: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        Ok(self
+            .database
+            .bind_device_public_key(device_id, public_key)?
+            > 0)
+    }
+
     pub fn get_topic(&self, platform: primitives::Platform) -> Option<String> {
         match platform {
             primitives::Platform::Android => None,
@@ -69,6 +109,30 @@ impl DevicesClient {
         Ok(result.counts > 0)
     }
 
+    // the app answers with the challenge when binding its first key
+    pub async fn send_key_challenge(
+        &mut self,
+        device_id: &str,
+        challenge: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        let device = self.get_device(device_id)?;
+        let device_token = self.database.get_device_token(device_id)?;
+        let data = PushNotificationDeviceKeyChallenge {
+            notification_type: PushNotificationTypes::DeviceKeyChallenge,
+            data: challenge.to_string(),
+        };
+        let notification = Notification {
+            tokens: vec![device_token],
+            platform: device.platform.as_i32(),
+            title: String::new(),
+            message: String::new(),
+            topic: self.get_topic(device.platform),
+            data: Some(serde_json::to_value(data)?),
+        };
+        let result = self.pusher.push(notification).await?;
+        Ok(result.counts > 0)
+    }
+
     pub fn delete_device(&mut self, device_id: &str) -> Result<usize, Box<dyn Error>> {
         Ok(self.database.delete_device(device_id)?)
     }
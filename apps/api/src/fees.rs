@@ -0,0 +1,32 @@
+extern crate rocket;
+use std::str::FromStr;
+
+use crate::fees_client::FeesClient;
+use crate::response::ResponseError;
+use primitives::{Chain, FeeEstimate};
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::tokio::sync::Mutex;
+use rocket::State;
+
+#[get("/fees/<chain>")]
+pub async fn get_fees(
+    chain: &str,
+    client: &State<Mutex<FeesClient>>,
+) -> Result<Json<FeeEstimate>, Custom<Json<ResponseError>>> {
+    let chain = Chain::from_str(chain).map_err(|_| {
+        ResponseError::status(Status::BadRequest, format!("invalid chain: {}", chain))
+    })?;
+    client
+        .lock()
+        .await
+        .get_fee_estimate(chain)
+        .await
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))?
+        .map(Json)
+        .ok_or(ResponseError::status(
+            Status::BadRequest,
+            format!("fee estimation is not supported on chain: {}", chain),
+        ))
+}
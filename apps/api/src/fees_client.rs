@@ -0,0 +1,55 @@
+use std::error::Error;
+
+use cacher::CacherClient;
+use primitives::{Chain, FeeEstimate};
+use settings::Settings;
+use settings_chain::ProviderFactory;
+
+pub struct FeesClient {
+    settings: Settings,
+    cacher: CacherClient,
+    cache_ttl: i64,
+}
+
+impl FeesClient {
+    pub async fn new(settings: Settings, redis_url: &str, cache_ttl: i64) -> Self {
+        let cacher = CacherClient::new(redis_url);
+        Self {
+            settings,
+            cacher,
+            cache_ttl,
+        }
+    }
+
+    // fees move every block, cache briefly to shield the nodes from bursts
+    pub async fn get_fee_estimate(
+        &mut self,
+        chain: Chain,
+    ) -> Result<Option<FeeEstimate>, Box<dyn Error + Send + Sync>> {
+        let url = ProviderFactory::url(chain, &self.settings);
+        let Some(provider) = ProviderFactory::new_fee_provider(chain, url) else {
+            return Ok(None);
+        };
+
+        let key = format!("fees:{}", chain.as_ref());
+        if let Some(estimate) = self.get_cached_estimate(&key).await {
+            return Ok(Some(estimate));
+        }
+
+        let rates = provider.get_fee_rates().await?;
+        let estimate = FeeEstimate { chain, rates };
+
+        if let Ok(value) = serde_json::to_string(&estimate) {
+            let _ = self
+                .cacher
+                .set_value_with_expiration(&key, value, self.cache_ttl)
+                .await;
+        }
+        Ok(Some(estimate))
+    }
+
+    async fn get_cached_estimate(&mut self, key: &str) -> Option<FeeEstimate> {
+        let value = self.cacher.get_value(key).await.ok()?;
+        serde_json::from_str(&value).ok()
+    }
+}
@@ -1,21 +1,34 @@
 extern crate rocket;
+use std::collections::HashMap;
+use std::str::FromStr;
+
 use fiat::client::Client as FiatProvider;
+use fiat::{FiatPusher, FiatWebhookRequest};
 use primitives::{
-    fiat_assets::FiatAssets, fiat_quote::FiatQuotes, fiat_quote_request::FiatBuyRequest,
+    fiat_assets::FiatAssets,
+    fiat_quote::FiatQuotes,
+    fiat_quote_request::{FiatBuyRequest, FiatSellRequest},
+    FiatPaymentMethod,
 };
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
 use rocket::serde::json::Json;
 use rocket::tokio::sync::Mutex;
 use rocket::State;
 
+const WEBHOOK_BODY_LIMIT: u64 = 256;
+
 // on ramp
 
-#[get("/fiat/on_ramp/quotes/<asset_id>?<amount>&<currency>&<wallet_address>&<ip_address>")]
+#[get("/fiat/on_ramp/quotes/<asset_id>?<amount>&<currency>&<wallet_address>&<ip_address>&<payment_method>")]
 pub async fn get_fiat_on_ramp_quotes(
     asset_id: String,
     amount: f64,
     currency: String,
     wallet_address: String,
     ip_address: Option<String>,
+    payment_method: Option<String>,
     ip: std::net::IpAddr,
     fiat_client: &State<Mutex<FiatProvider>>,
 ) -> Json<FiatQuotes> {
@@ -25,6 +38,7 @@ pub async fn get_fiat_on_ramp_quotes(
         currency,
         wallet_address,
         ip_address,
+        payment_method,
         ip,
         fiat_client,
     )
@@ -38,15 +52,43 @@ pub async fn get_fiat_on_ramp_assets(fiat_client: &State<Mutex<FiatProvider>>) -
 
 // off ramp
 
+#[get("/fiat/off_ramp/quotes/<asset_id>?<amount>&<currency>&<wallet_address>&<ip_address>")]
+pub async fn get_fiat_off_ramp_quotes(
+    asset_id: String,
+    amount: f64,
+    currency: String,
+    wallet_address: String,
+    ip_address: Option<String>,
+    ip: std::net::IpAddr,
+    fiat_client: &State<Mutex<FiatProvider>>,
+) -> Json<FiatQuotes> {
+    let request = FiatSellRequest {
+        asset_id,
+        ip_address: ip_address.unwrap_or(ip.to_string()),
+        crypto_amount: amount,
+        fiat_currency: currency,
+        wallet_address,
+    };
+    let quotes = fiat_client.lock().await.get_sell_quotes(request).await;
+    match quotes {
+        Ok(value) => Json(value),
+        Err(_) => Json(FiatQuotes {
+            quotes: vec![],
+            excluded: vec![],
+        }),
+    }
+}
+
 // old routes. switched to use on_ramp and off_ramp routes
 
-#[get("/fiat/quotes/<asset_id>?<amount>&<currency>&<wallet_address>&<ip_address>")]
+#[get("/fiat/quotes/<asset_id>?<amount>&<currency>&<wallet_address>&<ip_address>&<payment_method>")]
 pub async fn get_fiat_quotes(
     asset_id: String,
     amount: f64,
     currency: String,
     wallet_address: String,
     ip_address: Option<String>,
+    payment_method: Option<String>,
     ip: std::net::IpAddr,
     fiat_client: &State<Mutex<FiatProvider>>,
 ) -> Json<FiatQuotes> {
@@ -56,11 +98,15 @@ pub async fn get_fiat_quotes(
         fiat_amount: amount,
         fiat_currency: currency,
         wallet_address,
+        payment_method: payment_method.and_then(|x| FiatPaymentMethod::from_str(&x).ok()),
     };
     let quotes = fiat_client.lock().await.get_quotes(request).await;
     match quotes {
-        Ok(value) => Json(FiatQuotes { quotes: value }),
-        Err(_) => Json(FiatQuotes { quotes: vec![] }),
+        Ok(value) => Json(value),
+        Err(_) => Json(FiatQuotes {
+            quotes: vec![],
+            excluded: vec![],
+        }),
     }
 }
 
@@ -70,22 +116,103 @@ pub async fn get_fiat_assets(fiat_client: &State<Mutex<FiatProvider>>) -> Json<F
     Json(assets)
 }
 
+// signature headers and path are needed to verify provider webhooks against the raw body
+pub struct WebhookHeaders {
+    path: String,
+    headers: HashMap<String, String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebhookHeaders {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let headers = request
+            .headers()
+            .iter()
+            .map(|header| (header.name().to_string(), header.value().to_string()))
+            .collect();
+        Outcome::Success(WebhookHeaders {
+            path: request.uri().path().to_string(),
+            headers,
+        })
+    }
+}
+
 #[post("/fiat/webhooks/<provider>", format = "json", data = "<data>")]
 pub async fn create_fiat_webhook(
     provider: &str,
-    data: Json<serde_json::Value>,
+    headers: WebhookHeaders,
+    data: Data<'_>,
     fiat_client: &State<Mutex<FiatProvider>>,
-) -> Json<bool> {
+    fiat_pusher: &State<FiatPusher>,
+) -> Result<Json<bool>, Status> {
+    let body = data
+        .open(WEBHOOK_BODY_LIMIT.kibibytes())
+        .into_bytes()
+        .await
+        .map_err(|_| Status::BadRequest)?;
+    if !body.is_complete() {
+        return Err(Status::PayloadTooLarge);
+    }
+    let request = FiatWebhookRequest::new(headers.path, headers.headers, body.into_inner());
+
+    let mut client = fiat_client.lock().await;
+    let data = match client.verify_fiat_webhook(provider, &request) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("webhook: {}, verification error: {}", provider, err);
+            return Err(Status::Unauthorized);
+        }
+    };
+    match client.claim_fiat_webhook(provider, &request).await {
+        Ok(true) => {}
+        Ok(false) => {
+            println!("webhook: {}, duplicate callback", provider);
+            return Ok(Json(false));
+        }
+        Err(err) => {
+            println!("webhook: {}, claim error: {}", provider, err);
+            return Err(Status::InternalServerError);
+        }
+    }
     print!(
         "webhook: {}, data: {:?}",
         provider,
-        serde_json::to_string_pretty(&data.0)
+        serde_json::to_string_pretty(&data)
     );
-    let result = fiat_client
-        .lock()
-        .await
-        .create_fiat_webhook(provider, data.into_inner())
-        .await
-        .unwrap();
-    Json(result)
+    let notifications = match client.create_fiat_webhook(provider, data).await {
+        Ok(Some(notifications)) => notifications,
+        Ok(None) => return Ok(Json(false)),
+        Err(err) => {
+            println!("webhook: {}, error: {}", provider, err);
+            if let Err(err) = client.release_fiat_webhook(provider, &request).await {
+                println!("webhook: {}, release error: {}", provider, err);
+            }
+            return Err(Status::InternalServerError);
+        }
+    };
+    drop(client);
+
+    let mut rejected = vec![];
+    for notification in notifications {
+        let device_id = notification.device.id.clone();
+        match fiat_pusher.push(notification).await {
+            Ok(response) if !response.logs.is_empty() => {
+                println!("push logs: {:?}", response.logs);
+                rejected.push(device_id);
+            }
+            Ok(_) => {}
+            Err(err) => println!("webhook: {}, push error: {}", provider, err),
+        }
+    }
+    if !rejected.is_empty() {
+        let mut client = fiat_client.lock().await;
+        for device_id in rejected {
+            if let Err(err) = client.disable_push(&device_id) {
+                println!("webhook: {}, disable push error: {}", provider, err);
+            }
+        }
+    }
+    Ok(Json(true))
 }
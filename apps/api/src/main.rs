@@ -2,11 +2,17 @@
 extern crate rocket;
 mod asset;
 mod asset_client;
+mod balances;
+mod balances_client;
+mod broadcast_client;
 mod charts;
 mod config;
 mod config_client;
 mod device;
+mod device_auth;
 mod device_client;
+mod fees;
+mod fees_client;
 mod fiat_quotes;
 mod metrics;
 mod metrics_client;
@@ -17,10 +23,15 @@ mod node;
 mod node_client;
 mod parser;
 mod parser_client;
+mod portfolio;
+mod price_alert;
+mod price_alert_client;
 mod prices;
 mod response;
 mod scan;
 mod scan_client;
+mod simulation;
+mod simulation_client;
 mod status;
 mod subscription;
 mod subscription_client;
@@ -31,27 +42,38 @@ mod transaction_client;
 
 use api_connector::PusherClient;
 use asset_client::AssetsClient;
+use balances_client::BalancesClient;
+use broadcast_client::BroadcastClient;
+use cacher::CacherClient;
 use charter::client::ChartsClient;
+use charter::export_client::ExportClient;
+use charter::portfolio_client::PortfolioClient;
 use config_client::Client as ConfigClient;
+use device_auth::DeviceAuthenticator;
 use device_client::DevicesClient;
+use fees_client::FeesClient;
 use fiat::client::Client as FiatProvider;
-use fiat::FiatProviderFactory;
+use fiat::{FiatProviderFactory, FiatPusher, FiatRouter};
 use metrics_client::MetricsClient;
 use name_resolver::client::Client as NameClient;
 use name_resolver::NameProviderFactory;
 use nft_client::NFTClient;
 use node_client::Client as NodeClient;
 use parser_client::ParserClient;
+use price_alert_client::PriceAlertsClient;
 use pricer::client::PriceClient;
+use primitives::{Chain, ChainType};
 use rocket::fairing::AdHoc;
 use rocket::tokio::sync::Mutex;
 use rocket::{Build, Rocket};
 use scan_client::ScanClient;
 use settings::Settings;
+use settings_chain::ProviderFactory;
+use simulation_client::SimulationClient;
 use storage::DatabaseClient;
 use subscription_client::SubscriptionsClient;
 use swap_client::SwapClient;
-use swapper::{Swapper, SwapperClientConfiguration, SwapperConfiguration};
+use swapper::{RateLimiter, Swapper, SwapperClientConfiguration, SwapperConfiguration};
 use transaction_client::TransactionsClient;
 
 async fn rocket(settings: Settings) -> Rocket<Build> {
@@ -63,6 +85,8 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
     let settings_clone = settings.clone();
     let price_client = PriceClient::new(redis_url, postgres_url);
     let charts_client = ChartsClient::new(postgres_url, &settings.clickhouse.url);
+    let portfolio_client = PortfolioClient::new(postgres_url, &settings.clickhouse.url);
+    let export_client = ExportClient::new(postgres_url, &settings.clickhouse.url);
     let node_client = NodeClient::new(database_client).await;
     let config_client = ConfigClient::new(postgres_url).await;
     let providers = NameProviderFactory::create_providers(settings_clone.clone());
@@ -71,8 +95,25 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
     let pusher_client = PusherClient::new(settings.pusher.url);
     let devices_client =
         DevicesClient::new(postgres_url, pusher_client, settings.pusher.ios.topic).await;
-    let transactions_client = TransactionsClient::new(postgres_url).await;
-    let subscriptions_client = SubscriptionsClient::new(postgres_url).await;
+    let device_authenticator = DeviceAuthenticator::new(
+        settings.api.device_auth.required,
+        settings.api.device_auth.window,
+        CacherClient::new(redis_url),
+    );
+    let transactions_client =
+        TransactionsClient::new(postgres_url, redis_url, settings_clone.clone()).await;
+    let broadcast_client = BroadcastClient::new(postgres_url, settings_clone.clone()).await;
+    let balances_client = BalancesClient::new(settings_clone.clone()).await;
+    let fees_client =
+        FeesClient::new(settings_clone.clone(), redis_url, settings.api.fees.ttl).await;
+    let simulation_client = SimulationClient::new(postgres_url, settings_clone.clone()).await;
+    let subscriptions_client = SubscriptionsClient::new(
+        postgres_url,
+        settings.api.subscriptions.unverified_limit,
+        settings.api.subscriptions.unverified_window,
+    )
+    .await;
+    let price_alerts_client = PriceAlertsClient::new(postgres_url).await;
     let metrics_client = MetricsClient::new(postgres_url).await;
     let scan_client = ScanClient::new(postgres_url).await;
     let parser_client = ParserClient::new(settings_clone.clone()).await;
@@ -102,11 +143,39 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
             fee_percent: settings.swap.aftermath.fee.percent,
             fee_address: settings.swap.aftermath.fee.address,
         },
+        nodes: Chain::all()
+            .into_iter()
+            .filter(|chain| chain.chain_type() == ChainType::Ethereum)
+            .map(|chain| {
+                (
+                    chain,
+                    ProviderFactory::url(chain, &settings_clone).to_string(),
+                )
+            })
+            .collect(),
     };
     let swapper_client = Swapper::build(swapper_configuration);
-    let swap_client = SwapClient::new(postgres_url, swapper_client).await;
+    let swap_rate_limiter =
+        RateLimiter::new(settings.swap.limit.capacity, settings.swap.limit.refill);
+    let swap_client = SwapClient::new(
+        postgres_url,
+        redis_url,
+        swapper_client,
+        swap_rate_limiter,
+        settings.swap.cache.ttl,
+        settings.swap.cache.stale_ttl,
+    )
+    .await;
     let providers = FiatProviderFactory::new_providers(settings_clone.clone());
-    let fiat_client = FiatProvider::new(postgres_url, providers).await;
+    let fiat_pusher = FiatPusher::new(
+        settings_clone.pusher.url.clone(),
+        settings_clone.pusher.ios.topic.clone(),
+    );
+    let fiat_router = FiatRouter::new(
+        &settings_clone.fiat.geoip.path,
+        settings_clone.fiat.routing.ttl,
+    );
+    let fiat_client = FiatProvider::new(postgres_url, redis_url, providers, fiat_router).await;
     let nft_client = NFTClient::new(postgres_url).await;
 
     rocket::build()
@@ -121,15 +190,24 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
             },
         ))
         .manage(Mutex::new(fiat_client))
+        .manage(fiat_pusher)
         .manage(Mutex::new(price_client))
         .manage(Mutex::new(charts_client))
+        .manage(Mutex::new(portfolio_client))
         .manage(Mutex::new(node_client))
         .manage(Mutex::new(config_client))
         .manage(Mutex::new(name_client))
         .manage(Mutex::new(devices_client))
+        .manage(Mutex::new(device_authenticator))
         .manage(Mutex::new(assets_client))
         .manage(Mutex::new(subscriptions_client))
+        .manage(Mutex::new(price_alerts_client))
         .manage(Mutex::new(transactions_client))
+        .manage(Mutex::new(broadcast_client))
+        .manage(Mutex::new(balances_client))
+        .manage(Mutex::new(fees_client))
+        .manage(Mutex::new(simulation_client))
+        .manage(Mutex::new(export_client))
         .manage(Mutex::new(metrics_client))
         .manage(Mutex::new(scan_client))
         .manage(Mutex::new(parser_client))
@@ -142,10 +220,12 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
                 prices::get_price,
                 prices::get_assets_prices,
                 charts::get_charts,
+                portfolio::get_portfolio,
                 fiat_quotes::get_fiat_quotes,
                 fiat_quotes::get_fiat_assets,
                 fiat_quotes::get_fiat_on_ramp_quotes,
                 fiat_quotes::get_fiat_on_ramp_assets,
+                fiat_quotes::get_fiat_off_ramp_quotes,
                 fiat_quotes::create_fiat_webhook,
                 node::get_nodes,
                 config::get_config,
@@ -153,6 +233,9 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
                 device::add_device,
                 device::get_device,
                 device::update_device,
+                device::update_device_public_key,
+                device::create_device_public_key_challenge,
+                device::add_device_public_key,
                 device::delete_device,
                 device::send_push_notification_device,
                 asset::get_asset,
@@ -160,11 +243,24 @@ async fn rocket(settings: Settings) -> Rocket<Build> {
                 asset::get_assets_list,
                 asset::get_assets_search,
                 asset::get_assets_ids_by_device_id,
+                balances::get_balances,
+                fees::get_fees,
                 subscription::add_subscriptions,
                 subscription::get_subscriptions,
                 subscription::delete_subscriptions,
+                subscription::sync_subscriptions,
+                price_alert::get_price_alerts,
+                price_alert::add_price_alerts,
+                price_alert::delete_price_alerts,
                 transaction::get_transactions_by_device_id,
+                transaction::get_transactions_page,
+                transaction::stream_transactions,
+                transaction::add_transaction_pending,
+                transaction::broadcast_transaction,
+                transaction::broadcast_transaction_by_device_id,
+                simulation::simulate_transaction,
                 transaction::get_transactions_by_hash,
+                transaction::get_transactions_export,
                 scan::get_scan_address,
                 parser::get_parser_block,
                 parser::get_parser_block_finalize,
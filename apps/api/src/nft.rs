@@ -1,4 +1,5 @@
 extern crate rocket;
+use crate::device_auth::AuthenticatedDevice;
 use primitives::{Chain, NFTCollectible, NFTCollection};
 use rocket::{response::status::NotFound, serde::json::Json, tokio::sync::Mutex, State};
 use std::str::FromStr;
@@ -10,6 +11,7 @@ use crate::{nft_client::NFTClient, response::ResponseResults};
 #[get("/nft/collectibles_by_/<device_id>?<wallet_index>")]
 pub async fn get_nft_collections(
     device_id: &str,
+    _device: AuthenticatedDevice,
     wallet_index: i32,
     client: &State<Mutex<NFTClient>>,
 ) -> Result<Json<ResponseResults<NFTCollection>>, NotFound<String>> {
@@ -27,6 +29,7 @@ pub async fn get_nft_collections(
 #[get("/nft/collectibles_by_device_id/<device_id>/<collection_id>?<wallet_index>")]
 pub async fn get_nft_collectibles(
     device_id: &str,
+    _device: AuthenticatedDevice,
     collection_id: &str,
     wallet_index: i32,
     client: &State<Mutex<NFTClient>>,
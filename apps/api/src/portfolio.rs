@@ -0,0 +1,38 @@
+extern crate rocket;
+use crate::device_auth::AuthenticatedDevice;
+use crate::response::ResponseError;
+use charter::portfolio_client::{PortfolioClient, PortfolioError};
+use primitives::asset_price::ChartPeriod;
+use primitives::Portfolio;
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::tokio::sync::Mutex;
+use rocket::State;
+
+#[get("/portfolio/<device_id>?<wallet_index>&<period>&<currency>")]
+pub async fn get_portfolio(
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    wallet_index: i32,
+    period: Option<String>,
+    currency: Option<String>,
+    client: &State<Mutex<PortfolioClient>>,
+) -> Result<Json<Portfolio>, Custom<Json<ResponseError>>> {
+    let period = ChartPeriod::new(period.unwrap_or_default()).unwrap_or(ChartPeriod::Month);
+    client
+        .lock()
+        .await
+        .get_portfolio(device_id, wallet_index, period, currency)
+        .await
+        .map(Json)
+        .map_err(error_response)
+}
+
+fn error_response(err: PortfolioError) -> Custom<Json<ResponseError>> {
+    let status = match err {
+        PortfolioError::UnknownDevice => Status::NotFound,
+        PortfolioError::Database(_) => Status::InternalServerError,
+    };
+    ResponseError::status(status, err)
+}
@@ -0,0 +1,64 @@
+extern crate rocket;
+use crate::device_auth::{AuthenticatedDevice, SignedJson};
+use crate::price_alert_client::PriceAlertsError;
+use crate::response::ResponseError;
+use crate::PriceAlertsClient;
+use primitives::PriceAlert;
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::tokio::sync::Mutex;
+use rocket::State;
+
+#[get("/price_alerts/<device_id>")]
+pub async fn get_price_alerts(
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    client: &State<Mutex<PriceAlertsClient>>,
+) -> Result<Json<Vec<PriceAlert>>, Custom<Json<ResponseError>>> {
+    client
+        .lock()
+        .await
+        .get_price_alerts(device_id)
+        .map(Json)
+        .map_err(error_response)
+}
+
+#[delete("/price_alerts/<device_id>", format = "json", data = "<price_alerts>")]
+pub async fn delete_price_alerts(
+    price_alerts: SignedJson<Vec<PriceAlert>>,
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    client: &State<Mutex<PriceAlertsClient>>,
+) -> Result<Json<usize>, Custom<Json<ResponseError>>> {
+    client
+        .lock()
+        .await
+        .delete_price_alerts(device_id, price_alerts.0)
+        .map(Json)
+        .map_err(error_response)
+}
+
+#[post("/price_alerts/<device_id>", format = "json", data = "<price_alerts>")]
+pub async fn add_price_alerts(
+    price_alerts: SignedJson<Vec<PriceAlert>>,
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    client: &State<Mutex<PriceAlertsClient>>,
+) -> Result<Json<usize>, Custom<Json<ResponseError>>> {
+    client
+        .lock()
+        .await
+        .add_price_alerts(device_id, price_alerts.0)
+        .map(Json)
+        .map_err(error_response)
+}
+
+fn error_response(err: PriceAlertsError) -> Custom<Json<ResponseError>> {
+    let status = match err {
+        PriceAlertsError::InvalidValue => Status::BadRequest,
+        PriceAlertsError::UnknownDevice => Status::NotFound,
+        PriceAlertsError::Database(_) => Status::InternalServerError,
+    };
+    ResponseError::status(status, err)
+}
@@ -0,0 +1,112 @@
+extern crate rocket;
+use std::error::Error;
+use std::fmt;
+
+use primitives::PriceAlert;
+use storage::DatabaseClient;
+
+#[derive(Debug)]
+pub enum PriceAlertsError {
+    InvalidValue,
+    UnknownDevice,
+    Database(Box<dyn Error>),
+}
+
+impl fmt::Display for PriceAlertsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidValue => write!(f, "price alert value must be positive"),
+            Self::UnknownDevice => write!(f, "unknown device"),
+            Self::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for PriceAlertsError {}
+
+impl PriceAlertsError {
+    fn database(error: impl Error + 'static) -> Self {
+        Self::Database(Box::new(error))
+    }
+}
+
+pub struct PriceAlertsClient {
+    database: DatabaseClient,
+}
+
+impl PriceAlertsClient {
+    pub async fn new(database_url: &str) -> Self {
+        let database = DatabaseClient::new(database_url);
+        Self { database }
+    }
+
+    pub fn get_price_alerts(
+        &mut self,
+        device_id: &str,
+    ) -> Result<Vec<PriceAlert>, PriceAlertsError> {
+        let price_alerts = self
+            .database
+            .get_price_alerts_by_device_id(device_id)
+            .map_err(PriceAlertsError::database)?
+            .into_iter()
+            .flat_map(|x| x.as_primitive())
+            .collect();
+        Ok(price_alerts)
+    }
+
+    pub fn add_price_alerts(
+        &mut self,
+        device_id: &str,
+        price_alerts: Vec<PriceAlert>,
+    ) -> Result<usize, PriceAlertsError> {
+        if price_alerts
+            .iter()
+            .any(|x| !x.value.is_finite() || x.value <= 0.0)
+        {
+            return Err(PriceAlertsError::InvalidValue);
+        }
+        let device = self
+            .database
+            .get_device(device_id)
+            .map_err(|_| PriceAlertsError::UnknownDevice)?;
+        let price_alerts = price_alerts
+            .into_iter()
+            .map(|x| storage::models::PriceAlert::from_primitive(x, device.id))
+            .collect();
+        self.database
+            .add_price_alerts(price_alerts)
+            .map_err(PriceAlertsError::database)
+    }
+
+    // alerts are resolved to stored ids, values are compared in memory rather than with float equality in sql
+    pub fn delete_price_alerts(
+        &mut self,
+        device_id: &str,
+        price_alerts: Vec<PriceAlert>,
+    ) -> Result<usize, PriceAlertsError> {
+        let ids = self
+            .database
+            .get_price_alerts_by_device_id(device_id)
+            .map_err(PriceAlertsError::database)?
+            .into_iter()
+            .filter(|stored| {
+                stored
+                    .as_primitive()
+                    .is_some_and(|stored| price_alerts.iter().any(|x| is_same_alert(x, &stored)))
+            })
+            .map(|x| x.id)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        self.database
+            .delete_price_alerts(ids)
+            .map_err(PriceAlertsError::database)
+    }
+}
+
+fn is_same_alert(lhs: &PriceAlert, rhs: &PriceAlert) -> bool {
+    lhs.asset_id == rhs.asset_id
+        && lhs.alert_type == rhs.alert_type
+        && (lhs.value - rhs.value).abs() <= f64::EPSILON * lhs.value.abs().max(rhs.value.abs())
+}
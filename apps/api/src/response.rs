@@ -1,3 +1,6 @@
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,3 +20,15 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for ResponseError {
         }
     }
 }
+
+impl ResponseError {
+    pub fn new(error: impl ToString) -> Self {
+        Self {
+            error: error.to_string(),
+        }
+    }
+
+    pub fn status(status: Status, error: impl ToString) -> Custom<Json<ResponseError>> {
+        Custom(status, Json(Self::new(error)))
+    }
+}
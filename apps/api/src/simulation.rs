@@ -0,0 +1,44 @@
+extern crate rocket;
+use crate::response::ResponseError;
+use crate::simulation_client::SimulationClient;
+use std::error::Error;
+
+use primitives::{TransactionSimulation, TransactionSimulationRequest};
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::tokio::sync::Mutex;
+use rocket::State;
+
+#[post("/transactions/simulate", format = "json", data = "<request>")]
+pub async fn simulate_transaction(
+    request: Json<TransactionSimulationRequest>,
+    client: &State<Mutex<SimulationClient>>,
+) -> Result<Json<TransactionSimulation>, Custom<Json<ResponseError>>> {
+    let chain = request.chain;
+    simulate(client, request.into_inner())
+        .await
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))?
+        .map(Json)
+        .ok_or(ResponseError::status(
+            Status::BadRequest,
+            format!("simulation is not supported on chain: {}", chain),
+        ))
+}
+
+// the client is only locked to build the provider and read scan addresses, not while the node simulates
+async fn simulate(
+    client: &Mutex<SimulationClient>,
+    request: TransactionSimulationRequest,
+) -> Result<Option<TransactionSimulation>, Box<dyn Error + Send + Sync>> {
+    let chain = request.chain;
+    let Some(provider) = client.lock().await.get_provider(chain) else {
+        return Ok(None);
+    };
+
+    // providers can flag a partial simulation, scan warnings are added to those
+    let mut simulation = provider.simulate_transaction(&request).await?;
+    let warnings = client.lock().await.get_warnings(chain, &simulation);
+    simulation.warnings.extend(warnings);
+    Ok(Some(simulation))
+}
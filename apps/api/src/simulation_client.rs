@@ -0,0 +1,82 @@
+use gem_chain_rpc::SimulationProvider;
+use primitives::{
+    Chain, ScanAddress, SimulationWarning, SimulationWarningType, TransactionSimulation,
+};
+use settings::Settings;
+use settings_chain::ProviderFactory;
+use storage::DatabaseClient;
+
+pub struct SimulationClient {
+    database: DatabaseClient,
+    settings: Settings,
+}
+
+impl SimulationClient {
+    pub async fn new(database_url: &str, settings: Settings) -> Self {
+        let database = DatabaseClient::new(database_url);
+        Self { database, settings }
+    }
+
+    pub fn get_provider(&self, chain: Chain) -> Option<Box<dyn SimulationProvider>> {
+        let url = ProviderFactory::simulation_url(chain, &self.settings);
+        ProviderFactory::new_simulation_provider(chain, &url)
+    }
+
+    pub fn get_warnings(
+        &mut self,
+        chain: Chain,
+        simulation: &TransactionSimulation,
+    ) -> Vec<SimulationWarning> {
+        let mut warnings = vec![];
+        if !simulation.success {
+            warnings.push(SimulationWarning {
+                warning_type: SimulationWarningType::SimulationFailed,
+                address: None,
+            });
+        }
+
+        for approval in simulation.approvals.iter().filter(|x| x.is_unlimited) {
+            warnings.push(SimulationWarning {
+                warning_type: SimulationWarningType::UnlimitedApproval,
+                address: Some(approval.spender.clone()),
+            });
+        }
+
+        let addresses = simulation
+            .counterparties
+            .iter()
+            .chain(simulation.contract.iter());
+        for address in addresses {
+            if self
+                .get_scan_address(chain, address)
+                .is_some_and(|x| x.is_fradulent)
+            {
+                warnings.push(SimulationWarning {
+                    warning_type: SimulationWarningType::FraudulentAddress,
+                    address: Some(address.clone()),
+                });
+            }
+        }
+
+        // only an explicit record counts, most contracts are not in scan addresses
+        if let Some(contract) = &simulation.contract {
+            if self
+                .get_scan_address(chain, contract)
+                .is_some_and(|x| !x.is_verified)
+            {
+                warnings.push(SimulationWarning {
+                    warning_type: SimulationWarningType::UnverifiedContract,
+                    address: Some(contract.clone()),
+                });
+            }
+        }
+        warnings
+    }
+
+    fn get_scan_address(&mut self, chain: Chain, address: &str) -> Option<ScanAddress> {
+        self.database
+            .get_scan_address(chain, address)
+            .ok()
+            .map(|x| x.as_primitive())
+    }
+}
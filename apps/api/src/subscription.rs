@@ -1,6 +1,11 @@
 extern crate rocket;
+use crate::device_auth::{AuthenticatedDevice, SignedJson};
+use crate::response::ResponseError;
+use crate::subscription_client::SubscriptionsError;
 use crate::SubscriptionsClient;
-use primitives::Subscription;
+use primitives::{Subscription, SubscriptionsSync, SubscriptionsSyncResult};
+use rocket::http::Status;
+use rocket::response::status::Custom;
 use rocket::serde::json::Json;
 use rocket::tokio::sync::Mutex;
 use rocket::State;
@@ -8,6 +13,7 @@ use rocket::State;
 #[get("/subscriptions/<device_id>")]
 pub async fn get_subscriptions(
     device_id: &str,
+    _device: AuthenticatedDevice,
     client: &State<Mutex<SubscriptionsClient>>,
 ) -> Json<Vec<Subscription>> {
     let subscriptions = client.lock().await.get_subscriptions(device_id).unwrap();
@@ -20,8 +26,9 @@ pub async fn get_subscriptions(
     data = "<subscriptions>"
 )]
 pub async fn delete_subscriptions(
-    subscriptions: Json<Vec<Subscription>>,
+    subscriptions: SignedJson<Vec<Subscription>>,
     device_id: &str,
+    _device: AuthenticatedDevice,
     client: &State<Mutex<SubscriptionsClient>>,
 ) -> Json<usize> {
     let result = client
@@ -38,14 +45,41 @@ pub async fn delete_subscriptions(
     data = "<subscriptions>"
 )]
 pub async fn add_subscriptions(
-    subscriptions: Json<Vec<Subscription>>,
+    subscriptions: SignedJson<Vec<Subscription>>,
     device_id: &str,
+    _device: AuthenticatedDevice,
     client: &State<Mutex<SubscriptionsClient>>,
-) -> Json<usize> {
-    let subscriptions = client
+) -> Result<Json<usize>, Custom<Json<ResponseError>>> {
+    client
         .lock()
         .await
         .add_subscriptions(device_id, subscriptions.0)
-        .unwrap();
-    Json(subscriptions)
+        .map(Json)
+        .map_err(error_response)
+}
+
+// replaces all subscriptions of the device with the desired set
+#[post("/subscriptions/<device_id>/sync", format = "json", data = "<sync>")]
+pub async fn sync_subscriptions(
+    sync: SignedJson<SubscriptionsSync>,
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    client: &State<Mutex<SubscriptionsClient>>,
+) -> Result<Json<SubscriptionsSyncResult>, Custom<Json<ResponseError>>> {
+    client
+        .lock()
+        .await
+        .sync_subscriptions(device_id, sync.0)
+        .map(Json)
+        .map_err(error_response)
+}
+
+fn error_response(err: SubscriptionsError) -> Custom<Json<ResponseError>> {
+    let status = match err {
+        SubscriptionsError::InvalidProof(_, _) => Status::BadRequest,
+        SubscriptionsError::RateLimited => Status::TooManyRequests,
+        SubscriptionsError::VersionConflict(_) => Status::Conflict,
+        SubscriptionsError::Database(_) => Status::InternalServerError,
+    };
+    ResponseError::status(status, err)
 }
@@ -1,31 +1,183 @@
 extern crate rocket;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::fmt;
 
-use primitives::Subscription;
+use chrono::{Duration, Utc};
+use gem_proof::ProofError;
+use primitives::{Subscription, SubscriptionsSync, SubscriptionsSyncResult};
 use storage::DatabaseClient;
 
+#[derive(Debug)]
+pub enum SubscriptionsError {
+    InvalidProof(String, ProofError),
+    RateLimited,
+    VersionConflict(i32),
+    Database(Box<dyn Error>),
+}
+
+impl fmt::Display for SubscriptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProof(address, error) => {
+                write!(f, "invalid ownership proof for {}: {}", address, error)
+            }
+            Self::RateLimited => write!(f, "too many subscriptions without ownership proof"),
+            Self::VersionConflict(version) => {
+                write!(
+                    f,
+                    "subscriptions version is outdated, current version: {}",
+                    version
+                )
+            }
+            Self::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for SubscriptionsError {}
+
+impl SubscriptionsError {
+    fn database(error: impl Error + 'static) -> Self {
+        Self::Database(Box::new(error))
+    }
+}
+
 pub struct SubscriptionsClient {
     database: DatabaseClient,
+    unverified_limit: i64,
+    unverified_window: u64,
 }
 
 impl SubscriptionsClient {
-    pub async fn new(database_url: &str) -> Self {
+    pub async fn new(database_url: &str, unverified_limit: i64, unverified_window: u64) -> Self {
         let database = DatabaseClient::new(database_url);
-        Self { database }
+        Self {
+            database,
+            unverified_limit,
+            unverified_window,
+        }
     }
 
     pub fn add_subscriptions(
         &mut self,
         device_id: &str,
         subscriptions: Vec<Subscription>,
-    ) -> Result<usize, Box<dyn Error>> {
-        let device = self.database.get_device(device_id)?;
-        let subscriptions = subscriptions
+    ) -> Result<usize, SubscriptionsError> {
+        let values = self.subscription_values(device_id, subscriptions)?;
+        self.database
+            .add_subscriptions(values)
+            .map_err(SubscriptionsError::database)
+    }
+
+    pub fn sync_subscriptions(
+        &mut self,
+        device_id: &str,
+        sync: SubscriptionsSync,
+    ) -> Result<SubscriptionsSyncResult, SubscriptionsError> {
+        let device = self
+            .database
+            .get_device(device_id)
+            .map_err(SubscriptionsError::database)?;
+        let values = self.subscription_values(device_id, sync.subscriptions)?;
+        let result = self
+            .database
+            .sync_subscriptions(device.id, sync.version, values)
+            .map_err(SubscriptionsError::database)?
+            .ok_or(SubscriptionsError::VersionConflict(
+                device.subscriptions_version,
+            ))?;
+
+        Ok(SubscriptionsSyncResult {
+            version: result.version,
+            added: result.added as i32,
+            removed: result.removed as i32,
+            subscriptions: result
+                .subscriptions
+                .into_iter()
+                .map(|x| x.as_primitive())
+                .collect(),
+        })
+    }
+
+    // verifies ownership proofs and rate limits subscriptions added without one
+    fn subscription_values(
+        &mut self,
+        device_id: &str,
+        subscriptions: Vec<Subscription>,
+    ) -> Result<Vec<storage::models::Subscription>, SubscriptionsError> {
+        let device = self
+            .database
+            .get_device(device_id)
+            .map_err(SubscriptionsError::database)?;
+        let existing: HashMap<(i32, String, String), storage::models::Subscription> = self
+            .database
+            .get_subscriptions_by_device_id(device_id)
+            .map_err(SubscriptionsError::database)?
             .into_iter()
-            .map(|x| storage::models::Subscription::from_primitive(x, device.id))
+            .map(|x| (x.key(), x))
             .collect();
-        let result = self.database.add_subscriptions(subscriptions)?;
-        Ok(result)
+
+        let mut values: Vec<storage::models::Subscription> = vec![];
+        let mut keys = HashSet::new();
+        let mut unverified = 0;
+        for subscription in subscriptions {
+            let verified = Self::verify_subscription(device_id, &subscription)?;
+            let has_preferences = subscription.preferences.is_some();
+            let value = storage::models::Subscription {
+                verified,
+                ..storage::models::Subscription::from_primitive(subscription, device.id)
+            };
+            let value = match existing.get(&value.key()) {
+                Some(current) if !has_preferences => value.with_preferences_of(current),
+                _ => value,
+            };
+            // a single upsert can not update the same row twice
+            if !keys.insert(value.key()) {
+                continue;
+            }
+            if !verified && !existing.contains_key(&value.key()) {
+                unverified += 1;
+            }
+            values.push(value);
+        }
+
+        if unverified > 0 {
+            let since = (Utc::now() - Duration::seconds(self.unverified_window as i64)).naive_utc();
+            let count = self
+                .database
+                .count_subscriptions_unverified(device.id, since)
+                .map_err(SubscriptionsError::database)?;
+            if count + unverified > self.unverified_limit {
+                return Err(SubscriptionsError::RateLimited);
+            }
+        }
+        Ok(values)
+    }
+
+    // chains without proof support are accepted as unverified
+    fn verify_subscription(
+        device_id: &str,
+        subscription: &Subscription,
+    ) -> Result<bool, SubscriptionsError> {
+        let Some(proof) = &subscription.proof else {
+            return Ok(false);
+        };
+        let message =
+            gem_proof::subscription_message(device_id, subscription.chain, &subscription.address);
+        match gem_proof::verify_ownership(
+            subscription.chain,
+            &subscription.address,
+            &message,
+            proof,
+        ) {
+            Ok(_) => Ok(true),
+            Err(ProofError::Unsupported(_)) => Ok(false),
+            Err(error) => Err(SubscriptionsError::InvalidProof(
+                subscription.address.clone(),
+                error,
+            )),
+        }
     }
 
     pub fn get_subscriptions(
@@ -51,13 +203,7 @@ impl SubscriptionsClient {
             .into_iter()
             .map(|x| storage::models::Subscription::from_primitive(x, device.id))
             .collect::<Vec<storage::models::Subscription>>();
-
-        //TODO: Implement to delete all subscriptions at once
-        let mut result = 0;
-        for subscription in values {
-            let size = self.database.delete_subscription(subscription)?;
-            result += size;
-        }
+        let result = self.database.delete_subscriptions(values)?;
         Ok(result)
     }
 }
@@ -2,21 +2,41 @@ extern crate rocket;
 
 use std::error::Error;
 
+use cacher::CacherClient;
 use primitives::{
-    FiatAssets, SwapMode, SwapQuoteProtocolRequest, SwapQuoteRequest, SwapQuoteResult,
+    FiatAssets, SwapMode, SwapQuote, SwapQuoteProtocolRequest, SwapQuoteRequest, SwapQuoteResult,
 };
 use storage::DatabaseClient;
-use swapper::SwapperClient;
+use swapper::{is_throttled_error, RateLimiter, SwapperClient};
 
 pub struct SwapClient {
     database: DatabaseClient,
+    cacher: CacherClient,
     client: SwapperClient,
+    rate_limiter: RateLimiter,
+    cache_ttl: i64,
+    stale_ttl: i64,
 }
 
 impl SwapClient {
-    pub async fn new(database_url: &str, client: SwapperClient) -> Self {
+    pub async fn new(
+        database_url: &str,
+        redis_url: &str,
+        client: SwapperClient,
+        rate_limiter: RateLimiter,
+        cache_ttl: i64,
+        stale_ttl: i64,
+    ) -> Self {
         let database = DatabaseClient::new(database_url);
-        Self { database, client }
+        let cacher = CacherClient::new(redis_url);
+        Self {
+            database,
+            cacher,
+            client,
+            rate_limiter,
+            cache_ttl,
+            stale_ttl,
+        }
     }
 
     fn get_quote_request(
@@ -49,15 +69,113 @@ impl SwapClient {
         Ok(quote_request)
     }
 
+    fn quote_key(&self, request: &SwapQuoteProtocolRequest) -> String {
+        format!(
+            "swap:quote:{}:{}:{}:{}:{}:{:?}",
+            request.from_asset,
+            request.to_asset,
+            request.wallet_address,
+            request.destination_address,
+            request.amount,
+            request.mode
+        )
+    }
+
     pub async fn swap_quote(
         &mut self,
         request: SwapQuoteRequest,
     ) -> Result<SwapQuoteResult, Box<dyn Error + Send + Sync>> {
         let quote_request = self.get_quote_request(request)?;
-        let quote = self.client.get_quote(quote_request).await?;
+
+        // quotes with transaction data are single use, only cache price quotes
+        let key = self.quote_key(&quote_request);
+        if !quote_request.include_data {
+            if let Some(quote) = self.get_cached_quote(&key).await {
+                return Ok(SwapQuoteResult { quote });
+            }
+        }
+
+        let stale_key = format!("{}:stale", key);
+        let quote = match self.get_quote(quote_request.clone()).await {
+            Ok(quote) => quote,
+            // every provider for the chain is throttled, an older price quote beats no quote
+            Err(QuoteError::Throttled(error)) if !quote_request.include_data => {
+                return match self.get_cached_quote(&stale_key).await {
+                    Some(quote) => Ok(SwapQuoteResult { quote }),
+                    None => Err(error.into()),
+                };
+            }
+            Err(QuoteError::Throttled(error)) | Err(QuoteError::Failed(error)) => {
+                return Err(error.into())
+            }
+        };
+
+        if !quote_request.include_data {
+            if let Ok(value) = serde_json::to_string(&quote) {
+                let _ = self
+                    .cacher
+                    .set_value_with_expiration(&key, value.clone(), self.cache_ttl)
+                    .await;
+                let _ = self
+                    .cacher
+                    .set_value_with_expiration(&stale_key, value, self.stale_ttl)
+                    .await;
+            }
+        }
         Ok(SwapQuoteResult { quote })
     }
 
+    async fn get_cached_quote(&mut self, key: &str) -> Option<SwapQuote> {
+        let value = self.cacher.get_value(key).await.ok()?;
+        serde_json::from_str(&value).ok()
+    }
+
+    // Throttled providers are skipped, the request only fails if no provider is able to quote
+    async fn get_quote(
+        &mut self,
+        request: SwapQuoteProtocolRequest,
+    ) -> Result<SwapQuote, QuoteError> {
+        let chain = request.from_asset.chain;
+        let providers = self.client.get_providers(chain);
+        if providers.is_empty() {
+            return Err(QuoteError::Failed(format!(
+                "swap is not supported on chain: {}",
+                chain
+            )));
+        }
+
+        let mut errors: Vec<String> = Vec::new();
+        let mut throttled = true;
+        for provider in providers {
+            if !self.rate_limiter.try_acquire(&provider.name) {
+                errors.push(format!("{}: rate limited", provider.name));
+                continue;
+            }
+            match self
+                .client
+                .get_quote_by_provider(&provider, request.clone())
+                .await
+            {
+                Ok(quote) => return Ok(quote),
+                Err(err) => {
+                    if is_throttled_error(err.as_ref()) {
+                        self.rate_limiter.throttle(&provider.name);
+                    } else {
+                        throttled = false;
+                    }
+                    println!("swap quote provider {} error: {}", provider.name, err);
+                    errors.push(format!("{}: {}", provider.name, err));
+                }
+            }
+        }
+        let error = errors.join(", ");
+        if throttled {
+            Err(QuoteError::Throttled(error))
+        } else {
+            Err(QuoteError::Failed(error))
+        }
+    }
+
     pub async fn get_swap_assets(&mut self) -> Result<FiatAssets, Box<dyn Error>> {
         let assets = self.database.get_swap_assets()?;
         let version = self.database.get_swap_assets_version()?;
@@ -68,3 +186,9 @@ impl SwapClient {
         })
     }
 }
+
+enum QuoteError {
+    // all providers are rate limited locally or by the provider
+    Throttled(String),
+    Failed(String),
+}
@@ -1,38 +1,392 @@
 extern crate rocket;
+use crate::device_auth::{AuthenticatedDevice, SignedJson};
 
-use crate::TransactionsClient;
-use primitives::Transaction;
-use primitives::TransactionsFetchOption;
+use std::str::FromStr;
+
+use crate::response::ResponseError;
+use crate::transaction_client::PendingTransactionError;
+use crate::{broadcast_client, BroadcastClient, TransactionsClient};
+use charter::export_client::ExportClient;
+use futures::StreamExt;
+use primitives::{
+    Chain, Transaction, TransactionBroadcast, TransactionBroadcastError,
+    TransactionBroadcastErrorCode, TransactionBroadcastResult, TransactionDirection,
+    TransactionPendingRequest, TransactionState, TransactionStreamEvent, TransactionType,
+    TransactionsCursor, TransactionsExportFormat, TransactionsFetchOption, TransactionsPage,
+};
+use rocket::http::{ContentType, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::status::Custom;
+use rocket::response::stream::{Event, EventStream};
 use rocket::serde::json::Json;
+use rocket::tokio::select;
 use rocket::tokio::sync::Mutex;
-use rocket::State;
+use rocket::{Shutdown, State};
+
+const TRANSACTIONS_PAGE_DEFAULT_LIMIT: u32 = 50;
+const TRANSACTIONS_PAGE_MAX_LIMIT: u32 = 200;
 
-#[get("/transactions/by_device_id/<device_id>?<wallet_index>&<asset_id>&<from_timestamp>")]
+// pages are only served by /transactions/device, a cursor is ignored here
+#[get("/transactions/by_device_id/<device_id>?<filter..>")]
 pub async fn get_transactions_by_device_id(
     device_id: &str,
-    wallet_index: i32,
-    asset_id: Option<String>,
-    from_timestamp: Option<u32>,
+    _device: AuthenticatedDevice,
+    filter: TransactionsFilter,
     client: &State<Mutex<TransactionsClient>>,
-) -> Json<Vec<Transaction>> {
-    let options: TransactionsFetchOption = TransactionsFetchOption {
-        wallet_index,
-        asset_id,
-        from_timestamp,
-    };
-    let transactions = client
+) -> Result<Json<Vec<Transaction>>, Custom<Json<ResponseError>>> {
+    let options = TransactionsFilter {
+        cursor: None,
+        ..filter
+    }
+    .options()
+    .map_err(|err| ResponseError::status(Status::BadRequest, err))?;
+
+    client
         .lock()
         .await
         .get_transactions_by_device_id(device_id, options)
-        .unwrap();
-    Json(transactions)
+        .map(Json)
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))
+}
+
+#[get("/transactions/device/<device_id>?<limit>&<filter..>")]
+pub async fn get_transactions_page(
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    limit: Option<u32>,
+    filter: TransactionsFilter,
+    client: &State<Mutex<TransactionsClient>>,
+) -> Result<Json<TransactionsPage>, Custom<Json<ResponseError>>> {
+    let limit = limit.unwrap_or(TRANSACTIONS_PAGE_DEFAULT_LIMIT);
+    if limit == 0 || limit > TRANSACTIONS_PAGE_MAX_LIMIT {
+        return Err(ResponseError::status(
+            Status::BadRequest,
+            format!(
+                "limit must be between 1 and {}",
+                TRANSACTIONS_PAGE_MAX_LIMIT
+            ),
+        ));
+    }
+    let options = filter
+        .options()
+        .map_err(|err| ResponseError::status(Status::BadRequest, err))?;
+
+    client
+        .lock()
+        .await
+        .get_transactions_page(device_id, options, limit)
+        .map(Json)
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))
+}
+
+// registers a transaction right after broadcasting it, to follow it until it is included
+#[post(
+    "/transactions/pending/<device_id>?<wallet_index>",
+    format = "json",
+    data = "<request>"
+)]
+pub async fn add_transaction_pending(
+    request: SignedJson<TransactionPendingRequest>,
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    wallet_index: i32,
+    client: &State<Mutex<TransactionsClient>>,
+) -> Result<Json<Transaction>, Custom<Json<ResponseError>>> {
+    register_transaction_pending(client, device_id, wallet_index, &request.0)
+        .await
+        .map(Json)
+        .map_err(|err| {
+            let status = match err {
+                PendingTransactionError::InvalidTransaction(_)
+                | PendingTransactionError::UnsupportedChain(_) => Status::BadRequest,
+                PendingTransactionError::NotSubscribed(_) => Status::Forbidden,
+                PendingTransactionError::NotFound(_) => Status::NotFound,
+                PendingTransactionError::Node(_) => Status::BadGateway,
+                PendingTransactionError::Database(_) => Status::InternalServerError,
+            };
+            ResponseError::status(status, err)
+        })
+}
+
+// the client is only locked around database access, not while the node is queried
+async fn register_transaction_pending(
+    client: &Mutex<TransactionsClient>,
+    device_id: &str,
+    wallet_index: i32,
+    request: &TransactionPendingRequest,
+) -> Result<Transaction, PendingTransactionError> {
+    let provider = client
+        .lock()
+        .await
+        .get_pending_provider(device_id, wallet_index, request)?;
+    let transaction = provider
+        .get_pending_transaction(&request.hash)
+        .await
+        .map_err(PendingTransactionError::Node)?;
+    client
+        .lock()
+        .await
+        .add_transaction_pending(device_id, wallet_index, request, transaction)
+}
+
+// relays a signed payload to the chain's nodes, transaction details are ignored
+#[post(
+    "/transactions/broadcast/<chain>",
+    format = "json",
+    data = "<broadcast>"
+)]
+pub async fn broadcast_transaction(
+    broadcast: Json<TransactionBroadcast>,
+    chain: &str,
+    client: &State<Mutex<BroadcastClient>>,
+) -> Result<Json<TransactionBroadcastResult>, Custom<Json<TransactionBroadcastError>>> {
+    let chain = broadcast_chain(chain)?;
+    relay_transaction(client, chain, &broadcast.data)
+        .await
+        .map(Json)
+        .map_err(broadcast_error_response)
+}
+
+// same as broadcast_transaction, also tracks the transaction until it is included
+#[post(
+    "/transactions/broadcast/<chain>/<device_id>?<wallet_index>",
+    format = "json",
+    data = "<broadcast>"
+)]
+pub async fn broadcast_transaction_by_device_id(
+    broadcast: SignedJson<TransactionBroadcast>,
+    chain: &str,
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    wallet_index: i32,
+    client: &State<Mutex<BroadcastClient>>,
+    transactions_client: &State<Mutex<TransactionsClient>>,
+) -> Result<Json<TransactionBroadcastResult>, Custom<Json<TransactionBroadcastError>>> {
+    let chain = broadcast_chain(chain)?;
+    let broadcast = broadcast.0;
+
+    let result = relay_transaction(client, chain, &broadcast.data)
+        .await
+        .map_err(broadcast_error_response)?;
+
+    // the transaction is already on its way, failing to track it is not an error
+    if let Some(from) = broadcast.from {
+        let request = TransactionPendingRequest {
+            chain,
+            hash: result.hash.clone(),
+            from,
+        };
+        if let Err(err) =
+            register_transaction_pending(transactions_client, device_id, wallet_index, &request)
+                .await
+        {
+            println!("broadcast pending error: {}, hash: {}", err, result.hash);
+        }
+    }
+    Ok(Json(result))
+}
+
+// the client is only locked to read the nodes, not while they are waited on
+async fn relay_transaction(
+    client: &Mutex<BroadcastClient>,
+    chain: Chain,
+    data: &str,
+) -> Result<TransactionBroadcastResult, TransactionBroadcastError> {
+    let urls = client.lock().await.get_urls(chain);
+    broadcast_client::broadcast_transaction(chain, urls, data).await
+}
+
+fn broadcast_chain(chain: &str) -> Result<Chain, Custom<Json<TransactionBroadcastError>>> {
+    Chain::from_str(chain).map_err(|_| {
+        broadcast_error_response(TransactionBroadcastError {
+            code: TransactionBroadcastErrorCode::InvalidTransaction,
+            message: format!("invalid chain: {}", chain),
+        })
+    })
+}
+
+fn broadcast_error_response(
+    error: TransactionBroadcastError,
+) -> Custom<Json<TransactionBroadcastError>> {
+    let status = match error.code {
+        TransactionBroadcastErrorCode::NodeUnavailable => Status::BadGateway,
+        _ => Status::BadRequest,
+    };
+    Custom(status, Json(error))
+}
+
+// server-sent events of new and updated transactions, the event id is the cursor to resume from.
+// events can repeat around a resume, clients replace transactions by id
+#[get("/transactions/stream/<device_id>?<wallet_index>&<cursor>")]
+pub async fn stream_transactions(
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    wallet_index: i32,
+    cursor: Option<String>,
+    last_event_id: LastEventId,
+    client: &State<Mutex<TransactionsClient>>,
+    mut shutdown: Shutdown,
+) -> Result<EventStream![], Custom<Json<ResponseError>>> {
+    let cursor = match last_event_id.0.or(cursor) {
+        Some(cursor) => Some(TransactionsCursor::decode(&cursor).ok_or_else(|| {
+            ResponseError::status(Status::BadRequest, format!("invalid cursor: {}", cursor))
+        })?),
+        None => None,
+    };
+
+    let mut client = client.lock().await;
+    // subscribe before loading missed transactions, so none are lost in between
+    let mut messages = Box::pin(
+        client
+            .subscribe_transactions(device_id, wallet_index)
+            .await
+            .map_err(|err| ResponseError::status(Status::InternalServerError, err))?,
+    );
+    let missed = match cursor {
+        Some(cursor) => client
+            .get_transactions_after(device_id, wallet_index, &cursor)
+            .map_err(|err| ResponseError::status(Status::InternalServerError, err))?,
+        None => vec![],
+    };
+    drop(client);
+
+    Ok(EventStream! {
+        for event in missed {
+            yield transaction_event(&event);
+        }
+        loop {
+            let message = select! {
+                message = messages.next() => message,
+                _ = &mut shutdown => break,
+            };
+            let Some(message) = message else {
+                break;
+            };
+            match serde_json::from_str::<TransactionStreamEvent>(&message) {
+                Ok(event) => yield transaction_event(&event),
+                Err(err) => println!("stream transaction error: {:?}", err),
+            }
+        }
+    })
+}
+
+fn transaction_event(event: &TransactionStreamEvent) -> Event {
+    Event::json(&event.transaction)
+        .event("transaction")
+        .id(event.cursor.clone())
+}
+
+// sent by event source clients when reconnecting
+pub struct LastEventId(Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LastEventId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let value = request
+            .headers()
+            .get_one("Last-Event-ID")
+            .map(|x| x.to_string());
+        Outcome::Success(LastEventId(value))
+    }
 }
 
 #[get("/transactions/by_hash/<hash>")]
 pub async fn get_transactions_by_hash(
     hash: &str,
     client: &State<Mutex<TransactionsClient>>,
-) -> Json<Vec<Transaction>> {
-    let transactions = client.lock().await.get_transactions_by_hash(hash).unwrap();
-    Json(transactions)
+) -> Result<Json<Vec<Transaction>>, Custom<Json<ResponseError>>> {
+    client
+        .lock()
+        .await
+        .get_transactions_by_hash(hash)
+        .map(Json)
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))
+}
+
+#[get("/transactions/export/<device_id>?<wallet_index>&<format>&<currency>")]
+pub async fn get_transactions_export(
+    device_id: &str,
+    _device: AuthenticatedDevice,
+    wallet_index: i32,
+    format: Option<String>,
+    currency: Option<String>,
+    client: &State<Mutex<ExportClient>>,
+) -> Result<(ContentType, String), Custom<Json<ResponseError>>> {
+    let format = match format {
+        Some(format) => TransactionsExportFormat::from_str(&format).map_err(|_| {
+            ResponseError::status(Status::BadRequest, format!("invalid format: {}", format))
+        })?,
+        None => TransactionsExportFormat::Csv,
+    };
+    client
+        .lock()
+        .await
+        .export_transactions(device_id, wallet_index, format, currency)
+        .await
+        .map(|csv| (ContentType::CSV, csv))
+        .map_err(|err| ResponseError::status(Status::InternalServerError, err))
+}
+
+// raw query parameters, validated before reaching the database
+#[derive(FromForm)]
+pub struct TransactionsFilter {
+    wallet_index: i32,
+    asset_id: Option<String>,
+    from_timestamp: Option<u32>,
+    to_timestamp: Option<u32>,
+    transaction_type: Option<String>,
+    direction: Option<String>,
+    state: Option<String>,
+    address: Option<String>,
+    min_value: Option<String>,
+    max_value: Option<String>,
+    cursor: Option<String>,
+}
+
+impl TransactionsFilter {
+    fn options(self) -> Result<TransactionsFetchOption, String> {
+        let transaction_type = self
+            .transaction_type
+            .map(|x| TransactionType::from_str(&x).map_err(|_| format!("invalid type: {}", x)))
+            .transpose()?;
+        let direction = self
+            .direction
+            .map(|x| TransactionDirection::new(&x).ok_or(format!("invalid direction: {}", x)))
+            .transpose()?;
+        let state = self
+            .state
+            .map(|x| TransactionState::new(&x).ok_or(format!("invalid state: {}", x)))
+            .transpose()?;
+        for value in [&self.min_value, &self.max_value].into_iter().flatten() {
+            if value.is_empty() || !value.chars().all(|x| x.is_ascii_digit()) {
+                return Err(format!("invalid value: {}", value));
+            }
+        }
+        if let (Some(from), Some(to)) = (self.from_timestamp, self.to_timestamp) {
+            if from > to {
+                return Err("from_timestamp is after to_timestamp".to_string());
+            }
+        }
+        if let Some(cursor) = &self.cursor {
+            if TransactionsCursor::decode(cursor).is_none() {
+                return Err(format!("invalid cursor: {}", cursor));
+            }
+        }
+
+        Ok(TransactionsFetchOption {
+            asset_id: self.asset_id,
+            from_timestamp: self.from_timestamp,
+            to_timestamp: self.to_timestamp,
+            transaction_type,
+            direction,
+            state,
+            address: self.address,
+            min_value: self.min_value,
+            max_value: self.max_value,
+            cursor: self.cursor,
+            ..TransactionsFetchOption::new(self.wallet_index)
+        })
+    }
 }
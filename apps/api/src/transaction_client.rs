@@ -1,23 +1,66 @@
 use std::error::Error;
+use std::fmt;
 
-use primitives::TransactionsFetchOption;
+use cacher::CacherClient;
+use futures::Stream;
+use gem_chain_rpc::TransactionStateProvider;
+use primitives::{
+    Chain, Transaction, TransactionPendingRequest, TransactionStreamEvent, TransactionsCursor,
+    TransactionsFetchOption, TransactionsPage,
+};
+use settings::Settings;
+use settings_chain::ProviderFactory;
 use storage::DatabaseClient;
 
+#[derive(Debug)]
+pub enum PendingTransactionError {
+    InvalidTransaction(String),
+    UnsupportedChain(Chain),
+    NotSubscribed(String),
+    NotFound(String),
+    Node(Box<dyn Error + Send + Sync>),
+    Database(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for PendingTransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTransaction(error) => write!(f, "invalid transaction: {}", error),
+            Self::UnsupportedChain(chain) => {
+                write!(f, "pending transactions are not tracked on {}", chain)
+            }
+            Self::NotSubscribed(address) => write!(f, "address is not subscribed: {}", address),
+            Self::NotFound(hash) => write!(f, "transaction is not known to the node: {}", hash),
+            Self::Node(error) => write!(f, "node error: {}", error),
+            Self::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for PendingTransactionError {}
+
 pub struct TransactionsClient {
     database: DatabaseClient,
+    cacher: CacherClient,
+    settings: Settings,
 }
 
 impl TransactionsClient {
-    pub async fn new(database_url: &str) -> Self {
+    pub async fn new(database_url: &str, redis_url: &str, settings: Settings) -> Self {
         let database = DatabaseClient::new(database_url);
-        Self { database }
+        let cacher = CacherClient::new(redis_url);
+        Self {
+            database,
+            cacher,
+            settings,
+        }
     }
 
     pub fn get_transactions_by_device_id(
         &mut self,
         device_id: &str,
         options: TransactionsFetchOption,
-    ) -> Result<Vec<primitives::Transaction>, Box<dyn Error>> {
+    ) -> Result<Vec<primitives::Transaction>, Box<dyn Error + Send + Sync>> {
         let wallet_index = options.wallet_index;
         let subscriptions = self
             .database
@@ -45,10 +88,147 @@ impl TransactionsClient {
         Ok(transactions)
     }
 
+    // one extra transaction is loaded to know whether another page follows
+    pub fn get_transactions_page(
+        &mut self,
+        device_id: &str,
+        options: TransactionsFetchOption,
+        limit: u32,
+    ) -> Result<TransactionsPage, Box<dyn Error + Send + Sync>> {
+        let options = TransactionsFetchOption {
+            limit: Some(limit + 1),
+            ..options
+        };
+        let mut transactions = self.get_transactions_by_device_id(device_id, options)?;
+
+        let next_cursor = if transactions.len() > limit as usize {
+            transactions.truncate(limit as usize);
+            transactions
+                .last()
+                .map(|x| TransactionsCursor::new(x.created_at, x.id.clone()).encode())
+        } else {
+            None
+        };
+        Ok(TransactionsPage {
+            transactions,
+            next_cursor,
+        })
+    }
+
+    // transactions stored or updated after the cursor, in storage order
+    pub fn get_transactions_after(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+        cursor: &TransactionsCursor,
+    ) -> Result<Vec<TransactionStreamEvent>, Box<dyn Error + Send + Sync>> {
+        let subscriptions = self
+            .database
+            .get_subscriptions_by_device_id_wallet_index(device_id, wallet_index)?;
+        let addresses = subscriptions
+            .iter()
+            .map(|x| x.address.clone())
+            .collect::<Vec<String>>();
+        let chains = subscriptions
+            .iter()
+            .map(|x| x.chain.clone())
+            .collect::<Vec<String>>();
+
+        let events = self
+            .database
+            .get_transactions_updated_after(addresses.clone(), chains, cursor)?
+            .into_iter()
+            .map(|(transaction, updated_at)| TransactionStreamEvent {
+                cursor: TransactionsCursor::new(updated_at.and_utc(), transaction.id.clone())
+                    .encode(),
+                transaction: transaction
+                    .as_primitive(addresses.clone())
+                    .finalize(addresses.clone()),
+            })
+            .collect();
+        Ok(events)
+    }
+
+    pub async fn subscribe_transactions(
+        &self,
+        device_id: &str,
+        wallet_index: i32,
+    ) -> Result<impl Stream<Item = String>, Box<dyn Error + Send + Sync>> {
+        self.cacher
+            .subscribe(&cacher::transactions_channel(device_id, wallet_index))
+            .await
+    }
+
+    // node of a chain with a pending tracker, the sender has to be subscribed by the device
+    pub fn get_pending_provider(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+        request: &TransactionPendingRequest,
+    ) -> Result<Box<dyn TransactionStateProvider>, PendingTransactionError> {
+        if request.hash.is_empty() {
+            return Err(PendingTransactionError::InvalidTransaction(
+                "missing hash".to_string(),
+            ));
+        }
+        let url = ProviderFactory::url(request.chain, &self.settings);
+        let provider = ProviderFactory::new_transaction_state_provider(request.chain, url)
+            .ok_or(PendingTransactionError::UnsupportedChain(request.chain))?;
+
+        let is_subscribed = self
+            .database
+            .get_subscriptions_by_device_id_wallet_index(device_id, wallet_index)
+            .map_err(|err| PendingTransactionError::Database(Box::new(err)))?
+            .into_iter()
+            .any(|x| x.chain == request.chain.as_ref() && x.address == request.from);
+        if !is_subscribed {
+            return Err(PendingTransactionError::NotSubscribed(request.from.clone()));
+        }
+        Ok(provider)
+    }
+
+    // stored as pending and tracked by the parser until included or dropped,
+    // the transaction is loaded from the node so the client can not forge its details
+    pub fn add_transaction_pending(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+        request: &TransactionPendingRequest,
+        transaction: Option<Transaction>,
+    ) -> Result<Transaction, PendingTransactionError> {
+        let transaction =
+            transaction.ok_or(PendingTransactionError::NotFound(request.hash.clone()))?;
+        if !transaction.from.eq_ignore_ascii_case(&request.from) {
+            return Err(PendingTransactionError::InvalidTransaction(format!(
+                "sender is {}",
+                transaction.from
+            )));
+        }
+
+        let device = self
+            .database
+            .get_device(device_id)
+            .map_err(|err| PendingTransactionError::Database(Box::new(err)))?;
+        let pending = storage::models::TransactionPending {
+            transaction_id: transaction.id.clone(),
+            device_id: device.id,
+            wallet_index,
+            chain: request.chain.as_ref().to_string(),
+        };
+        self.database
+            .add_transaction_pending(
+                storage::models::Transaction::from_primitive(transaction.clone()),
+                storage::models::TransactionAddresses::from_primitive(transaction.clone()),
+                pending,
+            )
+            .map_err(|err| PendingTransactionError::Database(Box::new(err)))?;
+        Ok(transaction)
+    }
+
     pub fn get_transactions_by_hash(
         &mut self,
         hash: &str,
-    ) -> Result<Vec<primitives::Transaction>, Box<dyn Error>> {
+    ) -> Result<Vec<primitives::Transaction>, Box<dyn Error + Send + Sync>> {
         let transactions = self
             .database
             .get_transactions_by_hash(hash)?
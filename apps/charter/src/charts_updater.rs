@@ -1,16 +1,28 @@
 use crate::client::ChartsClient;
 
-use coingecko::{Coin, CoinGeckoClient};
+use cacher::CacherClient;
+use chrono::Utc;
+use coingecko::{CoinGeckoClient, MarketChart};
 use pricer::client::PriceClient;
+use primitives::DEFAULT_FIAT_CURRENCY;
 
-use std::thread;
+use std::collections::{HashMap, HashSet};
 
-use storage::models::CreateChart;
+use storage::models::{price::PriceCache, CreateChart, Price};
+
+const PRICES_CACHE_QUERY_BATCH_LIMIT: usize = 1000;
+const DAY_SECONDS: u64 = 86400;
 
 pub struct ChartsUpdater {
     coin_gecko_client: CoinGeckoClient,
     charts_client: ChartsClient,
     prices_client: PriceClient,
+    cacher_client: CacherClient,
+    backfill_days: u64,
+    backfill_limit: usize,
+    backfill_retry: i64,
+    // last point written per coin, unchanged prices are not appended again
+    last_updated: HashMap<String, u32>,
 }
 
 impl ChartsUpdater {
@@ -18,72 +30,292 @@ impl ChartsUpdater {
         charts_client: ChartsClient,
         prices_client: PriceClient,
         coin_gecko_client: CoinGeckoClient,
+        cacher_client: CacherClient,
+        backfill_days: u64,
+        backfill_limit: usize,
+        backfill_retry: i64,
     ) -> Self {
         Self {
             coin_gecko_client,
             prices_client,
             charts_client,
+            cacher_client,
+            backfill_days,
+            backfill_limit,
+            backfill_retry,
+            last_updated: HashMap::new(),
         }
     }
 
-    pub async fn get_coin_list(&mut self) -> Result<Vec<Coin>, Box<dyn std::error::Error>> {
-        Ok(self.coin_gecko_client.get_coin_list().await?)
+    // appends the latest points from the pricer cache
+    pub async fn update_charts(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        let asset_ids = self
+            .prices_client
+            .get_prices_assets()?
+            .into_iter()
+            .map(|x| x.asset_id)
+            .collect::<Vec<String>>();
+
+        let mut prices: Vec<PriceCache> = Vec::new();
+        for chunk in asset_ids.chunks(PRICES_CACHE_QUERY_BATCH_LIMIT) {
+            let chunk = chunk.iter().map(|x| x.as_str()).collect();
+            prices.extend(
+                self.prices_client
+                    .get_cache_prices(DEFAULT_FIAT_CURRENCY, chunk)
+                    .await?,
+            );
+        }
+
+        let charts = new_charts(
+            prices.into_iter().map(|x| x.price.as_chart()).collect(),
+            &self.last_updated,
+        );
+        let count = self.charts_client.set_charts(charts.clone()).await?;
+
+        for chart in charts {
+            self.last_updated.insert(chart.coin_id, chart.ts);
+        }
+        Ok(count)
     }
 
-    pub async fn update_charts_all(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
-        let coin_list = self.get_coin_list().await?;
+    // refetches only the missing days of each coin, largest market caps first.
+    // coins without any point get their full daily history
+    pub async fn backfill_charts(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        let coverage = self
+            .charts_client
+            .get_charts_coverage(self.backfill_days)
+            .await?
+            .into_iter()
+            .map(|x| (x.coin_id, x.dates))
+            .collect::<HashMap<String, Vec<u32>>>();
+
+        let now = Utc::now().timestamp() as u64;
+        let today = now - now % DAY_SECONDS;
+        let mut coins = Vec::new();
+        for (coin_id, range) in backfill_coins(
+            self.prices_client.get_prices()?,
+            &coverage,
+            today,
+            self.backfill_days,
+        ) {
+            if coins.len() >= self.backfill_limit {
+                break;
+            }
+            // attempts survive restarts, coingecko may have no older data for the coin
+            if self
+                .cacher_client
+                .get_value(&backfill_key(&coin_id))
+                .await
+                .is_ok()
+            {
+                continue;
+            }
+            coins.push((coin_id, range));
+        }
+
+        let mut count = 0;
+        for (coin_id, (from, to)) in coins {
+            self.cacher_client
+                .set_value_with_expiration(
+                    &backfill_key(&coin_id),
+                    now.to_string(),
+                    self.backfill_retry,
+                )
+                .await?;
 
-        for coin_id in coin_list.clone() {
-            let prices = self
+            let mut charts = Vec::new();
+            if !coverage.contains_key(&coin_id) {
+                match self.coin_gecko_client.get_market_chart(&coin_id).await {
+                    Ok(market_chart) => charts.extend(market_chart_charts(&coin_id, market_chart)),
+                    Err(err) => println!("backfill charts {} history error: {}", coin_id, err),
+                }
+            }
+            match self
                 .coin_gecko_client
-                .get_market_chart(coin_id.id.as_str())
-                .await;
-
-            match prices {
-                Ok(prices) => {
-                    let charts = prices
-                        .prices
-                        .clone()
-                        .into_iter()
-                        .map(|x| CreateChart {
-                            coin_id: coin_id.id.clone(),
-                            price: x[1] as f32,
-                            ts: (x[0] / 1_000_f64) as u32,
-                        })
-                        .filter(|x| x.ts > 0 && x.price > 0.0)
-                        .collect::<Vec<CreateChart>>();
-
-                    match self.charts_client.set_charts(charts).await {
-                        Ok(_) => {
-                            println!("set charts {}", coin_id.id.clone());
-                        }
-                        Err(err) => {
-                            println!("set charts error: {}", err);
-                        }
-                    };
-
-                    println!("update charts {}", coin_id.id.clone());
-
-                    thread::sleep(std::time::Duration::from_millis(100));
+                .get_market_chart_range(&coin_id, from, to.min(now))
+                .await
+            {
+                Ok(market_chart) => charts.extend(market_chart_charts(&coin_id, market_chart)),
+                Err(err) => println!("backfill charts {} error: {}", coin_id, err),
+            }
+            if charts.is_empty() {
+                continue;
+            }
+
+            match self.charts_client.set_charts(charts).await {
+                Ok(value) => {
+                    println!("backfill charts {}: {}", coin_id, value);
+                    count += value;
                 }
                 Err(err) => {
-                    println!("update charts error: {}", err);
-                    continue;
+                    println!("backfill charts {} error: {}", coin_id, err);
                 }
             }
         }
-        Ok(coin_list.len())
+        Ok(count)
     }
+}
 
-    pub async fn update_charts(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
-        let prices = self.prices_client.get_prices()?;
-        let charts = prices
-            .clone()
-            .into_iter()
-            .map(|x| x.as_chart())
-            .filter(|x| x.ts > 0 && x.price > 0.0)
-            .collect::<Vec<CreateChart>>();
+fn backfill_key(coin_id: &str) -> String {
+    format!("charts:backfill:{}", coin_id)
+}
+
+// assets sharing a coin are cached separately, only one point per coin is kept
+fn new_charts(charts: Vec<CreateChart>, last_updated: &HashMap<String, u32>) -> Vec<CreateChart> {
+    let mut result: Vec<CreateChart> = charts
+        .into_iter()
+        .filter(|x| x.ts > 0 && x.price > 0.0)
+        .filter(|x| !matches!(last_updated.get(&x.coin_id), Some(ts) if x.ts <= *ts))
+        .collect();
+    result.sort_by(|a, b| a.coin_id.cmp(&b.coin_id));
+    result.dedup_by(|a, b| a.coin_id == b.coin_id);
+    result
+}
+
+// coins with missing days and the range to refetch, ranked coins first
+fn backfill_coins(
+    mut prices: Vec<Price>,
+    coverage: &HashMap<String, Vec<u32>>,
+    today: u64,
+    days: u64,
+) -> Vec<(String, (u64, u64))> {
+    // unranked coins have rank 0
+    prices.sort_by_key(|x| (x.market_cap_rank <= 0, x.market_cap_rank));
+    prices
+        .into_iter()
+        .filter_map(|x| {
+            let dates = coverage
+                .get(&x.id)
+                .map(|x| x.as_slice())
+                .unwrap_or_default();
+            missing_range(dates, today, days).map(|range| (x.id, range))
+        })
+        .collect()
+}
+
+// from the start of the oldest missing day to the end of the newest one, days between are refetched as well
+fn missing_range(dates: &[u32], today: u64, days: u64) -> Option<(u64, u64)> {
+    let dates = dates.iter().map(|x| *x as u64).collect::<HashSet<u64>>();
+    let missing = (0..=days)
+        .map(|x| today - x * DAY_SECONDS)
+        .filter(|x| !dates.contains(x))
+        .collect::<Vec<u64>>();
+    Some((*missing.iter().min()?, missing.iter().max()? + DAY_SECONDS))
+}
+
+// market caps and volumes share the timestamps of prices
+fn market_chart_charts(coin_id: &str, market_chart: MarketChart) -> Vec<CreateChart> {
+    let value = |values: &Vec<Vec<f64>>, index: usize| {
+        values
+            .get(index)
+            .and_then(|x| x.get(1))
+            .copied()
+            .unwrap_or(0.0)
+    };
+    market_chart
+        .prices
+        .iter()
+        .enumerate()
+        .map(|(index, x)| CreateChart {
+            coin_id: coin_id.to_string(),
+            price: x[1] as f32,
+            market_cap: value(&market_chart.market_caps, index) as f32,
+            volume: value(&market_chart.total_volumes, index) as f32,
+            ts: (x[0] / 1_000_f64) as u32,
+        })
+        .filter(|x| x.ts > 0 && x.price > 0.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODAY: u64 = 1718064000;
+
+    #[test]
+    fn test_new_charts() {
+        let charts: Vec<CreateChart> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/charts.json"
+        )))
+        .unwrap();
+        let last_updated =
+            HashMap::from([("bitcoin".to_string(), 100), ("ethereum".to_string(), 100)]);
+        let charts = new_charts(charts, &last_updated);
+
+        assert_eq!(
+            charts
+                .iter()
+                .map(|x| x.coin_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ethereum", "solana"]
+        );
+    }
+
+    #[test]
+    fn test_backfill_coins() {
+        let prices: Vec<Price> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/prices.json"
+        )))
+        .unwrap();
+        let days = |range: std::ops::RangeInclusive<u64>| {
+            range
+                .map(|x| (TODAY - x * DAY_SECONDS) as u32)
+                .collect::<Vec<u32>>()
+        };
+        let coverage = HashMap::from([
+            ("bitcoin".to_string(), days(0..=30)),
+            ("ethereum".to_string(), days(0..=20)),
+            ("tether".to_string(), days(0..=30)),
+        ]);
+        let full = (TODAY - 30 * DAY_SECONDS, TODAY + DAY_SECONDS);
+
+        assert_eq!(
+            backfill_coins(prices, &coverage, TODAY, 30),
+            vec![
+                (
+                    "ethereum".to_string(),
+                    (TODAY - 30 * DAY_SECONDS, TODAY - 20 * DAY_SECONDS)
+                ),
+                ("solana".to_string(), full),
+                ("unranked".to_string(), full),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_range() {
+        let dates = [TODAY, TODAY - 2 * DAY_SECONDS, TODAY - 3 * DAY_SECONDS].map(|x| x as u32);
+
+        assert_eq!(
+            missing_range(&dates, TODAY, 3),
+            Some((TODAY - DAY_SECONDS, TODAY))
+        );
+        assert_eq!(
+            missing_range(&dates[1..], TODAY, 3),
+            Some((TODAY - DAY_SECONDS, TODAY + DAY_SECONDS))
+        );
+        assert_eq!(
+            missing_range(&dates, TODAY, 1),
+            Some((TODAY - DAY_SECONDS, TODAY))
+        );
+        assert_eq!(missing_range(&dates[..1], TODAY, 0), None);
+    }
+
+    #[test]
+    fn test_market_chart_charts() {
+        let market_chart: MarketChart = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/market_chart.json"
+        )))
+        .unwrap();
+        let charts = market_chart_charts("bitcoin", market_chart);
 
-        self.charts_client.set_charts(charts).await
+        assert_eq!(charts.len(), 1);
+        assert_eq!(charts[0].ts, 1718000000);
+        assert_eq!(charts[0].market_cap, 1.2e12);
+        assert_eq!(charts[0].volume, 0.0);
     }
 }
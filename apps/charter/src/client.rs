@@ -1,6 +1,10 @@
-use primitives::{ChartPeriod, ChartValue, DEFAULT_FIAT_CURRENCY};
+use primitives::{ChartCandle, ChartPeriod, ChartValue, Charts, DEFAULT_FIAT_CURRENCY};
 use std::error::Error;
-use storage::{models::CreateChart, ClickhouseDatabase, DatabaseClient};
+use storage::{
+    clickhouse_database::{CHARTS_DAILY_TABLE_NAME, CHARTS_HOURLY_TABLE_NAME},
+    models::{ChartCoverage, CreateChart},
+    ClickhouseDatabase, DatabaseClient,
+};
 
 pub struct ChartsClient {
     database: DatabaseClient,
@@ -25,38 +29,64 @@ impl ChartsClient {
         self.clickhouse_database.add_charts(charts.clone()).await?;
         Ok(charts.len())
     }
-    pub async fn get_charts_prices(
+    pub async fn get_charts_coverage(
+        &mut self,
+        days: u64,
+    ) -> Result<Vec<ChartCoverage>, Box<dyn Error>> {
+        Ok(self.clickhouse_database.get_charts_coverage(days).await?)
+    }
+
+    pub async fn get_charts(
         &mut self,
         coin_id: &str,
         period: ChartPeriod,
         currency: &str,
-    ) -> Result<Vec<ChartValue>, Box<dyn Error>> {
+    ) -> Result<Charts, Box<dyn Error>> {
+        // latest rate, used for points older than the stored rates history
         let base_rate = self.database.get_fiat_rate(DEFAULT_FIAT_CURRENCY)?;
         let rate = self.database.get_fiat_rate(currency)?;
-        let rate_multiplier = rate.rate / base_rate.rate;
+        let default_rate = rate.rate / base_rate.rate;
         let interval = self.period_sql(period.clone());
 
-        match period {
-            ChartPeriod::Hour
-            | ChartPeriod::Day
-            | ChartPeriod::Week
-            | ChartPeriod::Month
-            | ChartPeriod::Quarter
-            | ChartPeriod::Year
-            | ChartPeriod::All => {
-                let prices = self
-                    .clickhouse_database
-                    .get_charts(coin_id, interval, period.minutes())
+        let charts = match self.period_table(period.clone()) {
+            Some(table) => {
+                self.clickhouse_database
+                    .get_charts_aggregated(
+                        table,
+                        coin_id,
+                        interval,
+                        period.minutes(),
+                        currency,
+                        default_rate,
+                    )
                     .await?
-                    .into_iter()
-                    .map(|x| ChartValue {
-                        timestamp: x.date,
-                        value: (x.price * rate_multiplier) as f32,
-                    })
-                    .collect();
-                Ok(prices)
             }
-        }
+            None => {
+                self.clickhouse_database
+                    .get_charts(coin_id, interval, period.minutes(), currency, default_rate)
+                    .await?
+            }
+        };
+
+        let value = |timestamp: i32, value: f64| ChartValue {
+            timestamp,
+            value: value as f32,
+        };
+        Ok(Charts {
+            prices: charts.iter().map(|x| value(x.date, x.close)).collect(),
+            market_caps: charts.iter().map(|x| value(x.date, x.market_cap)).collect(),
+            total_volumes: charts.iter().map(|x| value(x.date, x.volume)).collect(),
+            candles: charts
+                .iter()
+                .map(|x| ChartCandle {
+                    timestamp: x.date,
+                    open: x.open as f32,
+                    high: x.high as f32,
+                    low: x.low as f32,
+                    close: x.close as f32,
+                })
+                .collect(),
+        })
     }
 
     fn period_sql(&self, period: ChartPeriod) -> &str {
@@ -70,4 +100,15 @@ impl ChartsClient {
             ChartPeriod::All => "3 day",
         }
     }
+
+    // short periods are built from raw points, longer ones from the aggregated candles
+    fn period_table(&self, period: ChartPeriod) -> Option<&str> {
+        match period {
+            ChartPeriod::Hour | ChartPeriod::Day => None,
+            ChartPeriod::Week | ChartPeriod::Month => Some(CHARTS_HOURLY_TABLE_NAME),
+            ChartPeriod::Quarter | ChartPeriod::Year | ChartPeriod::All => {
+                Some(CHARTS_DAILY_TABLE_NAME)
+            }
+        }
+    }
 }
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use primitives::{
+    Transaction, TransactionDirection, TransactionState, TransactionSwapMetadata, TransactionType,
+    TransactionsExportFormat,
+};
+
+use crate::portfolio::{is_fee_payer, is_self_transfer, price_at, PriceHistory};
+
+// symbol and decimals per asset id
+pub type ExportAssets = HashMap<String, (String, i32)>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportRowKind {
+    Send,
+    Receive,
+    Swap,
+    Reward,
+    Fee,
+}
+
+impl ExportRowKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Send => "send",
+            Self::Receive => "receive",
+            Self::Swap => "swap",
+            Self::Reward => "reward",
+            Self::Fee => "fee",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportAmount {
+    pub symbol: String,
+    pub value: String,
+}
+
+// fee rows carry the fee as the sent amount
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRow {
+    pub date: DateTime<Utc>,
+    pub kind: ExportRowKind,
+    pub sent: Option<ExportAmount>,
+    pub received: Option<ExportAmount>,
+    // market value of the received amount, or of the sent amount when nothing was received
+    pub value: Option<f64>,
+    pub hash: String,
+}
+
+// rows of a confirmed transaction, fees are exported as a separate row paid by the sender
+pub fn export_rows(
+    transaction: &Transaction,
+    addresses: &[String],
+    assets: &ExportAssets,
+    prices: &PriceHistory,
+) -> Vec<ExportRow> {
+    if transaction.state != TransactionState::Confirmed {
+        return vec![];
+    }
+    let timestamp = transaction.created_at.timestamp();
+    let amount = |asset_id: String, value: &str| -> Option<(ExportAmount, Option<f64>)> {
+        let (symbol, decimals) = assets.get(&asset_id)?;
+        let value = format_units(value, *decimals)?;
+        let market_value = prices
+            .get(&asset_id)
+            .and_then(|x| price_at(x, timestamp))
+            .zip(value.parse::<f64>().ok())
+            .map(|(price, value)| price * value);
+        Some((
+            ExportAmount {
+                symbol: symbol.clone(),
+                value,
+            },
+            market_value,
+        ))
+    };
+    let row = |kind: ExportRowKind,
+               sent: Option<(ExportAmount, Option<f64>)>,
+               received: Option<(ExportAmount, Option<f64>)>|
+     -> Option<ExportRow> {
+        if sent.is_none() && received.is_none() {
+            return None;
+        }
+        let value = match (&sent, &received) {
+            (_, Some((_, value))) => *value,
+            (Some((_, value)), None) => *value,
+            (None, None) => None,
+        };
+        Some(ExportRow {
+            date: transaction.created_at,
+            kind,
+            sent: sent.map(|x| x.0),
+            received: received.map(|x| x.0),
+            value,
+            hash: transaction.hash.clone(),
+        })
+    };
+
+    let asset_id = transaction.asset_id.to_string();
+    let mut rows = match transaction.transaction_type {
+        TransactionType::Transfer if is_self_transfer(transaction, addresses) => vec![],
+        TransactionType::Transfer => match transaction.direction {
+            TransactionDirection::Incoming => vec![row(
+                ExportRowKind::Receive,
+                None,
+                amount(asset_id, &transaction.value),
+            )],
+            TransactionDirection::Outgoing => vec![row(
+                ExportRowKind::Send,
+                amount(asset_id, &transaction.value),
+                None,
+            )],
+            TransactionDirection::SelfTransfer => vec![],
+        },
+        TransactionType::StakeRewards => vec![row(
+            ExportRowKind::Reward,
+            None,
+            amount(asset_id, &transaction.value),
+        )],
+        TransactionType::Swap => transaction
+            .metadata
+            .clone()
+            .and_then(|metadata| serde_json::from_value::<TransactionSwapMetadata>(metadata).ok())
+            .map(|metadata| {
+                vec![row(
+                    ExportRowKind::Swap,
+                    amount(metadata.from_asset.to_string(), &metadata.from_value),
+                    amount(metadata.to_asset.to_string(), &metadata.to_value),
+                )]
+            })
+            .unwrap_or_default(),
+        TransactionType::TokenApproval
+        | TransactionType::StakeDelegate
+        | TransactionType::StakeUndelegate
+        | TransactionType::StakeRedelegate
+        | TransactionType::StakeWithdraw => vec![],
+    };
+
+    if is_fee_payer(transaction) {
+        rows.push(row(
+            ExportRowKind::Fee,
+            amount(transaction.fee_asset_id.to_string(), &transaction.fee),
+            None,
+        ));
+    }
+    rows.into_iter().flatten().collect()
+}
+
+// exact decimal representation of a base unit amount, zero amounts are left out
+pub fn format_units(value: &str, decimals: i32) -> Option<String> {
+    if value.is_empty() || !value.chars().all(|x| x.is_ascii_digit()) || decimals < 0 {
+        return None;
+    }
+    let digits = value.trim_start_matches('0');
+    if digits.is_empty() {
+        return None;
+    }
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        Some(integer.to_string())
+    } else {
+        Some(format!("{}.{}", integer, fraction))
+    }
+}
+
+pub fn render(rows: &[ExportRow], format: TransactionsExportFormat, currency: &str) -> String {
+    let header: &[&str] = match format {
+        TransactionsExportFormat::Csv => &[
+            "Date",
+            "Type",
+            "Sent Amount",
+            "Sent Currency",
+            "Received Amount",
+            "Received Currency",
+            "Value",
+            "Value Currency",
+            "Transaction Hash",
+        ],
+        TransactionsExportFormat::Koinly => &[
+            "Date",
+            "Sent Amount",
+            "Sent Currency",
+            "Received Amount",
+            "Received Currency",
+            "Fee Amount",
+            "Fee Currency",
+            "Net Worth Amount",
+            "Net Worth Currency",
+            "Label",
+            "Description",
+            "TxHash",
+        ],
+        TransactionsExportFormat::CoinTracker => &[
+            "Date",
+            "Received Quantity",
+            "Received Currency",
+            "Sent Quantity",
+            "Sent Currency",
+            "Fee Amount",
+            "Fee Currency",
+            "Tag",
+        ],
+    };
+
+    let mut lines = vec![header.join(",")];
+    for row in rows {
+        let fields = match format {
+            TransactionsExportFormat::Csv => csv_fields(row, currency),
+            TransactionsExportFormat::Koinly => koinly_fields(row, currency),
+            TransactionsExportFormat::CoinTracker => coin_tracker_fields(row),
+        };
+        lines.push(
+            fields
+                .iter()
+                .map(|x| csv_field(x))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    lines.join("\n") + "\n"
+}
+
+fn amount_fields(amount: &Option<ExportAmount>) -> [String; 2] {
+    match amount {
+        Some(amount) => [amount.value.clone(), amount.symbol.clone()],
+        None => [String::new(), String::new()],
+    }
+}
+
+fn value_fields(value: Option<f64>, currency: &str) -> [String; 2] {
+    match value {
+        Some(value) => [format!("{:.2}", value), currency.to_string()],
+        None => [String::new(), String::new()],
+    }
+}
+
+fn csv_fields(row: &ExportRow, currency: &str) -> Vec<String> {
+    let [sent_amount, sent_currency] = amount_fields(&row.sent);
+    let [received_amount, received_currency] = amount_fields(&row.received);
+    let [value, value_currency] = value_fields(row.value, currency);
+    vec![
+        row.date.to_rfc3339(),
+        row.kind.as_str().to_string(),
+        sent_amount,
+        sent_currency,
+        received_amount,
+        received_currency,
+        value,
+        value_currency,
+        row.hash.clone(),
+    ]
+}
+
+// fee rows move the sent amount to the fee columns
+fn sent_and_fee(row: &ExportRow) -> (&Option<ExportAmount>, &Option<ExportAmount>) {
+    match row.kind {
+        ExportRowKind::Fee => (&None, &row.sent),
+        _ => (&row.sent, &None),
+    }
+}
+
+fn koinly_fields(row: &ExportRow, currency: &str) -> Vec<String> {
+    let (sent, fee) = sent_and_fee(row);
+    let [sent_amount, sent_currency] = amount_fields(sent);
+    let [received_amount, received_currency] = amount_fields(&row.received);
+    let [fee_amount, fee_currency] = amount_fields(fee);
+    let [value, value_currency] = value_fields(row.value, currency);
+    let label = match row.kind {
+        ExportRowKind::Reward => "staking",
+        ExportRowKind::Send | ExportRowKind::Receive | ExportRowKind::Swap | ExportRowKind::Fee => {
+            ""
+        }
+    };
+    vec![
+        row.date.format("%Y-%m-%d %H:%M UTC").to_string(),
+        sent_amount,
+        sent_currency,
+        received_amount,
+        received_currency,
+        fee_amount,
+        fee_currency,
+        value,
+        value_currency,
+        label.to_string(),
+        row.kind.as_str().to_string(),
+        row.hash.clone(),
+    ]
+}
+
+fn coin_tracker_fields(row: &ExportRow) -> Vec<String> {
+    let (sent, fee) = sent_and_fee(row);
+    let [sent_amount, sent_currency] = amount_fields(sent);
+    let [received_amount, received_currency] = amount_fields(&row.received);
+    let [fee_amount, fee_currency] = amount_fields(fee);
+    let tag = match row.kind {
+        ExportRowKind::Reward => "stake",
+        ExportRowKind::Send | ExportRowKind::Receive | ExportRowKind::Swap | ExportRowKind::Fee => {
+            ""
+        }
+    };
+    vec![
+        row.date.format("%m/%d/%Y %H:%M:%S").to_string(),
+        received_amount,
+        received_currency,
+        sent_amount,
+        sent_currency,
+        fee_amount,
+        fee_currency,
+        tag.to_string(),
+    ]
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testkit::transaction;
+    use std::str::FromStr;
+
+    const VALUE: &str = "1500000000000000000";
+
+    fn assets() -> ExportAssets {
+        HashMap::from([
+            ("ethereum".to_string(), ("ETH".to_string(), 18)),
+            (
+                "ethereum_0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string(),
+                ("USDT".to_string(), 6),
+            ),
+        ])
+    }
+
+    fn prices() -> PriceHistory {
+        PriceHistory::from([("ethereum".to_string(), vec![(1717977600, 3000.0)])])
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(
+            format_units("1500000000000000000", 18),
+            Some("1.5".to_string())
+        );
+        assert_eq!(format_units("1000", 6), Some("0.001".to_string()));
+        assert_eq!(format_units("2000000", 6), Some("2".to_string()));
+        assert_eq!(format_units("42", 0), Some("42".to_string()));
+        assert_eq!(format_units("0", 18), None);
+        assert_eq!(format_units("1.5", 18), None);
+    }
+
+    #[test]
+    fn test_export_rows_transfer() {
+        let outgoing = transaction(
+            TransactionType::Transfer,
+            TransactionDirection::Outgoing,
+            VALUE,
+        );
+        let rows = export_rows(&outgoing, &["0xfrom".to_string()], &assets(), &prices());
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].kind, ExportRowKind::Send);
+        assert_eq!(rows[0].sent.as_ref().unwrap().value, "1.5");
+        assert_eq!(rows[0].value, Some(4500.0));
+        assert_eq!(rows[1].kind, ExportRowKind::Fee);
+        assert_eq!(rows[1].sent.as_ref().unwrap().value, "0.001");
+
+        // no value before the first known price
+        let prices = PriceHistory::from([("ethereum".to_string(), vec![(1718064000, 3000.0)])]);
+        let rows = export_rows(&outgoing, &["0xfrom".to_string()], &assets(), &prices);
+        assert_eq!(rows[0].value, None);
+    }
+
+    #[test]
+    fn test_export_rows_swap_and_rewards() {
+        let swap = transaction(TransactionType::Swap, TransactionDirection::Outgoing, VALUE);
+        let rewards = transaction(
+            TransactionType::StakeRewards,
+            TransactionDirection::Incoming,
+            VALUE,
+        );
+        let addresses = vec!["0xto".to_string()];
+
+        let rows = export_rows(&swap, &addresses, &assets(), &prices());
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].kind, ExportRowKind::Swap);
+        assert_eq!(rows[0].sent.as_ref().unwrap().symbol, "ETH");
+        assert_eq!(rows[0].received.as_ref().unwrap().value, "3500");
+        // the received asset has no price
+        assert_eq!(rows[0].value, None);
+        assert_eq!(rows[1].kind, ExportRowKind::Fee);
+
+        let rows = export_rows(&rewards, &addresses, &assets(), &prices());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].kind, ExportRowKind::Reward);
+    }
+
+    #[test]
+    fn test_render() {
+        let transaction = transaction(
+            TransactionType::Transfer,
+            TransactionDirection::Outgoing,
+            VALUE,
+        );
+        let rows = export_rows(&transaction, &[], &assets(), &prices());
+
+        assert_eq!(
+            render(&rows, TransactionsExportFormat::Csv, "USD"),
+            "Date,Type,Sent Amount,Sent Currency,Received Amount,Received Currency,Value,Value Currency,Transaction Hash\n\
+             2024-06-10T06:13:20+00:00,send,1.5,ETH,,,4500.00,USD,0x1\n\
+             2024-06-10T06:13:20+00:00,fee,0.001,ETH,,,3.00,USD,0x1\n"
+        );
+        assert_eq!(
+            render(&rows, TransactionsExportFormat::Koinly, "USD"),
+            "Date,Sent Amount,Sent Currency,Received Amount,Received Currency,Fee Amount,Fee Currency,Net Worth Amount,Net Worth Currency,Label,Description,TxHash\n\
+             2024-06-10 06:13 UTC,1.5,ETH,,,,,4500.00,USD,,send,0x1\n\
+             2024-06-10 06:13 UTC,,,,,0.001,ETH,3.00,USD,,fee,0x1\n"
+        );
+        assert_eq!(
+            render(&rows, TransactionsExportFormat::CoinTracker, "USD"),
+            "Date,Received Quantity,Received Currency,Sent Quantity,Sent Currency,Fee Amount,Fee Currency,Tag\n\
+             06/10/2024 06:13:20,,,1.5,ETH,,,\n\
+             06/10/2024 06:13:20,,,,,0.001,ETH,\n"
+        );
+    }
+
+    #[test]
+    fn test_export_format() {
+        assert_eq!(
+            TransactionsExportFormat::from_str("koinly"),
+            Ok(TransactionsExportFormat::Koinly)
+        );
+        assert_eq!(
+            TransactionsExportFormat::from_str("CoinTracker"),
+            Ok(TransactionsExportFormat::CoinTracker)
+        );
+        assert_eq!(
+            TransactionsExportFormat::from_str("CSV"),
+            Ok(TransactionsExportFormat::Csv)
+        );
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("ETH"), "ETH");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+
+use primitives::TransactionsExportFormat;
+
+use crate::export::{export_rows, render, ExportAssets, ExportRow};
+use crate::portfolio_client::{transaction_asset_ids, PortfolioClient};
+
+pub struct ExportClient {
+    portfolio_client: PortfolioClient,
+}
+
+impl ExportClient {
+    pub fn new(database_url: &str, clichouse_database_url: &str) -> Self {
+        Self {
+            portfolio_client: PortfolioClient::new(database_url, clichouse_database_url),
+        }
+    }
+
+    // full history of the wallet in chronological order, valued in the device currency unless requested otherwise
+    pub async fn export_transactions(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+        format: TransactionsExportFormat,
+        currency: Option<String>,
+    ) -> Result<String, Box<dyn Error>> {
+        let currency = self.portfolio_client.get_currency(device_id, currency)?;
+        let (mut transactions, addresses) = self
+            .portfolio_client
+            .get_transactions(device_id, wallet_index)?;
+        transactions.sort_by_key(|x| x.created_at);
+
+        let asset_ids = transaction_asset_ids(&transactions);
+        let assets: ExportAssets = self
+            .portfolio_client
+            .get_assets(&asset_ids)?
+            .into_iter()
+            .map(|(asset_id, asset)| (asset_id, (asset.symbol, asset.decimals)))
+            .collect();
+        let prices = self
+            .portfolio_client
+            .get_price_history(&asset_ids, &currency)
+            .await?;
+
+        let rows = transactions
+            .iter()
+            .flat_map(|x| export_rows(x, &addresses, &assets, &prices))
+            .collect::<Vec<ExportRow>>();
+        Ok(render(&rows, format, &currency))
+    }
+}
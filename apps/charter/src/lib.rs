@@ -1,2 +1,9 @@
 pub mod charts_updater;
 pub mod client;
+pub mod export;
+pub mod export_client;
+pub mod portfolio;
+pub mod portfolio_client;
+
+#[cfg(test)]
+mod testkit;
@@ -1,3 +1,4 @@
+use cacher::CacherClient;
 use charter::{charts_updater::ChartsUpdater, client::ChartsClient};
 use coingecko::CoinGeckoClient;
 use pricer::client::PriceClient;
@@ -12,20 +13,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let coingecko_client = CoinGeckoClient::new(settings.coingecko.key.secret);
     let charts_client = ChartsClient::new(&settings.postgres.url, &settings.clickhouse.url);
     let price_client = PriceClient::new(&settings.redis.url, &settings.postgres.url);
-    let mut charts_updater = ChartsUpdater::new(charts_client, price_client, coingecko_client);
-
-    // updates charts
-    // only needed on initial setup
-    // println!("update charts: start");
-    // let result = charts_updater.update_charts_all().await;
-    // match result {
-    //     Ok(count) => {
-    //         println!("update charts all: {}", count)
-    //     }
-    //     Err(err) => {
-    //         println!("update charts all error: {}", err)
-    //     }
-    // }
+    let mut charts_updater = ChartsUpdater::new(
+        charts_client,
+        price_client,
+        coingecko_client,
+        CacherClient::new(&settings.redis.url),
+        settings.charter.backfill.days,
+        settings.charter.backfill.limit,
+        settings.charter.backfill.retry,
+    );
 
     loop {
         println!("update charts: start");
@@ -40,6 +36,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        println!("backfill charts: start");
+
+        match charts_updater.backfill_charts().await {
+            Ok(count) => {
+                println!("backfill charts: {}", count)
+            }
+            Err(err) => {
+                println!("backfill charts error: {}", err)
+            }
+        }
+
         thread::sleep(Duration::from_secs(settings.charter.timer));
     }
 }
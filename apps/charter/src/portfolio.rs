@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+
+use primitives::{
+    ChartValue, PortfolioAsset, Transaction, TransactionDirection, TransactionState,
+    TransactionSwapMetadata, TransactionType,
+};
+
+pub const DAY_SECONDS: i64 = 86400;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceChange {
+    pub asset_id: String,
+    pub value: f64,
+    pub timestamp: i64,
+}
+
+// daily closes per asset in the portfolio currency, ordered by timestamp
+pub type PriceHistory = HashMap<String, Vec<(i64, f64)>>;
+
+// balance changes of a confirmed transaction for the wallet, fees are paid by the sender only
+pub fn balance_changes(
+    transaction: &Transaction,
+    addresses: &[String],
+    decimals: &HashMap<String, i32>,
+) -> Vec<BalanceChange> {
+    if transaction.state != TransactionState::Confirmed {
+        return vec![];
+    }
+    let timestamp = transaction.created_at.timestamp();
+    let change = |asset_id: String, value: &str, sign: f64| -> Option<BalanceChange> {
+        let value = amount(value, *decimals.get(&asset_id)?)?;
+        Some(BalanceChange {
+            asset_id,
+            value: value * sign,
+            timestamp,
+        })
+    };
+
+    let sign = match transaction.direction {
+        _ if is_self_transfer(transaction, addresses) => 0.0,
+        TransactionDirection::Incoming => 1.0,
+        TransactionDirection::Outgoing => -1.0,
+        TransactionDirection::SelfTransfer => 0.0,
+    };
+    let asset_id = transaction.asset_id.to_string();
+    let mut changes = match transaction.transaction_type {
+        TransactionType::Transfer => vec![change(asset_id, &transaction.value, sign)],
+        TransactionType::StakeRewards => vec![change(asset_id, &transaction.value, 1.0)],
+        TransactionType::Swap => transaction
+            .metadata
+            .clone()
+            .and_then(|metadata| serde_json::from_value::<TransactionSwapMetadata>(metadata).ok())
+            .map(|metadata| {
+                vec![
+                    change(metadata.from_asset.to_string(), &metadata.from_value, -1.0),
+                    change(metadata.to_asset.to_string(), &metadata.to_value, 1.0),
+                ]
+            })
+            .unwrap_or_default(),
+        // staked funds are still owned by the wallet
+        TransactionType::TokenApproval
+        | TransactionType::StakeDelegate
+        | TransactionType::StakeUndelegate
+        | TransactionType::StakeRedelegate
+        | TransactionType::StakeWithdraw => vec![],
+    };
+
+    if is_fee_payer(transaction) {
+        changes.push(change(
+            transaction.fee_asset_id.to_string(),
+            &transaction.fee,
+            -1.0,
+        ));
+    }
+
+    changes
+        .into_iter()
+        .flatten()
+        .filter(|x| x.value != 0.0)
+        .collect()
+}
+
+// both sides belong to the wallet, only the fee changes balances
+pub fn is_self_transfer(transaction: &Transaction, addresses: &[String]) -> bool {
+    addresses.contains(&transaction.from) && addresses.contains(&transaction.to)
+}
+
+pub fn is_fee_payer(transaction: &Transaction) -> bool {
+    matches!(
+        transaction.direction,
+        TransactionDirection::Outgoing | TransactionDirection::SelfTransfer
+    ) || matches!(transaction.transaction_type, TransactionType::Swap)
+}
+
+fn amount(value: &str, decimals: i32) -> Option<f64> {
+    Some(value.parse::<f64>().ok()? / 10_f64.powi(decimals))
+}
+
+// latest price at or before the timestamp, none before the first known price
+pub fn price_at(prices: &[(i64, f64)], timestamp: i64) -> Option<f64> {
+    let index = prices.partition_point(|(date, _)| *date <= timestamp);
+    index.checked_sub(1).map(|index| prices[index].1)
+}
+
+// portfolio value at every timestamp, balances before `timestamps` are carried in
+pub fn portfolio_values(
+    changes: &[BalanceChange],
+    prices: &PriceHistory,
+    timestamps: &[i64],
+) -> Vec<ChartValue> {
+    let mut changes = changes.to_vec();
+    changes.sort_by_key(|x| x.timestamp);
+
+    let mut balances: HashMap<String, f64> = HashMap::new();
+    let mut index = 0;
+    timestamps
+        .iter()
+        .map(|timestamp| {
+            while index < changes.len() && changes[index].timestamp <= *timestamp {
+                *balances.entry(changes[index].asset_id.clone()).or_default() +=
+                    changes[index].value;
+                index += 1;
+            }
+            let value = balances
+                .iter()
+                .filter_map(|(asset_id, balance)| {
+                    Some(balance.max(0.0) * price_at(prices.get(asset_id)?, *timestamp)?)
+                })
+                .sum::<f64>();
+            ChartValue {
+                timestamp: *timestamp as i32,
+                value: value as f32,
+            }
+        })
+        .collect()
+}
+
+// average cost basis, every outgoing amount realizes profit or loss at the market price
+pub fn portfolio_assets(
+    changes: &[BalanceChange],
+    prices: &PriceHistory,
+    now: i64,
+) -> Vec<PortfolioAsset> {
+    let mut changes = changes.to_vec();
+    changes.sort_by_key(|x| x.timestamp);
+
+    let mut assets: Vec<PortfolioAsset> = Vec::new();
+    for change in changes {
+        let index = match assets.iter().position(|x| x.asset_id == change.asset_id) {
+            Some(index) => index,
+            None => {
+                assets.push(PortfolioAsset {
+                    asset_id: change.asset_id.clone(),
+                    balance: 0.0,
+                    value: 0.0,
+                    cost_basis: 0.0,
+                    realized_pnl: 0.0,
+                    unrealized_pnl: 0.0,
+                });
+                assets.len() - 1
+            }
+        };
+        let asset = &mut assets[index];
+        let price = prices
+            .get(&change.asset_id)
+            .and_then(|x| price_at(x, change.timestamp))
+            .unwrap_or_default();
+
+        if change.value > 0.0 {
+            asset.cost_basis += change.value * price;
+            asset.balance += change.value;
+        } else {
+            let amount = (-change.value).min(asset.balance);
+            let average_cost = if asset.balance > 0.0 {
+                asset.cost_basis / asset.balance
+            } else {
+                0.0
+            };
+            asset.realized_pnl += amount * (price - average_cost);
+            asset.cost_basis -= amount * average_cost;
+            asset.balance -= amount;
+        }
+    }
+
+    for asset in assets.iter_mut() {
+        let price = prices
+            .get(&asset.asset_id)
+            .and_then(|x| price_at(x, now))
+            .unwrap_or_default();
+        asset.value = asset.balance * price;
+        asset.unrealized_pnl = asset.value - asset.cost_basis;
+    }
+    assets
+}
+
+// daily points from the start of the period, or the first change if later, and the current time
+pub fn portfolio_timestamps(changes: &[BalanceChange], from: i64, now: i64) -> Vec<i64> {
+    let first = changes.iter().map(|x| x.timestamp).min().unwrap_or(now);
+    let start = from.max(first);
+    let mut timestamps: Vec<i64> = (start - start.rem_euclid(DAY_SECONDS) + DAY_SECONDS..now)
+        .step_by(DAY_SECONDS as usize)
+        .collect();
+    timestamps.push(now);
+    timestamps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testkit::transaction;
+
+    fn change(asset_id: &str, value: f64, timestamp: i64) -> BalanceChange {
+        BalanceChange {
+            asset_id: asset_id.to_string(),
+            value,
+            timestamp,
+        }
+    }
+
+    fn decimals() -> HashMap<String, i32> {
+        HashMap::from([
+            ("ethereum".to_string(), 18),
+            (
+                "ethereum_0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string(),
+                6,
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_balance_changes_transfer() {
+        let incoming = transaction(
+            TransactionType::Transfer,
+            TransactionDirection::Incoming,
+            "2000000000000000000",
+        );
+        let outgoing = transaction(
+            TransactionType::Transfer,
+            TransactionDirection::Outgoing,
+            "1000000000000000000",
+        );
+        let addresses = vec!["0xfrom".to_string()];
+
+        assert_eq!(
+            balance_changes(&incoming, &addresses, &decimals()),
+            vec![change("ethereum", 2.0, 1718000000)]
+        );
+        assert_eq!(
+            balance_changes(&outgoing, &addresses, &decimals()),
+            vec![
+                change("ethereum", -1.0, 1718000000),
+                change("ethereum", -0.001, 1718000000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_balance_changes_swap() {
+        let swap = transaction(
+            TransactionType::Swap,
+            TransactionDirection::Outgoing,
+            "1000000000000000000",
+        );
+
+        assert_eq!(
+            balance_changes(&swap, &["0xfrom".to_string()], &decimals()),
+            vec![
+                change("ethereum", -1.0, 1718000000),
+                change(
+                    "ethereum_0xdAC17F958D2ee523a2206206994597C13D831ec7",
+                    3500.0,
+                    1718000000
+                ),
+                change("ethereum", -0.001, 1718000000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_price_at() {
+        let prices = vec![(100, 1.0), (200, 2.0)];
+
+        assert_eq!(price_at(&prices, 50), None);
+        assert_eq!(price_at(&prices, 150), Some(1.0));
+        assert_eq!(price_at(&prices, 200), Some(2.0));
+        assert_eq!(price_at(&[], 200), None);
+    }
+
+    #[test]
+    fn test_portfolio_values() {
+        let changes = vec![change("ethereum", 2.0, 100), change("ethereum", -1.0, 250)];
+        let prices = PriceHistory::from([("ethereum".to_string(), vec![(0, 10.0), (200, 20.0)])]);
+
+        assert_eq!(
+            portfolio_values(&changes, &prices, &[50, 150, 300]),
+            vec![
+                ChartValue {
+                    timestamp: 50,
+                    value: 0.0
+                },
+                ChartValue {
+                    timestamp: 150,
+                    value: 20.0
+                },
+                ChartValue {
+                    timestamp: 300,
+                    value: 20.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_portfolio_assets() {
+        let changes = vec![
+            change("ethereum", 1.0, 100),
+            change("ethereum", 1.0, 200),
+            change("ethereum", -1.0, 300),
+        ];
+        let prices = PriceHistory::from([(
+            "ethereum".to_string(),
+            vec![(100, 10.0), (200, 20.0), (300, 30.0), (400, 40.0)],
+        )]);
+        let assets = portfolio_assets(&changes, &prices, 400);
+
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].balance, 1.0);
+        assert_eq!(assets[0].cost_basis, 15.0);
+        assert_eq!(assets[0].realized_pnl, 15.0);
+        assert_eq!(assets[0].value, 40.0);
+        assert_eq!(assets[0].unrealized_pnl, 25.0);
+    }
+
+    #[test]
+    fn test_portfolio_timestamps() {
+        let changes = vec![change("ethereum", 1.0, DAY_SECONDS * 10 + 100)];
+
+        assert_eq!(
+            portfolio_timestamps(&changes, 0, DAY_SECONDS * 12 + 50),
+            vec![DAY_SECONDS * 11, DAY_SECONDS * 12, DAY_SECONDS * 12 + 50]
+        );
+        assert_eq!(
+            portfolio_timestamps(&[], 0, DAY_SECONDS * 12 + 50),
+            vec![DAY_SECONDS * 12 + 50]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use chrono::Utc;
+use primitives::{
+    ChartPeriod, Portfolio, Transaction, TransactionsFetchOption, DEFAULT_FIAT_CURRENCY,
+};
+use storage::{models::Asset, ClickhouseDatabase, DatabaseClient};
+
+use crate::portfolio::{
+    balance_changes, portfolio_assets, portfolio_timestamps, portfolio_values, PriceHistory,
+};
+
+#[derive(Debug)]
+pub enum PortfolioError {
+    UnknownDevice,
+    Database(Box<dyn Error>),
+}
+
+impl fmt::Display for PortfolioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDevice => write!(f, "unknown device"),
+            Self::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for PortfolioError {}
+
+impl From<Box<dyn Error>> for PortfolioError {
+    fn from(error: Box<dyn Error>) -> Self {
+        Self::Database(error)
+    }
+}
+
+pub struct PortfolioClient {
+    database: DatabaseClient,
+    clickhouse_database: ClickhouseDatabase,
+}
+
+impl PortfolioClient {
+    pub fn new(database_url: &str, clichouse_database_url: &str) -> Self {
+        let database = DatabaseClient::new(database_url);
+        let clickhouse_database = ClickhouseDatabase::new(clichouse_database_url);
+        Self {
+            database,
+            clickhouse_database,
+        }
+    }
+
+    // value series and per asset profit and loss, in the device currency unless requested otherwise
+    pub async fn get_portfolio(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+        period: ChartPeriod,
+        currency: Option<String>,
+    ) -> Result<Portfolio, PortfolioError> {
+        let currency = self.get_currency(device_id, currency)?;
+        let (transactions, addresses) = self.get_transactions(device_id, wallet_index)?;
+        let asset_ids = transaction_asset_ids(&transactions);
+        let decimals = self
+            .get_assets(&asset_ids)?
+            .into_iter()
+            .map(|(asset_id, asset)| (asset_id, asset.decimals))
+            .collect::<HashMap<String, i32>>();
+
+        let changes = transactions
+            .iter()
+            .flat_map(|x| balance_changes(x, &addresses, &decimals))
+            .collect::<Vec<_>>();
+        let prices = self.get_price_history(&asset_ids, &currency).await?;
+
+        let now = Utc::now().timestamp();
+        let from = now - period.minutes() as i64 * 60;
+        let timestamps = portfolio_timestamps(&changes, from, now);
+
+        Ok(Portfolio {
+            currency,
+            values: portfolio_values(&changes, &prices, &timestamps),
+            assets: portfolio_assets(&changes, &prices, now),
+        })
+    }
+
+    pub fn get_currency(
+        &mut self,
+        device_id: &str,
+        currency: Option<String>,
+    ) -> Result<String, PortfolioError> {
+        match currency {
+            Some(currency) => Ok(currency),
+            None => Ok(self
+                .database
+                .get_device(device_id)
+                .map_err(|_| PortfolioError::UnknownDevice)?
+                .currency),
+        }
+    }
+
+    // transactions of the wallet with the subscribed addresses they were matched with
+    pub fn get_transactions(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+    ) -> Result<(Vec<Transaction>, Vec<String>), Box<dyn Error>> {
+        let subscriptions = self
+            .database
+            .get_subscriptions_by_device_id_wallet_index(device_id, wallet_index)?;
+        let addresses = subscriptions
+            .iter()
+            .map(|x| x.address.clone())
+            .collect::<Vec<String>>();
+        let chains = subscriptions
+            .iter()
+            .map(|x| x.chain.clone())
+            .collect::<Vec<String>>();
+        let options = TransactionsFetchOption::new(wallet_index);
+        let transactions = self
+            .database
+            .get_transactions_by_device_id(device_id, addresses.clone(), chains, options)?
+            .into_iter()
+            .map(|x| {
+                x.as_primitive(addresses.clone())
+                    .finalize(addresses.clone())
+            })
+            .collect::<Vec<_>>();
+        Ok((transactions, addresses))
+    }
+
+    // disabled assets are included, their transactions are still part of the history
+    pub fn get_assets(
+        &mut self,
+        asset_ids: &HashSet<String>,
+    ) -> Result<HashMap<String, Asset>, Box<dyn Error>> {
+        Ok(self
+            .database
+            .get_assets_all(asset_ids.iter().cloned().collect())?
+            .into_iter()
+            .map(|asset| (asset.id.clone(), asset))
+            .collect())
+    }
+
+    // assets without a price are left out of the valuation
+    pub async fn get_price_history(
+        &mut self,
+        asset_ids: &HashSet<String>,
+        currency: &str,
+    ) -> Result<PriceHistory, Box<dyn Error>> {
+        let base_rate = self.database.get_fiat_rate(DEFAULT_FIAT_CURRENCY)?;
+        let rate = self.database.get_fiat_rate(currency)?;
+        let default_rate = rate.rate / base_rate.rate;
+
+        // assets sharing a coin share its history
+        let prices_assets = self
+            .database
+            .get_prices_assets_for_asset_ids(asset_ids.iter().cloned().collect())?;
+        let coin_ids = prices_assets
+            .iter()
+            .map(|x| x.price_id.clone())
+            .collect::<HashSet<String>>();
+
+        let mut closes: HashMap<String, Vec<(i64, f64)>> = HashMap::new();
+        for chart in self
+            .clickhouse_database
+            .get_charts_daily_closes(coin_ids.into_iter().collect(), currency, default_rate)
+            .await?
+        {
+            closes
+                .entry(chart.coin_id)
+                .or_default()
+                .push((chart.date as i64, chart.close));
+        }
+        Ok(prices_assets
+            .into_iter()
+            .filter_map(|x| Some((x.asset_id, closes.get(&x.price_id)?.clone())))
+            .collect())
+    }
+}
+
+// assets moved by the transactions, including fee assets
+pub fn transaction_asset_ids(transactions: &[Transaction]) -> HashSet<String> {
+    transactions
+        .iter()
+        .flat_map(|x| {
+            let mut asset_ids = x.asset_ids();
+            asset_ids.push(x.fee_asset_id.to_string());
+            asset_ids
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+use chrono::DateTime;
+use primitives::{
+    AssetId, Chain, Transaction, TransactionDirection, TransactionState, TransactionSwapMetadata,
+    TransactionType,
+};
+
+// confirmed ether transfer from 0xfrom to 0xto at 1718000000, swaps trade 1 ETH for 3500 USDT
+pub fn transaction(
+    transaction_type: TransactionType,
+    direction: TransactionDirection,
+    value: &str,
+) -> Transaction {
+    let metadata = match transaction_type {
+        TransactionType::Swap => Some(
+            serde_json::to_value(TransactionSwapMetadata {
+                from_asset: AssetId::from_chain(Chain::Ethereum),
+                from_value: "1000000000000000000".to_string(),
+                to_asset: AssetId::from(
+                    Chain::Ethereum,
+                    Some("0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string()),
+                ),
+                to_value: "3500000000".to_string(),
+            })
+            .unwrap(),
+        ),
+        _ => None,
+    };
+    Transaction {
+        id: "ethereum_0x1".to_string(),
+        hash: "0x1".to_string(),
+        asset_id: AssetId::from_chain(Chain::Ethereum),
+        from: "0xfrom".to_string(),
+        to: "0xto".to_string(),
+        contract: None,
+        transaction_type,
+        state: TransactionState::Confirmed,
+        block_number: "1".to_string(),
+        sequence: "0".to_string(),
+        fee: "1000000000000000".to_string(),
+        fee_asset_id: AssetId::from_chain(Chain::Ethereum),
+        value: value.to_string(),
+        memo: None,
+        direction,
+        utxo_inputs: vec![],
+        utxo_outputs: vec![],
+        metadata,
+        created_at: DateTime::from_timestamp(1718000000, 0).unwrap(),
+    }
+}
@@ -0,0 +1,95 @@
+use std::error::Error;
+
+use chrono::Duration;
+use primitives::{FiatTransactionStatus, TransactionFiatMetadata};
+use storage::database::DatabaseClient;
+use storage::models::{GetFiatTransaction, Transaction};
+
+// Orders without a reported hash are only matched to a transfer that arrived shortly after them
+const FIAT_TRANSACTION_MATCH_WINDOW_HOURS: i64 = 2;
+
+// Links completed fiat purchases to the on-chain transfers that delivered them
+pub struct FiatTransactionsUpdater {
+    database: DatabaseClient,
+}
+
+impl FiatTransactionsUpdater {
+    pub fn new(database_url: &str) -> Self {
+        let database = DatabaseClient::new(database_url);
+        Self { database }
+    }
+
+    pub async fn update(&mut self) -> Result<usize, Box<dyn Error>> {
+        let fiat_transactions = self
+            .database
+            .get_fiat_transactions_unlinked(FiatTransactionStatus::Complete.as_ref())?;
+
+        let mut count = 0;
+        for fiat_transaction in fiat_transactions {
+            match self.link_transaction(&fiat_transaction) {
+                Ok(true) => count += 1,
+                Ok(false) => {}
+                Err(err) => println!(
+                    "fiat transaction link error: {}, id: {}",
+                    err, fiat_transaction.id
+                ),
+            }
+        }
+        Ok(count)
+    }
+
+    fn link_transaction(
+        &mut self,
+        fiat_transaction: &GetFiatTransaction,
+    ) -> Result<bool, Box<dyn Error>> {
+        let transaction = match self.get_transaction(fiat_transaction)? {
+            Some(transaction) => transaction,
+            None => return Ok(false),
+        };
+        let metadata = TransactionFiatMetadata {
+            provider_id: fiat_transaction.provider_id.clone(),
+            provider_transaction_id: fiat_transaction.provider_transaction_id.clone(),
+            fiat_amount: fiat_transaction.fiat_amount,
+            fiat_currency: fiat_transaction.fiat_currency.clone(),
+            fee_provider: fiat_transaction.fee_provider,
+            fee_network: fiat_transaction.fee_network,
+            fee_partner: fiat_transaction.fee_partner,
+        };
+        self.database.set_fiat_transaction_link(
+            fiat_transaction.id,
+            &transaction.id,
+            serde_json::to_value(metadata)?,
+        )?;
+        Ok(true)
+    }
+
+    fn get_transaction(
+        &mut self,
+        fiat_transaction: &GetFiatTransaction,
+    ) -> Result<Option<Transaction>, Box<dyn Error>> {
+        let asset_id = fiat_transaction.asset_id.clone().unwrap_or_default();
+
+        // provider reported hash, wait for the parser to index it
+        if let Some(hash) = fiat_transaction.transaction_hash.clone() {
+            return Ok(self
+                .database
+                .get_transactions_by_hash(&hash)?
+                .into_iter()
+                .find(|x| x.asset_id == asset_id));
+        }
+
+        // otherwise the only incoming transfer of the asset shortly after the order was created,
+        // with several candidates any of them could be an unrelated deposit
+        let address = fiat_transaction.address.clone().unwrap_or_default();
+        let transactions = self.database.get_transactions_incoming_transfers(
+            &address,
+            &asset_id,
+            fiat_transaction.created_at,
+            fiat_transaction.created_at + Duration::hours(FIAT_TRANSACTION_MATCH_WINDOW_HOURS),
+        )?;
+        match transactions.as_slice() {
+            [transaction] => Ok(Some(transaction.clone())),
+            _ => Ok(None),
+        }
+    }
+}
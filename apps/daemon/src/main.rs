@@ -1,5 +1,6 @@
 mod device_updater;
 mod fiat_assets_updater;
+mod fiat_transactions_updater;
 mod oneinch_updater;
 mod tokenlist_updater;
 mod version_updater;
@@ -11,6 +12,8 @@ use crate::version_updater::Client as VersionClient;
 use api_connector::AssetsClient;
 use fiat::FiatProviderFactory;
 use fiat_assets_updater::FiatAssetsUpdater;
+use fiat_transactions_updater::FiatTransactionsUpdater;
+use std::collections::HashMap;
 use std::thread;
 use std::time::Duration;
 
@@ -25,6 +28,7 @@ pub async fn main() {
 
     let providers = FiatProviderFactory::new_providers(settings::Settings::new().unwrap());
     let mut fiat_assets_updater = FiatAssetsUpdater::new(&settings.postgres.url, providers);
+    let mut fiat_transactions_updater = FiatTransactionsUpdater::new(&settings.postgres.url);
 
     let mut oneinch_updater = oneinch_updater::Client::new(
         swap_oneinch::OneInchClient::new(
@@ -32,6 +36,7 @@ pub async fn main() {
             settings.swap.oneinch.key,
             0.0,
             "".to_string(),
+            HashMap::new(),
         ),
         &settings.postgres.url,
     );
@@ -85,6 +90,15 @@ pub async fn main() {
     }
 
     loop {
+        match fiat_transactions_updater.update().await {
+            Ok(count) => {
+                println!("update fiat transactions linked: {}", count)
+            }
+            Err(err) => {
+                println!("update fiat transactions error: {}", err)
+            }
+        }
+
         let result = tokenlist_client.update().await;
         match result {
             Ok(count) => {
@@ -2,6 +2,8 @@ pub mod parser;
 pub use parser::Parser;
 pub mod parser_options;
 pub use parser_options::ParserOptions;
+pub mod pending_tracker;
+use pending_tracker::{PendingTracker, PendingTrackerOptions};
 pub mod pusher;
 use parser_proxy::{ParserProxy, ParserProxyUrlConfig};
 pub use pusher::Pusher;
@@ -9,6 +11,7 @@ pub mod parser_proxy;
 
 use std::{collections::HashMap, str::FromStr};
 
+use cacher::CacherClient;
 use primitives::Chain;
 use settings::Settings;
 use storage::DatabaseClient;
@@ -81,6 +84,7 @@ async fn parser_start(
         settings.pusher.ios.topic.clone(),
     );
     let database_client = DatabaseClient::new(settings.postgres.url.as_str());
+    let cacher_client = CacherClient::new(settings.redis.url.as_str());
 
     let url = settings_chain::ProviderFactory::url(chain, &settings);
     let node_urls = if node_urls.is_empty() {
@@ -88,10 +92,40 @@ async fn parser_start(
     } else {
         node_urls
     };
+
+    if let Some(provider) =
+        settings_chain::ProviderFactory::new_transaction_state_provider(chain, &node_urls[0])
+    {
+        let mut tracker = PendingTracker::new(
+            chain,
+            provider,
+            Pusher::new(
+                settings.pusher.url.clone(),
+                settings.postgres.url.clone(),
+                settings.pusher.ios.topic.clone(),
+            ),
+            DatabaseClient::new(settings.postgres.url.as_str()),
+            CacherClient::new(settings.redis.url.as_str()),
+            PendingTrackerOptions {
+                timeout: settings.parser.pending.timeout,
+                expiry: settings.parser.pending.expiry,
+            },
+        );
+        tokio::spawn(async move {
+            tracker.start().await;
+        });
+    }
+
     let config = ParserProxyUrlConfig { urls: node_urls };
     let proxy = ParserProxy::new(chain, config);
 
-    let mut parser = Parser::new(Box::new(proxy), pusher, database_client, parser_options);
+    let mut parser = Parser::new(
+        Box::new(proxy),
+        pusher,
+        database_client,
+        cacher_client,
+        parser_options,
+    );
     match parser.start().await {
         Ok(_) => {
             println!("parser {} start complete", chain)
@@ -6,8 +6,13 @@ use std::{
 };
 
 use crate::{ParserOptions, Pusher};
+use cacher::CacherClient;
+use chrono::NaiveDateTime;
 use gem_chain_rpc::ChainProvider;
-use primitives::Chain;
+use primitives::{
+    Chain, NumberFormatter, SubscriptionPreferences, Transaction, TransactionDirection,
+    TransactionStreamEvent, TransactionType, TransactionsCursor,
+};
 use storage::DatabaseClient;
 
 pub struct Parser {
@@ -15,6 +20,7 @@ pub struct Parser {
     provider: Box<dyn ChainProvider>,
     pusher: Pusher,
     database: DatabaseClient,
+    cacher: CacherClient,
     options: ParserOptions,
 }
 
@@ -29,6 +35,7 @@ impl Parser {
         provider: Box<dyn ChainProvider>,
         pusher: Pusher,
         database: DatabaseClient,
+        cacher: CacherClient,
         options: ParserOptions,
     ) -> Self {
         Self {
@@ -36,6 +43,7 @@ impl Parser {
             provider,
             pusher,
             database,
+            cacher,
             options,
         }
     }
@@ -174,6 +182,8 @@ impl Parser {
             .collect();
         let subscriptions = self.database.get_subscriptions(self.chain, addresses)?;
         let mut transactions_map: HashMap<String, primitives::Transaction> = HashMap::new();
+        // streamed once stored, so the events carry the storage order
+        let mut stream_transactions: Vec<(String, i32, Transaction)> = vec![];
 
         for subscription in subscriptions {
             for transaction in transactions.clone() {
@@ -189,9 +199,17 @@ impl Parser {
 
                     transactions_map.insert(transaction.clone().id, transaction.clone());
 
-                    let transaction = transaction
+                    let mut transaction = transaction
                         .finalize(vec![subscription.address.clone()])
                         .clone();
+                    transaction.direction =
+                        subscription_direction(&transaction, &subscription.address);
+
+                    stream_transactions.push((
+                        device.device_id.clone(),
+                        subscription.wallet_index,
+                        transaction.clone(),
+                    ));
 
                     if self
                         .options
@@ -204,6 +222,20 @@ impl Parser {
                         continue;
                     }
 
+                    let preferences = subscription.preferences();
+                    let amount =
+                        notification_amount(&mut self.database, &preferences, &transaction);
+                    if !is_notification_enabled(&preferences, &transaction, amount) {
+                        println!(
+                            "push muted: device: {}, transaction: {}",
+                            device.device_id, transaction.id
+                        );
+                        continue;
+                    }
+                    if !claim_push(&mut self.cacher, &device.device_id, &transaction.id).await {
+                        continue;
+                    }
+
                     match self
                         .pusher
                         .push(
@@ -225,7 +257,20 @@ impl Parser {
         }
 
         match self.store_transactions(transactions_map.clone()).await {
-            Ok(_) => {}
+            Ok(stored) => {
+                for (device_id, wallet_index, transaction) in stream_transactions {
+                    if let Some(updated_at) = stored.get(&transaction.id) {
+                        let cursor =
+                            TransactionsCursor::new(updated_at.and_utc(), transaction.id.clone());
+                        let event = TransactionStreamEvent {
+                            cursor: cursor.encode(),
+                            transaction,
+                        };
+                        self.publish_transaction(&device_id, wallet_index, &event)
+                            .await;
+                    }
+                }
+            }
             Err(err) => {
                 println!(
                     "transaction insert: chain: {}, error: {:?}",
@@ -241,10 +286,34 @@ impl Parser {
         })
     }
 
+    // streamed to connected clients of the device, independent of push preferences
+    async fn publish_transaction(
+        &mut self,
+        device_id: &str,
+        wallet_index: i32,
+        event: &TransactionStreamEvent,
+    ) {
+        let channel = cacher::transactions_channel(device_id, wallet_index);
+        let value = match serde_json::to_string(event) {
+            Ok(value) => value,
+            Err(err) => {
+                println!("publish transaction error: {:?}", err);
+                return;
+            }
+        };
+        if let Err(err) = self.cacher.publish(&channel, value).await {
+            println!(
+                "publish transaction: {}, error: {:?}",
+                event.transaction.id, err
+            );
+        }
+    }
+
+    // storage time by transaction id, transactions of unknown assets are not stored
     pub async fn store_transactions(
         &mut self,
         transactions_map: HashMap<String, primitives::Transaction>,
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<HashMap<String, NaiveDateTime>, Box<dyn Error + Send + Sync>> {
         let primitive_transactions = transactions_map
             .clone()
             .into_iter()
@@ -274,13 +343,152 @@ impl Parser {
             .collect::<Vec<storage::models::TransactionAddresses>>();
 
         if transactions.is_empty() || transaction_addresses.is_empty() {
-            return Ok(0);
+            return Ok(HashMap::new());
         }
 
-        let _ = self
+        let stored = self
             .database
-            .add_transactions(transactions.clone(), transaction_addresses)?;
+            .add_transactions(transactions, transaction_addresses)?;
+
+        Ok(stored.into_iter().collect())
+    }
+}
+
+// direction relative to the subscribed address, utxo transactions are already finalized for it
+pub fn subscription_direction(transaction: &Transaction, address: &str) -> TransactionDirection {
+    if transaction.asset_id.chain.is_utxo() {
+        return transaction.direction.clone();
+    }
+    match (transaction.from == address, transaction.to == address) {
+        (true, true) => TransactionDirection::SelfTransfer,
+        (true, false) => TransactionDirection::Outgoing,
+        (false, _) => TransactionDirection::Incoming,
+    }
+}
+
+// transfer amount in asset units, only looked up when a minimum amount is set
+pub fn notification_amount(
+    database: &mut DatabaseClient,
+    preferences: &SubscriptionPreferences,
+    transaction: &Transaction,
+) -> Option<f64> {
+    if preferences.min_amount.is_none()
+        || !matches!(transaction.transaction_type, TransactionType::Transfer)
+    {
+        return None;
+    }
+    database
+        .get_asset(transaction.asset_id.to_string().as_str())
+        .ok()
+        .and_then(|asset| NumberFormatter::value(transaction.value.as_str(), asset.decimals))
+        .and_then(|value| value.parse::<f64>().ok())
+}
+
+// false when the transaction was already pushed to the device, pushes anyway when redis fails
+pub async fn claim_push(cacher: &mut CacherClient, device_id: &str, transaction_id: &str) -> bool {
+    let key = cacher::transaction_push_key(device_id, transaction_id);
+    match cacher
+        .set_value_if_missing_with_expiration(
+            &key,
+            "1".to_string(),
+            cacher::TRANSACTION_PUSH_EXPIRATION,
+        )
+        .await
+    {
+        Ok(claimed) => claimed,
+        Err(err) => {
+            println!("push claim error: {:?}", err);
+            true
+        }
+    }
+}
+
+// transfers with an unknown amount are not filtered by the minimum amount
+pub fn is_notification_enabled(
+    preferences: &SubscriptionPreferences,
+    transaction: &Transaction,
+    amount: Option<f64>,
+) -> bool {
+    preferences.is_direction_enabled(&transaction.direction)
+        && preferences.is_asset_enabled(&transaction.asset_id)
+        && amount.map_or(true, |amount| preferences.is_amount_enabled(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{AssetId, TransactionState};
+
+    // ether transfer from 0xfrom to 0xto
+    fn transaction() -> Transaction {
+        Transaction::new(
+            "0x1".to_string(),
+            AssetId::from_chain(Chain::Ethereum),
+            "0xfrom".to_string(),
+            "0xto".to_string(),
+            None,
+            TransactionType::Transfer,
+            TransactionState::Confirmed,
+            "1".to_string(),
+            "0".to_string(),
+            "1000000000000000".to_string(),
+            AssetId::from_chain(Chain::Ethereum),
+            "1500000000000000000".to_string(),
+            None,
+            None,
+            chrono::Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_subscription_direction() {
+        let transaction = transaction();
+
+        assert_eq!(
+            subscription_direction(&transaction, "0xfrom"),
+            TransactionDirection::Outgoing
+        );
+        assert_eq!(
+            subscription_direction(&transaction, "0xto"),
+            TransactionDirection::Incoming
+        );
+        let self_transfer = Transaction {
+            to: "0xfrom".to_string(),
+            ..transaction
+        };
+        assert_eq!(
+            subscription_direction(&self_transfer, "0xfrom"),
+            TransactionDirection::SelfTransfer
+        );
+    }
+
+    #[test]
+    fn test_is_notification_enabled() {
+        let preferences = SubscriptionPreferences {
+            mute_incoming: true,
+            min_amount: Some(1.0),
+            ..SubscriptionPreferences::default()
+        };
+        // the provider reported the sender side, the receiver subscription must still be muted
+        let mut transaction = transaction();
+        transaction.direction = subscription_direction(&transaction, "0xto");
+        assert!(!is_notification_enabled(
+            &preferences,
+            &transaction,
+            Some(2.0)
+        ));
 
-        Ok(transactions.len())
+        transaction.direction = subscription_direction(&transaction, "0xfrom");
+        assert!(is_notification_enabled(
+            &preferences,
+            &transaction,
+            Some(2.0)
+        ));
+        assert!(!is_notification_enabled(
+            &preferences,
+            &transaction,
+            Some(0.5)
+        ));
+        assert!(is_notification_enabled(&preferences, &transaction, None));
     }
 }
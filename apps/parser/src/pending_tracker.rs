@@ -0,0 +1,273 @@
+use std::{error::Error, time::Duration};
+
+use cacher::CacherClient;
+use chrono::Utc;
+use gem_chain_rpc::{TransactionStateProvider, TransactionStatus};
+use primitives::{
+    Chain, Transaction, TransactionState, TransactionStreamEvent, TransactionsCursor,
+};
+use storage::{models::GetTransactionPending, DatabaseClient};
+
+use crate::parser::{
+    claim_push, is_notification_enabled, notification_amount, subscription_direction,
+};
+use crate::Pusher;
+
+#[derive(Debug, Clone)]
+pub struct PendingTrackerOptions {
+    pub timeout: u64,
+    pub expiry: u64,
+}
+
+// polls the node for transactions registered by devices right after broadcast
+pub struct PendingTracker {
+    chain: Chain,
+    provider: Box<dyn TransactionStateProvider>,
+    pusher: Pusher,
+    database: DatabaseClient,
+    cacher: CacherClient,
+    options: PendingTrackerOptions,
+}
+
+impl PendingTracker {
+    pub fn new(
+        chain: Chain,
+        provider: Box<dyn TransactionStateProvider>,
+        pusher: Pusher,
+        database: DatabaseClient,
+        cacher: CacherClient,
+        options: PendingTrackerOptions,
+    ) -> Self {
+        Self {
+            chain,
+            provider,
+            pusher,
+            database,
+            cacher,
+            options,
+        }
+    }
+
+    pub async fn start(&mut self) {
+        loop {
+            match self.update().await {
+                Ok(count) => {
+                    if count > 0 {
+                        println!("pending tracker: {}, updated: {}", self.chain, count);
+                    }
+                }
+                Err(err) => {
+                    println!("pending tracker: {}, error: {:?}", self.chain, err);
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(self.options.timeout)).await;
+        }
+    }
+
+    // a failing transaction is logged and retried on the next update, the others still run
+    pub async fn update(&mut self) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let values = self.database.get_transactions_pending(self.chain)?;
+        let mut updated = 0;
+
+        for (pending, transaction) in values {
+            let transaction_id = pending.transaction_id.clone();
+            match self.update_transaction(pending, transaction).await {
+                Ok(true) => updated += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    println!(
+                        "pending tracker: {}, transaction: {}, error: {:?}",
+                        self.chain, transaction_id, err
+                    );
+                }
+            }
+        }
+        Ok(updated)
+    }
+
+    // true once the transaction got its final state
+    async fn update_transaction(
+        &mut self,
+        pending: GetTransactionPending,
+        transaction: storage::models::Transaction,
+    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        // already stored with a final state by the parser, which also pushed it
+        if transaction.state != TransactionState::Pending.to_string() {
+            self.database
+                .delete_transaction_pending(&pending.transaction_id)?;
+            return Ok(false);
+        }
+
+        let transaction =
+            transaction.as_primitive(vec![transaction.from_address.clone().unwrap_or_default()]);
+        let is_expired = (Utc::now().naive_utc() - pending.created_at).num_seconds()
+            > self.options.expiry as i64;
+        let status = match self.provider.get_transaction_status(&transaction).await {
+            Ok(status) => status,
+            Err(err) => {
+                println!(
+                    "pending tracker: {}, transaction: {}, error: {:?}",
+                    self.chain, transaction.id, err
+                );
+                // an unreachable node must not keep a transaction pending forever
+                if !is_expired {
+                    return Ok(false);
+                }
+                TransactionStatus::Dropped
+            }
+        };
+        let Some(state) = transaction_state(status.clone(), is_expired) else {
+            return Ok(false);
+        };
+
+        let updated_at = self
+            .database
+            .update_transaction_state(&transaction.id, state.clone())?;
+        self.database.delete_transaction_pending(&transaction.id)?;
+
+        let transaction = Transaction {
+            state,
+            ..transaction
+        };
+        let cursor = TransactionsCursor::new(updated_at.and_utc(), transaction.id.clone());
+        self.notify(pending, transaction, status, cursor).await;
+        Ok(true)
+    }
+
+    // included transactions are also pushed by the parser, the first one to claim the push sends it
+    async fn notify(
+        &mut self,
+        pending: GetTransactionPending,
+        transaction: Transaction,
+        status: TransactionStatus,
+        cursor: TransactionsCursor,
+    ) {
+        let device = match self.database.get_device_by_id(pending.device_id) {
+            Ok(device) => device,
+            Err(err) => {
+                println!("pending tracker device error: {:?}", err);
+                return;
+            }
+        };
+
+        let channel = cacher::transactions_channel(&device.device_id, pending.wallet_index);
+        let event = TransactionStreamEvent {
+            cursor: cursor.encode(),
+            transaction: transaction.clone(),
+        };
+        if let Ok(value) = serde_json::to_string(&event) {
+            if let Err(err) = self.cacher.publish(&channel, value).await {
+                println!("pending tracker publish error: {:?}", err);
+            }
+        }
+
+        // registered by the sender, nothing is pushed once the device unsubscribed the address
+        let subscription = match self
+            .database
+            .get_subscriptions_by_device_id_wallet_index(&device.device_id, pending.wallet_index)
+        {
+            Ok(subscriptions) => subscriptions
+                .into_iter()
+                .find(|x| x.chain == self.chain.as_ref() && x.address == transaction.from),
+            Err(err) => {
+                println!("pending tracker subscription error: {:?}", err);
+                return;
+            }
+        };
+        let Some(subscription) = subscription else {
+            return;
+        };
+        let transaction = Transaction {
+            direction: subscription_direction(&transaction, &subscription.address),
+            ..transaction
+        };
+        let preferences = subscription.preferences();
+        let amount = notification_amount(&mut self.database, &preferences, &transaction);
+        if !is_notification_enabled(&preferences, &transaction, amount)
+            || !claim_push(&mut self.cacher, &device.device_id, &transaction.id).await
+        {
+            return;
+        }
+
+        match self
+            .pusher
+            .push_transaction_state(
+                device.as_primitive(),
+                transaction,
+                subscription.as_primitive(),
+                &status,
+            )
+            .await
+        {
+            Ok(result) => {
+                println!("pending tracker push: result: {:?}", result);
+            }
+            Err(err) => {
+                println!("pending tracker push: error: {:?}", err);
+            }
+        }
+    }
+}
+
+// None while the transaction can still be included
+fn transaction_state(status: TransactionStatus, is_expired: bool) -> Option<TransactionState> {
+    match status {
+        TransactionStatus::Confirmed => Some(TransactionState::Confirmed),
+        TransactionStatus::Failed | TransactionStatus::Replaced => Some(TransactionState::Failed),
+        TransactionStatus::Dropped if is_expired => Some(TransactionState::Failed),
+        TransactionStatus::Pending | TransactionStatus::Dropped => None,
+    }
+}
+
+// replaced and dropped transactions are stored as failed, the push tells them apart
+pub fn state_title(status: &TransactionStatus, title: String) -> Option<String> {
+    match status {
+        TransactionStatus::Pending => None,
+        TransactionStatus::Confirmed => Some(title),
+        TransactionStatus::Failed => Some(format!("Failed {}", title)),
+        TransactionStatus::Replaced => Some(format!("Replaced {}", title)),
+        TransactionStatus::Dropped => Some(format!("Dropped {}", title)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_state() {
+        assert_eq!(
+            transaction_state(TransactionStatus::Confirmed, false),
+            Some(TransactionState::Confirmed)
+        );
+        assert_eq!(
+            transaction_state(TransactionStatus::Replaced, false),
+            Some(TransactionState::Failed)
+        );
+        assert_eq!(transaction_state(TransactionStatus::Dropped, false), None);
+        assert_eq!(
+            transaction_state(TransactionStatus::Dropped, true),
+            Some(TransactionState::Failed)
+        );
+        assert_eq!(transaction_state(TransactionStatus::Pending, true), None);
+    }
+
+    #[test]
+    fn test_state_title() {
+        let title = "Transfer 1 ETH".to_string();
+
+        assert_eq!(
+            state_title(&TransactionStatus::Confirmed, title.clone()),
+            Some("Transfer 1 ETH".to_string())
+        );
+        assert_eq!(
+            state_title(&TransactionStatus::Replaced, title.clone()),
+            Some("Replaced Transfer 1 ETH".to_string())
+        );
+        assert_eq!(
+            state_title(&TransactionStatus::Failed, title.clone()),
+            Some("Failed Transfer 1 ETH".to_string())
+        );
+        assert_eq!(state_title(&TransactionStatus::Pending, title), None);
+    }
+}
@@ -6,22 +6,23 @@ use primitives::{
 };
 use storage::DatabaseClient;
 
-use api_connector::pusher::model::{Message, Notification};
-use api_connector::PusherClient;
+use api_connector::pusher::model::Message;
+use api_connector::DevicePusher;
+use gem_chain_rpc::TransactionStatus;
+
+use crate::pending_tracker::state_title;
 
 pub struct Pusher {
-    ios_topic: String,
-    client: PusherClient,
+    pusher: DevicePusher,
     database_client: DatabaseClient,
 }
 
 impl Pusher {
     pub fn new(url: String, database_url: String, ios_topic: String) -> Self {
-        let client = PusherClient::new(url.clone());
+        let pusher = DevicePusher::new(url, ios_topic);
         let database_client = DatabaseClient::new(&database_url);
         Self {
-            ios_topic,
-            client,
+            pusher,
             database_client,
         }
     }
@@ -158,13 +159,6 @@ impl Pusher {
         }
     }
 
-    pub fn get_topic(&self, platform: primitives::Platform) -> Option<String> {
-        match platform {
-            primitives::Platform::Android => None,
-            primitives::Platform::IOS => Some(self.ios_topic.clone()),
-        }
-    }
-
     pub async fn push(
         &mut self,
         device: primitives::Device,
@@ -172,25 +166,64 @@ impl Pusher {
         subscription: Subscription,
     ) -> Result<usize, Box<dyn Error>> {
         // only push if push is enabled and token is set
-        if !device.is_push_enabled || device.token.is_empty() {
+        if !DevicePusher::is_pushable(&device) {
             return Ok(0);
         }
 
         let message = self.message(transaction.clone(), subscription.clone())?;
+        // several watched addresses share a wallet, name the one the transaction belongs to
+        let is_watch_only = subscription
+            .preferences
+            .as_ref()
+            .is_some_and(|x| x.is_watch_only);
+        let title = if is_watch_only {
+            format!(
+                "{}: {}",
+                AddressFormatter::short(subscription.chain, subscription.address.as_str()),
+                message.title
+            )
+        } else {
+            message.title
+        };
+        self.send(device, transaction, title, message.message).await
+    }
+
+    // final state of a transaction registered by the device after broadcasting it
+    pub async fn push_transaction_state(
+        &mut self,
+        device: primitives::Device,
+        transaction: Transaction,
+        subscription: Subscription,
+        status: &TransactionStatus,
+    ) -> Result<usize, Box<dyn Error>> {
+        if !DevicePusher::is_pushable(&device) {
+            return Ok(0);
+        }
+
+        let message = self.message(transaction.clone(), subscription)?;
+        let Some(title) = state_title(status, message.title) else {
+            return Ok(0);
+        };
+        self.send(device, transaction, title, message.message).await
+    }
+
+    async fn send(
+        &mut self,
+        device: primitives::Device,
+        transaction: Transaction,
+        title: String,
+        message: Option<String>,
+    ) -> Result<usize, Box<dyn Error>> {
         let data = PushNotification {
             notification_type: PushNotificationTypes::Transaction,
             data: transaction,
         };
 
-        let notification = Notification {
-            tokens: vec![device.token],
-            platform: device.platform.as_i32(),
-            title: message.title,
-            message: message.message.unwrap_or_default(),
-            topic: self.get_topic(device.platform),
-            data: Some(data),
-        };
-        let response = self.client.push(notification).await?;
+        let message = Message { title, message };
+        let response = self
+            .pusher
+            .push(&device, message, serde_json::to_value(data).ok())
+            .await?;
 
         if !response.logs.is_empty() {
             println!("push logs: {:?}", response.logs);
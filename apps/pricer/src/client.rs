@@ -1,10 +1,11 @@
 use chrono::NaiveDateTime;
 use primitives::{asset_price::AssetPrices, Asset, AssetDetails, AssetMarketPrice, AssetScore};
 use redis::{AsyncCommands, RedisResult};
+use std::collections::HashMap;
 use std::error::Error;
 use storage::{
     models::{
-        price::{PriceAsset, PriceCache},
+        price::{PriceAsset, PriceCache, PriceSource},
         FiatRate,
     },
     DatabaseClient,
@@ -22,6 +23,8 @@ pub struct PriceClient {
 
 const PRICES_INSERT_BATCH_LIMIT: usize = 1000;
 const PRICES_ASSETS_INSERT_BATCH_LIMIT: usize = 1000;
+const PRICES_SOURCES_INSERT_BATCH_LIMIT: usize = 1000;
+const ASSETS_QUERY_BATCH_LIMIT: usize = 1000;
 
 impl PriceClient {
     pub fn new(redis_url: &str, database_url: &str) -> Self {
@@ -57,6 +60,29 @@ impl PriceClient {
         Ok(prices.len())
     }
 
+    pub fn set_prices_sources(
+        &mut self,
+        values: Vec<PriceSource>,
+    ) -> Result<usize, Box<dyn Error>> {
+        for chunk in values.chunks(PRICES_SOURCES_INSERT_BATCH_LIMIT) {
+            self.database.set_prices_sources(chunk.to_vec())?;
+        }
+        Ok(values.len())
+    }
+
+    // asset id to symbol
+    pub fn get_assets_symbols(
+        &mut self,
+        asset_ids: Vec<String>,
+    ) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        let mut symbols = HashMap::new();
+        for chunk in asset_ids.chunks(ASSETS_QUERY_BATCH_LIMIT) {
+            let assets = self.database.get_assets(chunk.to_vec())?;
+            symbols.extend(assets.into_iter().map(|x| (x.id, x.symbol)));
+        }
+        Ok(symbols)
+    }
+
     pub fn set_prices_assets(&mut self, values: Vec<PriceAsset>) -> Result<usize, Box<dyn Error>> {
         // filter non existing prices and assets
         let assets_ids = self
@@ -1,2 +1,5 @@
 pub mod client;
+pub mod price_aggregator;
+pub mod price_alert_evaluator;
 pub mod price_updater;
+pub mod providers;
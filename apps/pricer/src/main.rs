@@ -1,16 +1,38 @@
 use coingecko::CoinGeckoClient;
-use pricer::{client::PriceClient, price_updater::PriceUpdater};
+use pricer::{
+    client::PriceClient, price_aggregator::PriceAggregator,
+    price_alert_evaluator::PriceAlertEvaluator, price_updater::PriceUpdater,
+    providers::PriceProviderFactory,
+};
 use settings::Settings;
 use std::{thread, time::Duration};
+use storage::ClickhouseDatabase;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("pricer init");
 
     let settings = Settings::new().unwrap();
-    let coingecko_client = CoinGeckoClient::new(settings.coingecko.key.secret);
+    let coingecko_client = CoinGeckoClient::new(settings.coingecko.key.secret.clone());
     let price_client = PriceClient::new(&settings.redis.url, &settings.postgres.url);
-    let mut price_updater = PriceUpdater::new(price_client, coingecko_client.clone());
+    let providers = PriceProviderFactory::new_providers(settings.clone(), coingecko_client.clone());
+    let aggregator = PriceAggregator::new(
+        settings.pricer.sources.max_age,
+        settings.pricer.sources.max_deviation,
+    );
+    let mut price_updater = PriceUpdater::new(
+        price_client,
+        coingecko_client.clone(),
+        providers,
+        aggregator,
+        ClickhouseDatabase::new(&settings.clickhouse.url),
+    );
+    let mut price_alert_evaluator = PriceAlertEvaluator::new(
+        &settings.postgres.url,
+        settings.pusher.url.clone(),
+        settings.pusher.ios.topic.clone(),
+        settings.pricer.alerts.cooldown,
+    );
 
     println!("clean outdated asset: start");
 
@@ -26,15 +48,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    match price_updater.update_fiat_rates().await {
-        Ok(count) => {
-            println!("update rates: {}", count)
-        }
-        Err(err) => {
-            println!("update rates error: {}", err)
-        }
-    }
-
     println!("update prices assets: start");
 
     match price_updater.update_prices_assets().await {
@@ -58,6 +71,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     loop {
+        // rates are refreshed every cycle to keep a history for charts
+        match price_updater.update_fiat_rates().await {
+            Ok(count) => {
+                println!("update rates: {}", count)
+            }
+            Err(err) => {
+                println!("update rates error: {}", err)
+            }
+        }
+
         println!("update simple prices: start");
 
         match price_updater.update_prices_simple().await {
@@ -69,6 +92,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        println!("evaluate price alerts: start");
+
+        match price_alert_evaluator.evaluate().await {
+            Ok(count) => {
+                println!("evaluate price alerts: {}", count)
+            }
+            Err(err) => {
+                println!("evaluate price alerts error: {}", err)
+            }
+        }
+
         println!("update prices cache: start");
 
         match price_updater.update_prices_cache().await {
@@ -0,0 +1,128 @@
+use chrono::{Duration, NaiveDateTime};
+
+use crate::providers::PriceProviderPrice;
+
+// Combines prices reported by several sources for the same asset.
+// Stale prices are dropped, the rest are compared against their median and
+// prices deviating more than `max_deviation` are rejected as outliers.
+pub struct PriceAggregator {
+    max_age: Duration,
+    max_deviation: f64,
+}
+
+impl PriceAggregator {
+    pub fn new(max_age: u64, max_deviation: f64) -> Self {
+        Self {
+            max_age: Duration::seconds(max_age as i64),
+            max_deviation,
+        }
+    }
+
+    // prices are expected in source priority order
+    pub fn aggregate(
+        &self,
+        prices: &[PriceProviderPrice],
+        now: NaiveDateTime,
+    ) -> Option<PriceProviderPrice> {
+        let fresh = prices
+            .iter()
+            .filter(|x| x.price > 0.0 && now - x.last_updated_at <= self.max_age)
+            .collect::<Vec<_>>();
+        let primary = *fresh.first()?;
+        let reference = median(fresh.iter().map(|x| x.price).collect())?;
+        let inliers = fresh
+            .into_iter()
+            .filter(|x| (x.price - reference).abs() <= reference * self.max_deviation)
+            .collect::<Vec<_>>();
+
+        // no consensus between sources, trust the highest priority one
+        if inliers.is_empty() {
+            return Some(primary.clone());
+        }
+
+        // market data comes from the highest priority source reporting it
+        Some(PriceProviderPrice {
+            price_id: primary.price_id.clone(),
+            price: median(inliers.iter().map(|x| x.price).collect())?,
+            price_change_percentage_24h: inliers.iter().find_map(|x| x.price_change_percentage_24h),
+            market_cap: inliers.iter().find_map(|x| x.market_cap),
+            total_volume: inliers.iter().find_map(|x| x.total_volume),
+            last_updated_at: inliers.iter().map(|x| x.last_updated_at).max()?,
+        })
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let middle = values.len() / 2;
+    match values.len() % 2 {
+        0 => Some((values[middle - 1] + values[middle]) / 2.0),
+        _ => Some(values[middle]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn price(value: f64, age: i64, market_cap: Option<f64>) -> PriceProviderPrice {
+        PriceProviderPrice {
+            price_id: "bitcoin".to_string(),
+            price: value,
+            price_change_percentage_24h: None,
+            market_cap,
+            total_volume: None,
+            last_updated_at: Utc::now().naive_utc() - Duration::seconds(age),
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(vec![4.0, 1.0, 2.0, 3.0]), Some(2.5));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_outliers() {
+        let aggregator = PriceAggregator::new(600, 0.1);
+        let now = Utc::now().naive_utc();
+        let prices = vec![
+            price(100.0, 0, Some(1000.0)),
+            price(150.0, 0, None),
+            price(102.0, 0, None),
+        ];
+
+        let result = aggregator.aggregate(&prices, now).unwrap();
+
+        assert_eq!(result.price, 101.0);
+        assert_eq!(result.market_cap, Some(1000.0));
+    }
+
+    #[test]
+    fn test_aggregate_skips_stale_prices() {
+        let aggregator = PriceAggregator::new(600, 0.1);
+        let now = Utc::now().naive_utc();
+        let prices = vec![price(90.0, 3600, Some(1000.0)), price(100.0, 0, None)];
+
+        let result = aggregator.aggregate(&prices, now).unwrap();
+
+        assert_eq!(result.price, 100.0);
+        assert_eq!(result.market_cap, None);
+        assert_eq!(aggregator.aggregate(&[price(90.0, 3600, None)], now), None);
+    }
+
+    #[test]
+    fn test_aggregate_without_consensus() {
+        let aggregator = PriceAggregator::new(600, 0.1);
+        let now = Utc::now().naive_utc();
+        let prices = vec![price(100.0, 0, None), price(200.0, 0, None)];
+
+        assert_eq!(aggregator.aggregate(&prices, now).unwrap().price, 100.0);
+    }
+}
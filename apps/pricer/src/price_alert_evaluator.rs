@@ -0,0 +1,294 @@
+use std::{error::Error, str::FromStr};
+
+use api_connector::pusher::model::Message;
+use api_connector::DevicePusher;
+use chrono::{Duration, NaiveDateTime, Utc};
+use primitives::{PriceAlertType, PushNotificationPriceAlert, PushNotificationTypes};
+use storage::{
+    models::{Device, GetPriceAlert, Price},
+    DatabaseClient,
+};
+
+pub struct PriceAlertEvaluator {
+    database: DatabaseClient,
+    pusher: DevicePusher,
+    cooldown: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AlertState {
+    Notify,
+    // the condition no longer holds, the alert can notify again once it is met
+    Rearm,
+    Unchanged,
+}
+
+impl PriceAlertEvaluator {
+    pub fn new(database_url: &str, pusher_url: String, ios_topic: String, cooldown: u64) -> Self {
+        Self {
+            database: DatabaseClient::new(database_url),
+            pusher: DevicePusher::new(pusher_url, ios_topic),
+            cooldown: Duration::seconds(cooldown as i64),
+        }
+    }
+
+    // Push every alert whose condition was met since the last check, returns the number of alerts notified
+    pub async fn evaluate(&mut self) -> Result<usize, Box<dyn Error>> {
+        let now = Utc::now().naive_utc();
+        let alerts = self.database.get_price_alerts_with_prices()?;
+
+        let mut notified = Vec::new();
+        let mut rearmed = Vec::new();
+        for (alert, device, price, symbol) in alerts {
+            let alert_type = match PriceAlertType::from_str(&alert.alert_type) {
+                Ok(alert_type) => alert_type,
+                Err(_) => continue,
+            };
+            match alert_state(&alert, &alert_type, &price, now, self.cooldown) {
+                AlertState::Notify => {}
+                AlertState::Rearm => {
+                    rearmed.push(alert.id);
+                    continue;
+                }
+                AlertState::Unchanged => continue,
+            }
+            let message = message(&alert_type, &symbol, &price);
+            match self.push(&device, &alert, message).await {
+                Ok(_) => notified.push(alert.id),
+                Err(err) => {
+                    println!(
+                        "price alert push error: {}, device: {}, error: {}",
+                        alert.asset_id, device.device_id, err
+                    );
+                }
+            }
+        }
+
+        let count = notified.len();
+        self.database.set_price_alerts_notified(notified, now)?;
+        self.database.set_price_alerts_rearmed(rearmed)?;
+        Ok(count)
+    }
+
+    async fn push(
+        &mut self,
+        device: &Device,
+        alert: &GetPriceAlert,
+        message: Message,
+    ) -> Result<(), Box<dyn Error>> {
+        let device = device.as_primitive();
+        if !DevicePusher::is_pushable(&device) {
+            return Err("push is disabled for the device".into());
+        }
+        let data = PushNotificationPriceAlert {
+            notification_type: PushNotificationTypes::PriceAlert,
+            data: alert.as_primitive().ok_or("unknown price alert type")?,
+        };
+        let response = self
+            .pusher
+            .push(&device, message, serde_json::to_value(data).ok())
+            .await?;
+        if !response.logs.is_empty() {
+            println!("push logs: {:?}", response.logs);
+            self.database
+                .update_device_is_push_enabled(&device.id, false)?;
+        }
+        Ok(())
+    }
+}
+
+// an alert notifies once when its condition is met, and again only after the condition cleared in between.
+// cooldown limits notifications for prices moving back and forth around the value
+pub fn alert_state(
+    alert: &GetPriceAlert,
+    alert_type: &PriceAlertType,
+    price: &Price,
+    now: NaiveDateTime,
+    cooldown: Duration,
+) -> AlertState {
+    let triggered = is_triggered(alert_type, alert.value, price);
+    match (triggered, alert.is_triggered) {
+        (true, false) if !is_cooldown(alert.last_notified_at, now, cooldown) => AlertState::Notify,
+        (false, true) if price.price > 0.0 => AlertState::Rearm,
+        _ => AlertState::Unchanged,
+    }
+}
+
+pub fn is_triggered(alert_type: &PriceAlertType, value: f64, price: &Price) -> bool {
+    // missing prices are stored as zero, they should never trigger an alert
+    if price.price <= 0.0 {
+        return false;
+    }
+    match alert_type {
+        PriceAlertType::PriceAbove => price.price >= value,
+        PriceAlertType::PriceBelow => price.price <= value,
+        PriceAlertType::PricePercentChange24h => price.price_change_percentage_24h.abs() >= value,
+    }
+}
+
+pub fn is_cooldown(
+    last_notified_at: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+    cooldown: Duration,
+) -> bool {
+    match last_notified_at {
+        Some(last_notified_at) => now - last_notified_at < cooldown,
+        None => false,
+    }
+}
+
+pub fn message(alert_type: &PriceAlertType, symbol: &str, price: &Price) -> Message {
+    let value = format_price(price.price);
+    let title = match alert_type {
+        PriceAlertType::PriceAbove => format!("{} is above {}", symbol, value),
+        PriceAlertType::PriceBelow => format!("{} is below {}", symbol, value),
+        PriceAlertType::PricePercentChange24h => {
+            let direction = if price.price_change_percentage_24h >= 0.0 {
+                "up"
+            } else {
+                "down"
+            };
+            format!(
+                "{} is {} {:.2}% in 24h",
+                symbol,
+                direction,
+                price.price_change_percentage_24h.abs()
+            )
+        }
+    };
+    Message {
+        title,
+        message: Some(format!("Current price: {}", value)),
+    }
+}
+
+fn format_price(price: f64) -> String {
+    if price >= 1.0 {
+        format!("${:.2}", price)
+    } else {
+        format!("${:.6}", price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn price(price: f64, price_change_percentage_24h: f64) -> Price {
+        Price {
+            id: "bitcoin".to_string(),
+            price,
+            price_change_percentage_24h,
+            market_cap: 0.0,
+            market_cap_rank: 0,
+            total_volume: 0.0,
+            circulating_supply: 0.0,
+            total_supply: 0.0,
+            max_supply: 0.0,
+            last_updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_is_triggered() {
+        let price = price(65000.0, -6.5);
+
+        assert!(is_triggered(&PriceAlertType::PriceAbove, 60000.0, &price));
+        assert!(!is_triggered(&PriceAlertType::PriceAbove, 70000.0, &price));
+        assert!(is_triggered(&PriceAlertType::PriceBelow, 70000.0, &price));
+        assert!(!is_triggered(&PriceAlertType::PriceBelow, 60000.0, &price));
+        assert!(is_triggered(
+            &PriceAlertType::PricePercentChange24h,
+            5.0,
+            &price
+        ));
+        assert!(!is_triggered(
+            &PriceAlertType::PricePercentChange24h,
+            10.0,
+            &price
+        ));
+    }
+
+    #[test]
+    fn test_is_triggered_missing_price() {
+        assert!(!is_triggered(
+            &PriceAlertType::PriceBelow,
+            100.0,
+            &price(0.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn test_is_cooldown() {
+        let now = NaiveDate::from_ymd_opt(2024, 6, 14)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let cooldown = Duration::hours(24);
+
+        assert!(!is_cooldown(None, now, cooldown));
+        assert!(is_cooldown(Some(now - Duration::hours(1)), now, cooldown));
+        assert!(!is_cooldown(Some(now - Duration::hours(24)), now, cooldown));
+    }
+
+    #[test]
+    fn test_alert_state() {
+        let now = NaiveDate::from_ymd_opt(2024, 6, 14)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let cooldown = Duration::hours(1);
+        let alert_type = PriceAlertType::PricePercentChange24h;
+        let alert = GetPriceAlert {
+            id: 1,
+            device_id: 1,
+            asset_id: "bitcoin".to_string(),
+            alert_type: alert_type.as_ref().to_string(),
+            value: 5.0,
+            last_notified_at: None,
+            is_triggered: false,
+        };
+        let notified = GetPriceAlert {
+            is_triggered: true,
+            last_notified_at: Some(now - Duration::hours(2)),
+            ..alert.clone()
+        };
+
+        let moved = price(65000.0, -6.5);
+        let settled = price(65000.0, 1.5);
+
+        assert_eq!(
+            alert_state(&alert, &alert_type, &moved, now, cooldown),
+            AlertState::Notify
+        );
+        // still above the threshold after the cooldown, waits for the change to settle first
+        assert_eq!(
+            alert_state(&notified, &alert_type, &moved, now, cooldown),
+            AlertState::Unchanged
+        );
+        assert_eq!(
+            alert_state(&notified, &alert_type, &settled, now, cooldown),
+            AlertState::Rearm
+        );
+        assert_eq!(
+            alert_state(&alert, &alert_type, &settled, now, cooldown),
+            AlertState::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_message() {
+        let message = message(
+            &PriceAlertType::PricePercentChange24h,
+            "BTC",
+            &price(65000.0, -6.5),
+        );
+
+        assert_eq!(message.title, "BTC is down 6.50% in 24h");
+        assert_eq!(
+            message.message,
+            Some("Current price: $65000.00".to_string())
+        );
+    }
+}
@@ -1,24 +1,39 @@
 use crate::client::PriceClient;
-use chrono::{DateTime, Duration, Utc};
+use crate::price_aggregator::PriceAggregator;
+use crate::providers::{PriceProvider, PriceProviderAsset, PriceProviderPrice};
+use chrono::{Duration, Utc};
 use coingecko::mapper::{get_chain_for_coingecko_platform_id, get_coingecko_market_id_for_chain};
-use coingecko::{Coin, CoinGeckoClient, CoinMarket, SimplePrice};
+use coingecko::{Coin, CoinGeckoClient, CoinMarket};
 use primitives::chain::Chain;
 use primitives::{AssetId, DEFAULT_FIAT_CURRENCY};
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use storage::models::price::{PriceAsset, PriceCache};
-use storage::models::{FiatRate, Price};
+use storage::models::price::{PriceAsset, PriceCache, PriceSource};
+use storage::models::{CreateFiatRate, FiatRate, Price};
+use storage::ClickhouseDatabase;
 
 pub struct PriceUpdater {
     coin_gecko_client: CoinGeckoClient,
     price_client: PriceClient,
+    providers: Vec<Box<dyn PriceProvider + Send + Sync>>,
+    aggregator: PriceAggregator,
+    clickhouse_database: ClickhouseDatabase,
 }
 
 impl PriceUpdater {
-    pub fn new(price_client: PriceClient, coin_gecko_client: CoinGeckoClient) -> Self {
+    pub fn new(
+        price_client: PriceClient,
+        coin_gecko_client: CoinGeckoClient,
+        providers: Vec<Box<dyn PriceProvider + Send + Sync>>,
+        aggregator: PriceAggregator,
+        clickhouse_database: ClickhouseDatabase,
+    ) -> Self {
         PriceUpdater {
             coin_gecko_client,
             price_client,
+            providers,
+            aggregator,
+            clickhouse_database,
         }
     }
 
@@ -54,23 +69,98 @@ impl PriceUpdater {
         self.update_prices(u32::MAX).await
     }
 
+    // a failing source is skipped, prices are aggregated from the remaining ones
     pub async fn update_prices_simple(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
-        let ids = self.price_client.get_prices_ids()?;
-        let ids_chunks = ids.chunks(500);
-        for ids in ids_chunks {
-            let prices = self
-                .coin_gecko_client
-                .get_prices_by_ids(ids.to_vec(), DEFAULT_FIAT_CURRENCY)
-                .await?;
-            let prices = prices
-                .into_iter()
-                .map(|(id, price)| price_for_simple_price(id.as_str(), price))
-                .collect::<Vec<Price>>();
+        let prices = self.price_client.get_prices()?;
+        let assets = self.get_price_provider_assets(&prices)?;
 
-            let _ = self.price_client.set_prices_simple(prices);
+        let mut sources: HashMap<String, Vec<PriceProviderPrice>> = HashMap::new();
+        for provider in self.providers.iter() {
+            let values = match provider.get_prices(assets.clone()).await {
+                Ok(values) => values,
+                Err(err) => {
+                    println!("price provider {} error: {}", provider.name(), err);
+                    continue;
+                }
+            };
+            let prices_sources = values
+                .iter()
+                .map(|x| PriceSource {
+                    price_id: x.price_id.clone(),
+                    source: provider.name().to_string(),
+                    price: x.price,
+                    last_updated_at: x.last_updated_at,
+                })
+                .collect();
+            if let Err(err) = self.price_client.set_prices_sources(prices_sources) {
+                println!("price provider {} sources error: {}", provider.name(), err);
+            }
+            for value in values {
+                sources
+                    .entry(value.price_id.clone())
+                    .or_default()
+                    .push(value);
+            }
         }
 
-        Ok(ids.len())
+        let now = Utc::now().naive_utc();
+        let prices = prices
+            .into_iter()
+            .flat_map(|price| {
+                let value = self.aggregator.aggregate(sources.get(&price.id)?, now)?;
+                Some(price_for_provider_price(price, value))
+            })
+            .collect::<Vec<Price>>();
+
+        self.price_client.set_prices_simple(prices)
+    }
+
+    // symbols are only assigned to the price id with the largest market cap, as many tokens share a symbol
+    fn get_price_provider_assets(
+        &mut self,
+        prices: &[Price],
+    ) -> Result<Vec<PriceProviderAsset>, Box<dyn Error>> {
+        let prices_assets = self.price_client.get_prices_assets()?;
+        let symbols = self
+            .price_client
+            .get_assets_symbols(prices_assets.iter().map(|x| x.asset_id.clone()).collect())?;
+        let market_caps: HashMap<&str, f64> = prices
+            .iter()
+            .map(|x| (x.id.as_str(), x.market_cap))
+            .collect();
+
+        let mut symbols_price_ids: HashMap<String, (String, f64)> = HashMap::new();
+        for price_asset in prices_assets {
+            let symbol = match symbols.get(&price_asset.asset_id) {
+                Some(symbol) => symbol.to_uppercase(),
+                None => continue,
+            };
+            let market_cap = market_caps
+                .get(price_asset.price_id.as_str())
+                .copied()
+                .unwrap_or_default();
+            if market_cap <= 0.0 {
+                continue;
+            }
+            let entry = symbols_price_ids
+                .entry(symbol)
+                .or_insert((price_asset.price_id.clone(), market_cap));
+            if market_cap > entry.1 {
+                *entry = (price_asset.price_id, market_cap);
+            }
+        }
+        let price_ids_symbols: HashMap<String, String> = symbols_price_ids
+            .into_iter()
+            .map(|(symbol, (price_id, _))| (price_id, symbol))
+            .collect();
+
+        Ok(prices
+            .iter()
+            .map(|x| PriceProviderAsset {
+                price_id: x.id.clone(),
+                symbol: price_ids_symbols.get(&x.id).cloned(),
+            })
+            .collect())
     }
 
     pub async fn update_prices(&mut self, pages: u32) -> Result<usize, Box<dyn std::error::Error>> {
@@ -165,10 +255,15 @@ impl PriceUpdater {
     }
 
     pub async fn update_fiat_rates(&mut self) -> Result<usize, Box<dyn Error>> {
-        let rates = self
-            .coin_gecko_client
-            .get_fiat_rates()
-            .await?
+        let rates = self.coin_gecko_client.get_fiat_rates().await?;
+
+        // history is kept for charts in other currencies
+        let history = fiat_rates_history(&rates, Utc::now().timestamp() as u32);
+        if let Err(err) = self.clickhouse_database.add_fiat_rates(history).await {
+            println!("update fiat rates history error: {}", err);
+        }
+
+        let rates = rates
             .into_iter()
             .map(FiatRate::from_primitive)
             .collect::<Vec<_>>();
@@ -187,6 +282,22 @@ impl PriceUpdater {
     }
 }
 
+// coingecko rates are relative to BTC, history is stored relative to the default currency
+fn fiat_rates_history(rates: &[primitives::FiatRate], ts: u32) -> Vec<CreateFiatRate> {
+    let base_rate = match rates.iter().find(|x| x.symbol == DEFAULT_FIAT_CURRENCY) {
+        Some(rate) if rate.rate > 0.0 => rate.rate,
+        _ => return vec![],
+    };
+    rates
+        .iter()
+        .map(|x| CreateFiatRate {
+            symbol: x.symbol.clone(),
+            rate: x.rate / base_rate,
+            ts,
+        })
+        .collect()
+}
+
 fn price_for_market(market: CoinMarket) -> Price {
     Price::new(
         market.id,
@@ -202,23 +313,21 @@ fn price_for_market(market: CoinMarket) -> Price {
     )
 }
 
-fn price_for_simple_price(id: &str, price: SimplePrice) -> Price {
-    let last_updated_at = price
-        .last_updated_at
-        .and_then(|x| DateTime::from_timestamp(x as i64, 0))
-        .map(|x| x.naive_utc());
-
+// market data missing from the sources is kept from the previous update
+fn price_for_provider_price(price: Price, value: PriceProviderPrice) -> Price {
     Price::new(
-        id.to_string(),
-        price.usd.unwrap_or_default(),
-        price.usd_24h_change.unwrap_or_default(),
-        price.usd_market_cap.unwrap_or_default(),
-        0,
-        0.0,
-        0.0,
-        0.0,
-        0.0,
-        last_updated_at,
+        price.id,
+        value.price,
+        value
+            .price_change_percentage_24h
+            .unwrap_or(price.price_change_percentage_24h),
+        value.market_cap.unwrap_or(price.market_cap),
+        price.market_cap_rank,
+        value.total_volume.unwrap_or(price.total_volume),
+        price.circulating_supply,
+        price.total_supply,
+        price.max_supply,
+        Some(value.last_updated_at),
     )
 }
 
@@ -229,3 +338,35 @@ fn get_asset_id(chain: Chain, token_id: String) -> Option<String> {
     let token_id = AssetId::format_token_id(chain, token_id)?;
     format!("{}_{}", chain.as_ref(), token_id).into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(symbol: &str, rate: f64) -> primitives::FiatRate {
+        primitives::FiatRate {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            rate,
+        }
+    }
+
+    #[test]
+    fn test_fiat_rates_history() {
+        let rates = vec![rate("USD", 60000.0), rate("EUR", 54000.0)];
+        let history = fiat_rates_history(&rates, 1718000000);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].rate, 1.0);
+        assert_eq!(history[1].symbol, "EUR");
+        assert_eq!(history[1].rate, 0.9);
+        assert_eq!(history[1].ts, 1718000000);
+    }
+
+    #[test]
+    fn test_fiat_rates_history_missing_base_rate() {
+        let rates = vec![rate("EUR", 54000.0)];
+
+        assert!(fiat_rates_history(&rates, 1718000000).is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{PriceProvider, PriceProviderAsset, PriceProviderPrice};
+
+pub const PROVIDER_NAME: &str = "binance";
+const BINANCE_API_URL: &str = "https://api.binance.com";
+// USDT pairs have the deepest liquidity, USDT is treated as USD
+const QUOTE_ASSET: &str = "USDT";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker {
+    pub symbol: String,
+    pub last_price: String,
+    pub price_change_percent: String,
+    pub quote_volume: String,
+    pub close_time: i64,
+}
+
+pub struct BinanceProvider {
+    client: reqwest::Client,
+}
+
+impl BinanceProvider {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_tickers(&self) -> Result<Vec<Ticker>, reqwest::Error> {
+        let url = format!("{}/api/v3/ticker/24hr", BINANCE_API_URL);
+        self.client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<Ticker>>()
+            .await
+    }
+
+    fn map_price(price_id: String, ticker: &Ticker) -> Option<PriceProviderPrice> {
+        let price = ticker.last_price.parse::<f64>().ok().filter(|x| *x > 0.0)?;
+        let last_updated_at = DateTime::from_timestamp_millis(ticker.close_time)
+            .unwrap_or_else(Utc::now)
+            .naive_utc();
+        Some(PriceProviderPrice {
+            price_id,
+            price,
+            price_change_percentage_24h: ticker.price_change_percent.parse().ok(),
+            market_cap: None,
+            total_volume: ticker.quote_volume.parse().ok(),
+            last_updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl PriceProvider for BinanceProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER_NAME
+    }
+
+    async fn get_prices(
+        &self,
+        assets: Vec<PriceProviderAsset>,
+    ) -> Result<Vec<PriceProviderPrice>, Box<dyn Error + Send + Sync>> {
+        let tickers = self
+            .get_tickers()
+            .await?
+            .into_iter()
+            .map(|x| (x.symbol.clone(), x))
+            .collect::<HashMap<_, _>>();
+
+        let prices = assets
+            .into_iter()
+            .flat_map(|asset| {
+                let symbol = format!("{}{}", asset.symbol?.to_uppercase(), QUOTE_ASSET);
+                Self::map_price(asset.price_id, tickers.get(&symbol)?)
+            })
+            .collect();
+        Ok(prices)
+    }
+}
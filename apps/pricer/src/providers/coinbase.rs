@@ -0,0 +1,86 @@
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use primitives::DEFAULT_FIAT_CURRENCY;
+use serde::Deserialize;
+
+use super::{PriceProvider, PriceProviderAsset, PriceProviderPrice};
+
+pub const PROVIDER_NAME: &str = "coinbase";
+const COINBASE_API_URL: &str = "https://api.coinbase.com";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Response<T> {
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeRates {
+    pub currency: String,
+    // units of the symbol per one unit of currency
+    pub rates: HashMap<String, String>,
+}
+
+pub struct CoinbaseProvider {
+    client: reqwest::Client,
+}
+
+impl CoinbaseProvider {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_exchange_rates(
+        &self,
+        currency: &str,
+    ) -> Result<ExchangeRates, reqwest::Error> {
+        let url = format!("{}/v2/exchange-rates", COINBASE_API_URL);
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("currency", currency)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Response<ExchangeRates>>()
+            .await?;
+        Ok(response.data)
+    }
+}
+
+#[async_trait]
+impl PriceProvider for CoinbaseProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER_NAME
+    }
+
+    // exchange rates carry no timestamp or market data, the price is as of the request
+    async fn get_prices(
+        &self,
+        assets: Vec<PriceProviderAsset>,
+    ) -> Result<Vec<PriceProviderPrice>, Box<dyn Error + Send + Sync>> {
+        let rates = self.get_exchange_rates(DEFAULT_FIAT_CURRENCY).await?.rates;
+        let now = Utc::now().naive_utc();
+
+        let prices = assets
+            .into_iter()
+            .flat_map(|asset| {
+                let rate = rates
+                    .get(&asset.symbol?.to_uppercase())?
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|x| *x > 0.0)?;
+                Some(PriceProviderPrice {
+                    price_id: asset.price_id,
+                    price: 1.0 / rate,
+                    price_change_percentage_24h: None,
+                    market_cap: None,
+                    total_volume: None,
+                    last_updated_at: now,
+                })
+            })
+            .collect();
+        Ok(prices)
+    }
+}
@@ -0,0 +1,80 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use coingecko::{CoinGeckoClient, SimplePrice};
+use primitives::DEFAULT_FIAT_CURRENCY;
+
+use super::{PriceProvider, PriceProviderAsset, PriceProviderPrice};
+
+pub const PROVIDER_NAME: &str = "coingecko";
+const IDS_CHUNK_SIZE: usize = 500;
+
+pub struct CoinGeckoProvider {
+    client: CoinGeckoClient,
+}
+
+impl CoinGeckoProvider {
+    pub fn new(client: CoinGeckoClient) -> Self {
+        Self { client }
+    }
+
+    fn map_price(id: String, price: SimplePrice) -> Option<PriceProviderPrice> {
+        let last_updated_at = price
+            .last_updated_at
+            .and_then(|x| DateTime::from_timestamp(x as i64, 0))
+            .unwrap_or_else(Utc::now)
+            .naive_utc();
+        Some(PriceProviderPrice {
+            price_id: id,
+            price: price.usd?,
+            price_change_percentage_24h: price.usd_24h_change,
+            market_cap: price.usd_market_cap,
+            total_volume: price.usd_24h_vol,
+            last_updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl PriceProvider for CoinGeckoProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER_NAME
+    }
+
+    async fn get_prices(
+        &self,
+        assets: Vec<PriceProviderAsset>,
+    ) -> Result<Vec<PriceProviderPrice>, Box<dyn Error + Send + Sync>> {
+        let ids = assets.into_iter().map(|x| x.price_id).collect::<Vec<_>>();
+        let mut results = vec![];
+        let mut last_error = None;
+        // a failed chunk is skipped, prices from the other chunks are still returned
+        for (index, ids) in ids.chunks(IDS_CHUNK_SIZE).enumerate() {
+            match self
+                .client
+                .get_prices_by_ids(ids.to_vec(), DEFAULT_FIAT_CURRENCY)
+                .await
+            {
+                Ok(prices) => results.extend(
+                    prices
+                        .into_iter()
+                        .flat_map(|(id, price)| Self::map_price(id, price)),
+                ),
+                Err(err) => {
+                    println!(
+                        "coingecko prices chunk {} ({} ids) error: {}",
+                        index,
+                        ids.len(),
+                        err
+                    );
+                    last_error = Some(err.to_string());
+                }
+            }
+        }
+        match last_error {
+            Some(err) if results.is_empty() => Err(err.into()),
+            _ => Ok(results),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use primitives::DEFAULT_FIAT_CURRENCY;
+use serde::Deserialize;
+
+use super::{PriceProvider, PriceProviderAsset, PriceProviderPrice};
+
+pub const PROVIDER_NAME: &str = "coinmarketcap";
+const COINMARKETCAP_API_URL: &str = "https://pro-api.coinmarketcap.com";
+const SYMBOLS_CHUNK_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Response<T> {
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Quotes {
+    pub cmc_rank: Option<i64>,
+    pub quote: HashMap<String, Quote>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Quote {
+    pub price: Option<f64>,
+    pub volume_24h: Option<f64>,
+    pub percent_change_24h: Option<f64>,
+    pub market_cap: Option<f64>,
+    pub last_updated: DateTime<Utc>,
+}
+
+pub struct CoinMarketCapProvider {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl CoinMarketCapProvider {
+    pub fn new(client: reqwest::Client, api_key: String) -> Self {
+        Self { client, api_key }
+    }
+
+    // several listings can share a symbol, they are returned per symbol
+    pub async fn get_quotes(
+        &self,
+        symbols: Vec<String>,
+    ) -> Result<HashMap<String, Vec<Quotes>>, reqwest::Error> {
+        let url = format!("{}/v2/cryptocurrency/quotes/latest", COINMARKETCAP_API_URL);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-CMC_PRO_API_KEY", self.api_key.as_str())
+            .query(&[
+                ("symbol", symbols.join(",").as_str()),
+                ("convert", DEFAULT_FIAT_CURRENCY),
+                ("skip_invalid", "true"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Response<HashMap<String, Vec<Quotes>>>>()
+            .await?;
+        Ok(response.data)
+    }
+
+    // the best ranked listing is used for the symbol
+    fn map_price(price_id: String, quotes: &[Quotes]) -> Option<PriceProviderPrice> {
+        let quote = quotes
+            .iter()
+            .filter(|x| x.cmc_rank.is_some())
+            .min_by_key(|x| x.cmc_rank)?
+            .quote
+            .get(DEFAULT_FIAT_CURRENCY)?;
+        Some(PriceProviderPrice {
+            price_id,
+            price: quote.price.filter(|x| *x > 0.0)?,
+            price_change_percentage_24h: quote.percent_change_24h,
+            market_cap: quote.market_cap,
+            total_volume: quote.volume_24h,
+            last_updated_at: quote.last_updated.naive_utc(),
+        })
+    }
+}
+
+#[async_trait]
+impl PriceProvider for CoinMarketCapProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER_NAME
+    }
+
+    async fn get_prices(
+        &self,
+        assets: Vec<PriceProviderAsset>,
+    ) -> Result<Vec<PriceProviderPrice>, Box<dyn Error + Send + Sync>> {
+        let assets = assets
+            .into_iter()
+            .flat_map(|x| Some((x.symbol?.to_uppercase(), x.price_id)))
+            .collect::<Vec<_>>();
+
+        let mut results = vec![];
+        for chunk in assets.chunks(SYMBOLS_CHUNK_SIZE) {
+            let symbols = chunk.iter().map(|(symbol, _)| symbol.clone()).collect();
+            let quotes = self.get_quotes(symbols).await?;
+            results.extend(chunk.iter().flat_map(|(symbol, price_id)| {
+                Self::map_price(price_id.clone(), quotes.get(symbol)?)
+            }));
+        }
+        Ok(results)
+    }
+}
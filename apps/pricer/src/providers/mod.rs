@@ -0,0 +1,87 @@
+pub mod binance;
+pub mod coinbase;
+pub mod coingecko;
+pub mod coinmarketcap;
+
+use std::error::Error;
+
+use ::coingecko::CoinGeckoClient;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use settings::Settings;
+
+pub use self::binance::BinanceProvider;
+pub use self::coinbase::CoinbaseProvider;
+pub use self::coingecko::CoinGeckoProvider;
+pub use self::coinmarketcap::CoinMarketCapProvider;
+
+#[derive(Debug, Clone)]
+pub struct PriceProviderAsset {
+    pub price_id: String,
+    // set only when the symbol unambiguously identifies the price id
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceProviderPrice {
+    pub price_id: String,
+    // usd
+    pub price: f64,
+    pub price_change_percentage_24h: Option<f64>,
+    pub market_cap: Option<f64>,
+    pub total_volume: Option<f64>,
+    pub last_updated_at: NaiveDateTime,
+}
+
+#[async_trait]
+pub trait PriceProvider {
+    fn name(&self) -> &'static str;
+
+    // assets without a price are omitted from the result
+    async fn get_prices(
+        &self,
+        assets: Vec<PriceProviderAsset>,
+    ) -> Result<Vec<PriceProviderPrice>, Box<dyn Error + Send + Sync>>;
+}
+
+pub struct PriceProviderFactory {}
+
+impl PriceProviderFactory {
+    // providers in order of `pricer.sources.priority`, unknown or unconfigured sources are skipped
+    pub fn new_providers(
+        settings: Settings,
+        coingecko_client: CoinGeckoClient,
+    ) -> Vec<Box<dyn PriceProvider + Send + Sync>> {
+        let client = reqwest::Client::new();
+
+        settings
+            .pricer
+            .sources
+            .priority
+            .iter()
+            .flat_map(|name| -> Option<Box<dyn PriceProvider + Send + Sync>> {
+                match name.as_str() {
+                    coingecko::PROVIDER_NAME => {
+                        Some(Box::new(CoinGeckoProvider::new(coingecko_client.clone())))
+                    }
+                    binance::PROVIDER_NAME => Some(Box::new(BinanceProvider::new(client.clone()))),
+                    coinbase::PROVIDER_NAME => {
+                        Some(Box::new(CoinbaseProvider::new(client.clone())))
+                    }
+                    coinmarketcap::PROVIDER_NAME
+                        if !settings.coinmarketcap.key.secret.is_empty() =>
+                    {
+                        Some(Box::new(CoinMarketCapProvider::new(
+                            client.clone(),
+                            settings.coinmarketcap.key.secret.clone(),
+                        )))
+                    }
+                    _ => {
+                        println!("price provider {} is not available", name);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
@@ -3,3 +3,4 @@ pub mod pusher;
 
 pub use self::assets_client::AssetsClient;
 pub use self::pusher::client::PusherClient;
+pub use self::pusher::device::DevicePusher;
@@ -0,0 +1,46 @@
+use primitives::{Device, Platform};
+
+use super::client::PusherClient;
+use super::model::{Message, Notification, Response};
+
+// Push to a single device, ios devices are addressed through the app topic
+pub struct DevicePusher {
+    client: PusherClient,
+    ios_topic: String,
+}
+
+impl DevicePusher {
+    pub fn new(url: String, ios_topic: String) -> Self {
+        let client = PusherClient::new(url);
+        Self { client, ios_topic }
+    }
+
+    pub fn get_topic(&self, platform: Platform) -> Option<String> {
+        match platform {
+            Platform::Android => None,
+            Platform::IOS => Some(self.ios_topic.clone()),
+        }
+    }
+
+    pub fn is_pushable(device: &Device) -> bool {
+        device.is_push_enabled && !device.token.is_empty()
+    }
+
+    // logs in the response mean the token was rejected, callers disable push for the device
+    pub async fn push(
+        &self,
+        device: &Device,
+        message: Message,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response, reqwest::Error> {
+        let notification = Notification {
+            tokens: vec![device.token.clone()],
+            platform: device.platform.as_i32(),
+            title: message.title,
+            message: message.message.unwrap_or_default(),
+            topic: self.get_topic(device.platform.clone()),
+            data,
+        };
+        self.client.push(notification).await
+    }
+}
@@ -1,4 +1,3 @@
-use primitives::PushNotification;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -8,7 +7,8 @@ pub struct Notification {
     pub title: String,
     pub message: String,
     pub topic: Option<String>,
-    pub data: Option<PushNotification>,
+    // PushNotification or PushNotificationFiatTransaction
+    pub data: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1,16 +1,46 @@
 use std::error::Error;
 
+use futures::{Stream, StreamExt};
+use redis::aio::MultiplexedConnection;
 use redis::AsyncCommands;
 
+// new and updated transactions of a device wallet
+pub fn transactions_channel(device_id: &str, wallet_index: i32) -> String {
+    format!("transactions:{}:{}", device_id, wallet_index)
+}
+
+// set once a transaction push was sent to a device, the parser and the pending tracker both
+// push the final state of a tracked transaction and only the first one is delivered
+pub fn transaction_push_key(device_id: &str, transaction_id: &str) -> String {
+    format!("transactions:push:{}:{}", device_id, transaction_id)
+}
+
+pub const TRANSACTION_PUSH_EXPIRATION: i64 = 86_400;
+
 // Work in progress. In the future use it for caching any temporary data.
+#[derive(Clone)]
 pub struct CacherClient {
     client: redis::Client,
+    // opened on first use and kept for frequent calls like publish
+    connection: Option<MultiplexedConnection>,
 }
 
 impl CacherClient {
     pub fn new(redis_url: &str) -> Self {
         let client = redis::Client::open(redis_url).unwrap();
-        Self { client }
+        Self {
+            client,
+            connection: None,
+        }
+    }
+
+    async fn connection(&mut self) -> Result<MultiplexedConnection, Box<dyn Error>> {
+        if let Some(connection) = &self.connection {
+            return Ok(connection.clone());
+        }
+        let connection = self.client.get_multiplexed_async_connection().await?;
+        self.connection = Some(connection.clone());
+        Ok(connection)
     }
 
     pub async fn set_values(
@@ -37,9 +67,58 @@ impl CacherClient {
         Ok(())
     }
 
+    // false when the key already exists, the value is left untouched
+    pub async fn set_value_if_missing_with_expiration(
+        &mut self,
+        key: &str,
+        value: String,
+        seconds: i64,
+    ) -> Result<bool, Box<dyn Error>> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let result: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("EX")
+            .arg(seconds)
+            .query_async(&mut connection)
+            .await?;
+        Ok(result.is_some())
+    }
+
+    pub async fn delete_value(&mut self, key: &str) -> Result<(), Box<dyn Error>> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let _: () = connection.del(key).await?;
+        Ok(())
+    }
+
     pub async fn get_value(&mut self, key: &str) -> Result<String, Box<dyn Error>> {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let value: String = connection.get(key).await?;
         Ok(value)
     }
+
+    // a failed connection is dropped and opened again on the next call
+    pub async fn publish(&mut self, channel: &str, value: String) -> Result<usize, Box<dyn Error>> {
+        let mut connection = self.connection().await?;
+        match connection.publish(channel, value).await {
+            Ok(receivers) => Ok(receivers),
+            Err(err) => {
+                self.connection = None;
+                Err(err.into())
+            }
+        }
+    }
+
+    // messages published to the channel once subscribed, earlier ones are not delivered
+    pub async fn subscribe(
+        &self,
+        channel: &str,
+    ) -> Result<impl Stream<Item = String>, Box<dyn Error + Send + Sync>> {
+        let mut pubsub = self.client.get_async_pubsub().await?;
+        pubsub.subscribe(channel).await?;
+        Ok(pubsub
+            .into_on_message()
+            .filter_map(|message| async move { message.get_payload::<String>().ok() }))
+    }
 }
@@ -196,6 +196,30 @@ impl CoinGeckoClient {
         let response = self
             .client
             .get(url)
+            .headers(self.headers())
+            .send()
+            .await?
+            .json::<MarketChart>()
+            .await?;
+
+        Ok(response)
+    }
+
+    // hourly points for ranges up to 90 days, daily points for longer ranges
+    pub async fn get_market_chart_range(
+        &self,
+        coin_id: &str,
+        from: u64,
+        to: u64,
+    ) -> Result<MarketChart, Error> {
+        let url = format!(
+            "{}/api/v3/coins/{}/market_chart/range?vs_currency=usd&from={}&to={}&precision=full",
+            self.url, coin_id, from, to
+        );
+        let response = self
+            .client
+            .get(url)
+            .headers(self.headers())
             .send()
             .await?
             .json::<MarketChart>()
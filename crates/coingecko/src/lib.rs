@@ -4,4 +4,4 @@ pub mod model;
 
 pub use self::client::CoinGeckoClient;
 pub use self::mapper::{get_chain_for_coingecko_platform_id, get_coingecko_market_id_for_chain};
-pub use self::model::{Coin, CoinInfo, CoinMarket, SimplePrice, SimplePrices};
+pub use self::model::{Coin, CoinInfo, CoinMarket, MarketChart, SimplePrice, SimplePrices};
@@ -52,6 +52,10 @@ pub struct DetailPlatform {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MarketChart {
     pub prices: Vec<Vec<f64>>,
+    #[serde(default)]
+    pub market_caps: Vec<Vec<f64>>,
+    #[serde(default)]
+    pub total_volumes: Vec<Vec<f64>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
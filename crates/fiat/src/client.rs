@@ -1,33 +1,47 @@
 use std::collections::HashSet;
 use std::error::Error;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use crate::{
     model::{FiatMapping, FiatMappingMap},
-    FiatProvider,
+    webhook::WEBHOOK_REPLAY_EXPIRATION,
+    FiatNotification, FiatProvider, FiatPusher, FiatRouter, FiatWebhookRequest,
 };
+use api_connector::DevicePusher;
+use cacher::CacherClient;
 use futures::future::join_all;
 use primitives::{
-    fiat_assets::FiatAssets, fiat_quote::FiatQuote, fiat_quote_request::FiatBuyRequest,
+    fiat_assets::FiatAssets,
+    fiat_quote::{FiatProviderExclusion, FiatProviderExclusionReason, FiatQuote, FiatQuotes},
+    fiat_quote_request::{FiatBuyRequest, FiatSellRequest},
+    FiatTransaction,
 };
 use reqwest::Client as RequestClient;
 use storage::DatabaseClient;
 
 pub struct Client {
     database: DatabaseClient,
-    providers: Vec<Box<dyn FiatProvider + Send + Sync>>,
+    cacher: CacherClient,
+    providers: Vec<Arc<dyn FiatProvider + Send + Sync>>,
+    router: Arc<RwLock<FiatRouter>>,
 }
 
 impl Client {
     pub async fn new(
         database_url: &str,
+        redis_url: &str,
         providers: Vec<Box<dyn FiatProvider + Send + Sync>>,
+        router: FiatRouter,
     ) -> Self {
         let database = DatabaseClient::new(database_url);
+        let cacher = CacherClient::new(redis_url);
 
         Self {
             database,
-            providers,
+            cacher,
+            providers: providers.into_iter().map(Arc::from).collect(),
+            router: Arc::new(RwLock::new(router)),
         }
     }
 
@@ -56,23 +70,134 @@ impl Client {
         })
     }
 
+    pub fn verify_fiat_webhook(
+        &self,
+        provider_name: &str,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.providers
+            .iter()
+            .find(|x| x.name().id() == provider_name)
+            .ok_or(format!("unknown fiat provider: {}", provider_name))?
+            .verify_webhook(request)
+    }
+
+    // false when the callback was already accepted, verified callbacks stay valid for the whole
+    // timestamp window and each replay would apply the status and push again
+    pub async fn claim_fiat_webhook(
+        &mut self,
+        provider_name: &str,
+        request: &FiatWebhookRequest,
+    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let key = request.replay_key(provider_name);
+        let claimed = self
+            .cacher
+            .set_value_if_missing_with_expiration(&key, "1".to_string(), WEBHOOK_REPLAY_EXPIRATION)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(claimed)
+    }
+
+    // lets the provider retry a callback that could not be processed
+    pub async fn release_fiat_webhook(
+        &mut self,
+        provider_name: &str,
+        request: &FiatWebhookRequest,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let key = request.replay_key(provider_name);
+        self.cacher
+            .delete_value(&key)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    // returns notifications for the status change, None for an unknown provider.
+    // pushing is left to the caller so it does not hold the client meanwhile
     pub async fn create_fiat_webhook(
         &mut self,
         provider_name: &str,
         data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
-        for provider in &self.providers {
-            if provider.name().id() == provider_name {
-                let transaction = provider.webhook(data).await?;
-                let transaction =
-                    storage::models::FiatTransaction::from_primitive(transaction.clone());
+    ) -> Result<Option<Vec<FiatNotification>>, Box<dyn std::error::Error + Send + Sync>> {
+        let provider = match self
+            .providers
+            .iter()
+            .find(|x| x.name().id() == provider_name)
+        {
+            Some(provider) => provider,
+            None => return Ok(None),
+        };
+        let provider_name = provider.name().as_str();
+        let transaction = provider.webhook(data).await?;
+
+        let previous_status = self
+            .database
+            .get_fiat_transaction(
+                &transaction.provider_id,
+                &transaction.provider_transaction_id,
+            )
+            .ok()
+            .map(|x| x.status);
 
-                let _ = self.database.add_fiat_transactions(vec![transaction])?;
+        let _ = self.database.add_fiat_transactions(vec![
+            storage::models::FiatTransaction::from_primitive(transaction.clone()),
+        ])?;
 
-                return Ok(true);
+        if previous_status.as_deref() == Some(transaction.status.as_ref()) {
+            return Ok(Some(vec![]));
+        }
+        let notifications = match self.get_notifications(transaction, provider_name) {
+            Ok(notifications) => notifications,
+            Err(err) => {
+                println!("fiat webhook notifications error: {}", err);
+                vec![]
+            }
+        };
+        Ok(Some(notifications))
+    }
+
+    // devices subscribed to the receiving address are notified about order status changes
+    fn get_notifications(
+        &mut self,
+        transaction: FiatTransaction,
+        provider_name: &str,
+    ) -> Result<Vec<FiatNotification>, Box<dyn Error + Send + Sync>> {
+        let message = match FiatPusher::message(&transaction, provider_name) {
+            Some(message) => message,
+            None => return Ok(vec![]),
+        };
+        let (asset_id, address) = match (transaction.asset_id.clone(), transaction.address.clone())
+        {
+            (Some(asset_id), Some(address)) => (asset_id, address),
+            _ => return Ok(vec![]),
+        };
+        let subscriptions = self
+            .database
+            .get_subscriptions(asset_id.chain, vec![address])?;
+
+        let mut notifications = vec![];
+        for subscription in subscriptions {
+            let device = self
+                .database
+                .get_device_by_id(subscription.device_id)?
+                .as_primitive();
+            if !DevicePusher::is_pushable(&device) {
+                continue;
             }
+            notifications.push(FiatNotification {
+                device,
+                transaction: transaction.clone(),
+                message: message.clone(),
+            });
         }
-        Ok(false)
+        Ok(notifications)
+    }
+
+    // push tokens rejected by the push service
+    pub fn disable_push(&mut self, device_id: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.database
+            .update_device_is_push_enabled(device_id, false)?;
+        Ok(())
     }
 
     fn get_fiat_mapping(
@@ -95,33 +220,158 @@ impl Client {
         Ok(map)
     }
 
+    // refresh countries and limits in the background, quotes are routed with the current data meanwhile.
+    // providers failing to respond keep their previous routes
+    fn update_routes(&self) {
+        if !self.router.write().unwrap().start_update() {
+            return;
+        }
+        let providers = self.providers.clone();
+        let router = self.router.clone();
+        tokio::spawn(async move {
+            for provider in providers {
+                let provider_id = provider.name().id();
+                match provider.get_countries().await {
+                    Ok(countries) => router
+                        .write()
+                        .unwrap()
+                        .set_countries(&provider_id, countries),
+                    Err(err) => println!("fiat provider {} countries error: {}", provider_id, err),
+                }
+                match provider.get_limits().await {
+                    Ok(limits) => router.write().unwrap().set_limits(&provider_id, limits),
+                    Err(err) => println!("fiat provider {} limits error: {}", provider_id, err),
+                }
+            }
+        });
+    }
+
     pub async fn get_quotes(
         &mut self,
         request: FiatBuyRequest,
-    ) -> Result<Vec<FiatQuote>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<FiatQuotes, Box<dyn Error + Send + Sync>> {
         let fiat_mapping_map = self.get_fiat_mapping(&request.asset_id)?;
+        self.update_routes();
+
+        let routes = {
+            let router = self.router.read().unwrap();
+            let country = router.get_country(&request.ip_address);
+            self.providers
+                .iter()
+                .map(|x| router.route(&x.name().id(), &request, country.as_deref()))
+                .collect::<Vec<_>>()
+        };
         let mut futures = vec![];
+        let mut excluded = vec![];
 
-        for provider in &self.providers {
-            if let Some(fiat_mapping) = fiat_mapping_map.get(provider.name().id().as_str()) {
-                futures.push(provider.get_quote(request.clone(), fiat_mapping.clone()));
-            }
+        for (provider, route) in self.providers.iter().zip(routes) {
+            let reason = match fiat_mapping_map.get(provider.name().id().as_str()) {
+                Some(fiat_mapping) => match route {
+                    Ok(_) => {
+                        futures.push(provider.get_quote(request.clone(), fiat_mapping.clone()));
+                        continue;
+                    }
+                    Err(reason) => reason,
+                },
+                None => FiatProviderExclusionReason::UnsupportedAsset,
+            };
+            excluded.push(FiatProviderExclusion {
+                provider: provider.name().as_fiat_provider(),
+                reason,
+            });
         }
 
-        let mut results: Vec<FiatQuote> = join_all(futures)
-            .await
+        let quotes = join_all(futures).await.into_iter().flatten().collect();
+        let results: Vec<FiatQuote> = rank_by_effective_rate(quotes, true)
             .into_iter()
-            .flatten()
             .map(|quote| {
-                let mut result = quote.clone();
+                let mut result = round_fees(quote.clone());
                 result.crypto_amount = precision(quote.crypto_amount, 5);
                 result
             })
             .collect();
 
-        results.sort_by(|a, b| b.crypto_amount.partial_cmp(&a.crypto_amount).unwrap());
+        Ok(FiatQuotes {
+            quotes: results,
+            excluded,
+        })
+    }
+
+    pub async fn get_sell_quotes(
+        &mut self,
+        request: FiatSellRequest,
+    ) -> Result<FiatQuotes, Box<dyn Error + Send + Sync>> {
+        let fiat_mapping_map = self.get_fiat_mapping(&request.asset_id)?;
+        self.update_routes();
+
+        let routes = {
+            let router = self.router.read().unwrap();
+            let country = router.get_country(&request.ip_address);
+            self.providers
+                .iter()
+                .map(|x| router.route_sell(&x.name().id(), country.as_deref()))
+                .collect::<Vec<_>>()
+        };
+        let mut futures = vec![];
+        let mut excluded = vec![];
+
+        for (provider, route) in self.providers.iter().zip(routes) {
+            let reason = match fiat_mapping_map.get(provider.name().id().as_str()) {
+                Some(fiat_mapping) => match route {
+                    Ok(_) => {
+                        futures
+                            .push(provider.get_sell_quote(request.clone(), fiat_mapping.clone()));
+                        continue;
+                    }
+                    Err(reason) => reason,
+                },
+                None => FiatProviderExclusionReason::UnsupportedAsset,
+            };
+            excluded.push(FiatProviderExclusion {
+                provider: provider.name().as_fiat_provider(),
+                reason,
+            });
+        }
+
+        let quotes = join_all(futures).await.into_iter().flatten().collect();
+        let results: Vec<FiatQuote> = rank_by_effective_rate(quotes, false)
+            .into_iter()
+            .map(|quote| {
+                let mut result = round_fees(quote.clone());
+                result.fiat_amount = precision(quote.fiat_amount, 2);
+                result
+            })
+            .collect();
+
+        Ok(FiatQuotes {
+            quotes: results,
+            excluded,
+        })
+    }
+}
 
-        Ok(results)
+// ranked before rounding, so close quotes keep their order.
+// buy quotes rank by the lowest fiat paid per unit, sell quotes by the highest fiat received per unit.
+// quotes without an amount have no rate and are dropped
+fn rank_by_effective_rate(mut quotes: Vec<FiatQuote>, lowest_first: bool) -> Vec<FiatQuote> {
+    quotes.retain(|x| x.crypto_amount > 0.0 && x.fiat_amount > 0.0);
+    quotes.sort_by(|a, b| {
+        let ordering = a.effective_rate().total_cmp(&b.effective_rate());
+        if lowest_first {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+    quotes
+}
+
+fn round_fees(quote: FiatQuote) -> FiatQuote {
+    FiatQuote {
+        fee_provider: precision(quote.fee_provider, 2),
+        fee_network: precision(quote.fee_network, 2),
+        fee_partner: precision(quote.fee_partner, 2),
+        ..quote
     }
 }
 
@@ -141,4 +391,28 @@ mod tests {
         assert_eq!(precision(1.123, 2), 1.12);
         assert_eq!(precision(1.123, 5), 1.123);
     }
+
+    fn names(quotes: Vec<FiatQuote>) -> Vec<String> {
+        quotes.into_iter().map(|x| x.provider.name).collect()
+    }
+
+    #[test]
+    fn test_rank_by_effective_rate() {
+        let buy: Vec<FiatQuote> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/buy_quotes.json"
+        )))
+        .unwrap();
+        assert_eq!(names(rank_by_effective_rate(buy, true)), vec!["c", "a"]);
+
+        let sell: Vec<FiatQuote> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/sell_quotes.json"
+        )))
+        .unwrap();
+        assert_eq!(
+            names(rank_by_effective_rate(sell, false)),
+            vec!["b", "c", "a"]
+        );
+    }
 }
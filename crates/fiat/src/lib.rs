@@ -3,10 +3,16 @@ pub mod model;
 pub mod provider;
 pub use provider::FiatProvider;
 pub mod providers;
+pub mod pusher;
+pub use pusher::{FiatNotification, FiatPusher};
+pub mod router;
+pub use router::FiatRouter;
+pub mod webhook;
 use crate::providers::{
     BanxaClient, KadoClient, MercuryoClient, MoonPayClient, RampClient, TransakClient,
 };
 use settings::Settings;
+pub use webhook::FiatWebhookRequest;
 
 pub struct FiatProviderFactory {}
 impl FiatProviderFactory {
@@ -17,21 +23,35 @@ impl FiatProviderFactory {
             request_client.clone(),
             settings.moonpay.key.public.clone(),
             settings.moonpay.key.secret.clone(),
+            settings.moonpay.webhook.secret.clone(),
+        );
+        let ramp = RampClient::new(
+            request_client.clone(),
+            settings.ramp.key.public.clone(),
+            settings.ramp.webhook.public.clone(),
         );
-        let ramp = RampClient::new(request_client.clone(), settings.ramp.key.public.clone());
         let mercuryo = MercuryoClient::new(
             request_client.clone(),
             settings.mercuryo.key.public.clone(),
             settings.mercuryo.key.secret.clone(),
+            settings.mercuryo.webhook.secret.clone(),
+        );
+        let transak = TransakClient::new(
+            request_client.clone(),
+            settings.transak.key.public,
+            settings.transak.webhook.secret,
         );
-        let transak = TransakClient::new(request_client.clone(), settings.transak.key.public);
         let banxa = BanxaClient::new(
             request_client.clone(),
             settings.banxa.url,
             settings.banxa.key.public,
             settings.banxa.key.secret,
         );
-        let kado = KadoClient::new(request_client.clone(), settings.kado.key.secret);
+        let kado = KadoClient::new(
+            request_client.clone(),
+            settings.kado.key.secret,
+            settings.kado.webhook.unverified,
+        );
 
         vec![
             Box::new(moonpay),
@@ -1,4 +1,4 @@
-use primitives::{Chain, CosmosDenom};
+use primitives::{Chain, CosmosDenom, FiatPaymentMethod};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -33,6 +33,33 @@ pub struct FiatProviderAsset {
 
 pub type FiatMappingMap = HashMap<String, FiatMapping>;
 
+// routing
+#[derive(Debug, Clone)]
+pub struct FiatProviderCountry {
+    // ISO 3166-1 alpha-2
+    pub alpha2: String,
+    pub is_allowed: bool,
+    pub is_sell_allowed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FiatProviderLimit {
+    pub fiat_currency: String,
+    // None when the limit applies to every payment method
+    pub payment_method: Option<FiatPaymentMethod>,
+    pub min_amount: f64,
+    pub max_amount: f64,
+}
+
+// fiat price of one unit of the asset, zero when the amount is unknown
+pub fn exchange_rate(fiat_amount: f64, crypto_amount: f64) -> f64 {
+    if crypto_amount > 0.0 {
+        fiat_amount / crypto_amount
+    } else {
+        0.0
+    }
+}
+
 // used to filter out fiat tokens that have specific token ids for native coins
 pub fn filter_token_id(token_id: Option<String>) -> Option<String> {
     token_id.filter(|contract_address| {
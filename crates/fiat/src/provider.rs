@@ -1,9 +1,12 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction};
+use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest, FiatTransaction};
 
-use crate::model::{FiatMapping, FiatProviderAsset};
+use crate::{
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry, FiatProviderLimit},
+    FiatWebhookRequest,
+};
 
 #[async_trait]
 pub trait FiatProvider {
@@ -13,10 +16,39 @@ pub trait FiatProvider {
         request: FiatBuyRequest,
         request_map: FiatMapping,
     ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>>;
+    async fn get_sell_quote(
+        &self,
+        _request: FiatSellRequest,
+        _request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        Err("sell is not supported".into())
+    }
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>>;
 
+    // empty list means the provider does not restrict by country
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(vec![])
+    }
+
+    // empty list means limits are unknown, the provider is quoted for any currency and amount
+    async fn get_limits(
+        &self,
+    ) -> Result<Vec<FiatProviderLimit>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(vec![])
+    }
+
+    // verifies provider signature and returns trusted webhook payload
+    fn verify_webhook(
+        &self,
+        _request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        Err("webhook verification is not supported".into())
+    }
+
     async fn webhook(
         &self,
         data: serde_json::Value,
@@ -40,12 +72,39 @@ where
         (**self).get_quote(request, request_map).await
     }
 
+    async fn get_sell_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).get_sell_quote(request, request_map).await
+    }
+
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>> {
         (**self).get_assets().await
     }
 
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).get_countries().await
+    }
+
+    async fn get_limits(
+        &self,
+    ) -> Result<Vec<FiatProviderLimit>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).get_limits().await
+    }
+
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).verify_webhook(request)
+    }
+
     async fn webhook(
         &self,
         data: serde_json::Value,
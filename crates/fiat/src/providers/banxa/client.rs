@@ -1,13 +1,21 @@
 use std::time::SystemTime;
 
-use crate::model::{filter_token_id, FiatMapping, FiatProviderAsset};
+use crate::{
+    model::{
+        exchange_rate, filter_token_id, FiatMapping, FiatProviderAsset, FiatProviderCountry,
+        FiatProviderLimit,
+    },
+    webhook::{verify_hmac_sha256, verify_timestamp},
+    FiatWebhookRequest,
+};
 use hex;
-use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote};
+use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest};
 use reqwest::Client;
 use url::Url;
 
 use super::model::{
-    Asset, Coins, Order, OrderData, OrderDetails, OrderRequest, Price, Prices, Response,
+    Asset, Coins, Countries, Country, Order, OrderData, OrderDetails, OrderRequest, PaymentMethod,
+    PaymentMethods, Price, Prices, Response,
 };
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
@@ -58,6 +66,39 @@ impl BanxaClient {
         format!("{}:{}:{}", merchant_key, hex::encode(signature), nonce)
     }
 
+    // https://docs.banxa.com/docs/webhooks
+    // Authorization: Bearer {merchant_key}:{signature}:{nonce}, signed the same way as api requests
+    pub fn verify_webhook_signature(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let authorization = request.header("Authorization")?;
+        let token = authorization
+            .strip_prefix("Bearer ")
+            .unwrap_or(authorization);
+        let parts: Vec<&str> = token.split(':').collect();
+        let (merchant_key, signature, nonce) = match parts.as_slice() {
+            [merchant_key, signature, nonce] => (*merchant_key, *signature, *nonce),
+            _ => return Err("invalid authorization header".into()),
+        };
+        if merchant_key != self.merchant_key {
+            return Err("invalid merchant key".into());
+        }
+        verify_timestamp(nonce.parse()?)?;
+
+        let payload = format!(
+            "POST\n{}\n{}\n{}",
+            request.path,
+            nonce,
+            String::from_utf8(request.body.clone())?
+        );
+        verify_hmac_sha256(
+            self.secret_key.as_bytes(),
+            payload.as_bytes(),
+            &hex::decode(signature)?,
+        )
+    }
+
     pub async fn get_assets(&self) -> Result<Vec<Asset>, Box<dyn std::error::Error + Send + Sync>> {
         let query = "/api/coins/buy";
         let authorization = self.get_authorization("GET", query, None);
@@ -111,12 +152,19 @@ impl BanxaClient {
         Ok(response.data.order)
     }
 
+    // https://docs.banxa.com/reference/get-prices
+    // fiat is the source for buys and the coin for sells, with an amount set the response has order totals
     pub async fn get_prices(
         &self,
         source: &str,
         target: &str,
+        source_amount: f64,
+        blockchain: &str,
     ) -> Result<Prices, Box<dyn std::error::Error + Send + Sync>> {
-        let query = format!("/api/prices?source={}&target={}", source, target);
+        let query = format!(
+            "/api/prices?source={}&target={}&source_amount={}&blockchain={}",
+            source, target, source_amount, blockchain
+        );
         let authorization = self.get_authorization("GET", &query, None);
         let url = format!("{}{}", self.url, query);
         let response = self
@@ -130,6 +178,65 @@ impl BanxaClient {
         Ok(response.data)
     }
 
+    pub async fn get_countries(
+        &self,
+    ) -> Result<Vec<Country>, Box<dyn std::error::Error + Send + Sync>> {
+        let query = "/api/countries";
+        let authorization = self.get_authorization("GET", query, None);
+        let url = format!("{}{}", self.url, query);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(authorization)
+            .send()
+            .await?
+            .json::<Response<Countries>>()
+            .await?;
+        Ok(response.data.countries)
+    }
+
+    pub async fn get_payment_methods(
+        &self,
+    ) -> Result<Vec<PaymentMethod>, Box<dyn std::error::Error + Send + Sync>> {
+        let query = "/api/payment-methods";
+        let authorization = self.get_authorization("GET", query, None);
+        let url = format!("{}{}", self.url, query);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(authorization)
+            .send()
+            .await?
+            .json::<Response<PaymentMethods>>()
+            .await?;
+        Ok(response.data.payment_methods)
+    }
+
+    // only supported countries are listed
+    pub fn map_country(country: Country) -> FiatProviderCountry {
+        FiatProviderCountry {
+            alpha2: country.country_code,
+            is_allowed: true,
+            is_sell_allowed: true,
+        }
+    }
+
+    pub fn map_limits(payment_method: PaymentMethod) -> Vec<FiatProviderLimit> {
+        let method = super::mapper::map_payment_method(&payment_method.payment_type);
+        payment_method
+            .transaction_limits
+            .into_iter()
+            .flat_map(|x| {
+                Some(FiatProviderLimit {
+                    fiat_currency: x.fiat_code.to_uppercase(),
+                    payment_method: method.clone(),
+                    min_amount: x.min.parse().ok()?,
+                    max_amount: x.max.parse().ok()?,
+                })
+            })
+            .collect()
+    }
+
     pub fn map_asset(asset: Asset) -> Vec<FiatProviderAsset> {
         asset
             .clone()
@@ -157,10 +264,9 @@ impl BanxaClient {
         fiat_mapping: FiatMapping,
         price: Price,
     ) -> FiatQuote {
-        let price_fiat_amount = price.fiat_amount.parse::<f64>().unwrap_or_default();
         let fee_amount = price.fee_amount.parse::<f64>().unwrap_or_default();
         let network_fee = price.network_fee.parse::<f64>().unwrap_or_default();
-        let crypto_amount = request.fiat_amount / (price_fiat_amount + fee_amount + network_fee);
+        let crypto_amount = price.coin_amount.parse::<f64>().unwrap_or_default();
         let redirect_url = self.get_redirect_url(request.clone(), fiat_mapping);
 
         FiatQuote {
@@ -169,6 +275,14 @@ impl BanxaClient {
             fiat_currency: request.fiat_currency,
             crypto_amount,
             redirect_url,
+            fee_provider: fee_amount,
+            fee_network: network_fee,
+            fee_partner: 0.0,
+            exchange_rate: exchange_rate(
+                request.fiat_amount - fee_amount - network_fee,
+                crypto_amount,
+            ),
+            payment_method: None,
         }
     }
 
@@ -185,4 +299,116 @@ impl BanxaClient {
 
         return components.as_str().to_string();
     }
+
+    pub fn get_sell_fiat_quote(
+        &self,
+        request: FiatSellRequest,
+        fiat_mapping: FiatMapping,
+        price: Price,
+    ) -> FiatQuote {
+        let price_fiat_amount = price.fiat_amount.parse::<f64>().unwrap_or_default();
+        let fee_amount = price.fee_amount.parse::<f64>().unwrap_or_default();
+        let network_fee = price.network_fee.parse::<f64>().unwrap_or_default();
+        let fiat_amount = (price_fiat_amount - fee_amount - network_fee).max(0.0);
+        let redirect_url = self.get_sell_redirect_url(request.clone(), fiat_mapping);
+
+        FiatQuote {
+            provider: Self::NAME.as_fiat_provider(),
+            fiat_amount,
+            fiat_currency: request.fiat_currency,
+            crypto_amount: request.crypto_amount,
+            redirect_url,
+            fee_provider: fee_amount,
+            fee_network: network_fee,
+            fee_partner: 0.0,
+            exchange_rate: exchange_rate(price_fiat_amount, request.crypto_amount),
+            payment_method: None,
+        }
+    }
+
+    pub fn get_sell_redirect_url(
+        &self,
+        request: FiatSellRequest,
+        fiat_mapping: FiatMapping,
+    ) -> String {
+        let mut components = Url::parse(&self.url).unwrap();
+
+        components
+            .query_pairs_mut()
+            .append_pair("orderType", "sell")
+            .append_pair("coinType", &fiat_mapping.symbol)
+            .append_pair("blockchain", &fiat_mapping.network.unwrap_or_default())
+            .append_pair("fiatType", request.fiat_currency.as_str())
+            .append_pair("coinAmount", &request.crypto_amount.to_string())
+            .append_pair("walletAddress", &request.wallet_address);
+
+        components.as_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> BanxaClient {
+        BanxaClient::new(
+            Client::new(),
+            "https://gemwallet.banxa.com".to_string(),
+            "merchant_key".to_string(),
+            "secret_key".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_get_fiat_quote() {
+        let request: FiatBuyRequest = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/buy_request.json"
+        )))
+        .unwrap();
+        let response: Response<Prices> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/banxa/buy_prices.json"
+        )))
+        .unwrap();
+        let fiat_mapping = FiatMapping {
+            symbol: "ETH".to_string(),
+            network: Some("ETH".to_string()),
+        };
+
+        let quote = client().get_fiat_quote(request, fiat_mapping, response.data.prices[0].clone());
+
+        assert_eq!(quote.fiat_amount, 100.0);
+        assert_eq!(quote.crypto_amount, 0.0485);
+        assert_eq!(quote.fee_provider, 2.0);
+        assert_eq!(quote.fee_network, 1.0);
+        assert_eq!(quote.exchange_rate, 2000.0);
+    }
+
+    #[test]
+    fn test_get_sell_fiat_quote() {
+        let request: FiatSellRequest = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/sell_request.json"
+        )))
+        .unwrap();
+        let response: Response<Prices> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/banxa/sell_prices.json"
+        )))
+        .unwrap();
+        let fiat_mapping = FiatMapping {
+            symbol: "ETH".to_string(),
+            network: Some("ETH".to_string()),
+        };
+
+        let quote =
+            client().get_sell_fiat_quote(request, fiat_mapping, response.data.prices[0].clone());
+
+        assert_eq!(quote.fiat_amount, 978.0);
+        assert_eq!(quote.crypto_amount, 0.5);
+        assert_eq!(quote.fee_provider, 20.0);
+        assert_eq!(quote.fee_network, 2.0);
+        assert_eq!(quote.exchange_rate, 2000.0);
+    }
 }
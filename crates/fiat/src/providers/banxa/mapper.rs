@@ -1,4 +1,16 @@
-use primitives::Chain;
+use primitives::{Chain, FiatPaymentMethod};
+
+pub fn map_payment_method(payment_type: &str) -> Option<FiatPaymentMethod> {
+    match payment_type {
+        "CHECKOUTCREDIT" | "WORLDPAYCREDIT" | "PRIMERCC" => Some(FiatPaymentMethod::Card),
+        "WORLDPAYAPPLE" | "PRIMERAP" => Some(FiatPaymentMethod::ApplePay),
+        "WORLDPAYGOOGLE" | "PRIMERGP" => Some(FiatPaymentMethod::GooglePay),
+        "CLEARJUNCTION" | "CLEARJUNCTIONFP" | "DCINTERAC" | "MONOOVAPAYID" => {
+            Some(FiatPaymentMethod::BankTransfer)
+        }
+        _ => None,
+    }
+}
 
 pub fn map_asset_chain(chain: String) -> Option<Chain> {
     match chain.as_str() {
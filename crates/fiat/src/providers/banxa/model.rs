@@ -21,6 +21,35 @@ pub struct Blockchain {
     pub code: String,
     pub contract_id: Option<String>,
 }
+#[derive(Debug, Deserialize)]
+pub struct Countries {
+    pub countries: Vec<Country>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Country {
+    pub country_code: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaymentMethods {
+    pub payment_methods: Vec<PaymentMethod>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PaymentMethod {
+    #[serde(rename = "paymentType")]
+    pub payment_type: String,
+    pub transaction_limits: Vec<TransactionLimit>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TransactionLimit {
+    pub fiat_code: String,
+    pub min: String,
+    pub max: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct OrderData<T> {
     pub order: T,
@@ -51,6 +80,7 @@ pub struct Prices {
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Price {
+    pub coin_amount: String,
     pub network_fee: String,
     pub fee_amount: String,
     pub fiat_amount: String,
@@ -59,6 +89,7 @@ pub struct Price {
 #[derive(Debug, Deserialize, Clone)]
 pub struct OrderDetails {
     pub id: String,
+    pub order_type: String,
     pub coin_code: String,
     pub fiat_amount: f64,
     pub fiat_code: String,
@@ -1,11 +1,12 @@
 use async_trait::async_trait;
 use primitives::{
-    AssetId, FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction, FiatTransactionStatus,
+    AssetId, FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest, FiatTransaction,
+    FiatTransactionStatus, FiatTransactionType,
 };
 
 use crate::{
-    model::{FiatMapping, FiatProviderAsset},
-    FiatProvider,
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry, FiatProviderLimit},
+    FiatProvider, FiatWebhookRequest,
 };
 
 use super::{
@@ -25,13 +26,36 @@ impl FiatProvider for BanxaClient {
         request_map: FiatMapping,
     ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
         let prices = self
-            .get_prices(&request.fiat_currency, &request_map.symbol)
+            .get_prices(
+                &request.fiat_currency,
+                &request_map.symbol,
+                request.fiat_amount,
+                &request_map.network.clone().unwrap_or_default(),
+            )
             .await?;
         let price = prices.prices.first().cloned().ok_or("No price available")?;
 
         Ok(self.get_fiat_quote(request, request_map, price))
     }
 
+    async fn get_sell_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let prices = self
+            .get_prices(
+                &request_map.symbol,
+                &request.fiat_currency,
+                request.crypto_amount,
+                &request_map.network.clone().unwrap_or_default(),
+            )
+            .await?;
+        let price = prices.prices.first().cloned().ok_or("No price available")?;
+
+        Ok(self.get_sell_fiat_quote(request, request_map, price))
+    }
+
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>> {
@@ -44,6 +68,38 @@ impl FiatProvider for BanxaClient {
         Ok(assets)
     }
 
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        let countries = self
+            .get_countries()
+            .await?
+            .into_iter()
+            .map(Self::map_country)
+            .collect();
+        Ok(countries)
+    }
+
+    async fn get_limits(
+        &self,
+    ) -> Result<Vec<FiatProviderLimit>, Box<dyn std::error::Error + Send + Sync>> {
+        let limits = self
+            .get_payment_methods()
+            .await?
+            .into_iter()
+            .flat_map(Self::map_limits)
+            .collect();
+        Ok(limits)
+    }
+
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.verify_webhook_signature(request)?;
+        request.json()
+    }
+
     // https://docs.banxa.com/docs/webhooks
     async fn webhook(
         &self,
@@ -62,6 +118,11 @@ impl FiatProvider for BanxaClient {
             "complete" | "completed" | "succeeded" => FiatTransactionStatus::Complete,
             _ => FiatTransactionStatus::Unknown,
         };
+        let transaction_type = if order.order_type.to_uppercase().contains("SELL") {
+            FiatTransactionType::Sell
+        } else {
+            FiatTransactionType::Buy
+        };
         let asset = Asset {
             coin_code: order.clone().coin_code,
             blockchains: vec![order.clone().blockchain.clone()],
@@ -76,6 +137,7 @@ impl FiatProvider for BanxaClient {
             provider_id: Self::NAME.id(),
             provider_transaction_id: order.id,
             status,
+            transaction_type,
             fiat_amount: order.fiat_amount,
             fiat_currency: order.fiat_code,
             transaction_hash: order.tx_hash,
@@ -5,7 +5,7 @@ use url::Url;
 
 use super::{
     mapper,
-    model::{Asset, Blockchain, Blockchains, Quote, QuoteData, QuoteQuery, Response},
+    model::{Amount, Asset, Blockchain, Blockchains, Quote, QuoteData, QuoteQuery, Response},
 };
 
 const API_BASE_URL: &str = "https://api.kado.money";
@@ -14,13 +14,18 @@ const REDIRECT_URL: &str = "https://app.kado.money";
 pub struct KadoClient {
     pub client: Client,
     pub api_key: String,
+    pub webhook_unverified: bool,
 }
 
 impl KadoClient {
     pub const NAME: FiatProviderName = FiatProviderName::Kado;
 
-    pub fn new(client: Client, api_key: String) -> Self {
-        KadoClient { client, api_key }
+    pub fn new(client: Client, api_key: String, webhook_unverified: bool) -> Self {
+        KadoClient {
+            client,
+            api_key,
+            webhook_unverified,
+        }
     }
 
     pub async fn get_quote_buy(
@@ -123,9 +128,18 @@ impl KadoClient {
                 request_map.clone(),
                 request.wallet_address.as_str(),
             ),
+            fee_provider: Self::amount(quote.processing_fee),
+            fee_network: Self::amount(quote.network_fee),
+            fee_partner: 0.0,
+            exchange_rate: Self::amount(quote.price),
+            payment_method: None,
         }
     }
 
+    fn amount(amount: Option<Amount>) -> f64 {
+        amount.and_then(|x| x.amount).unwrap_or_default()
+    }
+
     pub fn redirect_url(
         &self,
         fiat_currency: &str,
@@ -161,6 +175,7 @@ mod tests {
         let client = KadoClient {
             client: Client::new(),
             api_key: "API_KEY".to_string(),
+            webhook_unverified: false,
         };
         let fiat_mapping = FiatMapping {
             symbol: "ETH".to_string(),
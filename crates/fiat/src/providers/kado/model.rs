@@ -64,6 +64,9 @@ pub struct QuoteData {
 #[serde(rename_all = "camelCase")]
 pub struct Quote {
     pub receive_unit_count_after_fees: Amount,
+    pub price: Option<Amount>,
+    pub processing_fee: Option<Amount>,
+    pub network_fee: Option<Amount>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -1,15 +1,17 @@
 use async_trait::async_trait;
 use primitives::{
     FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction, FiatTransactionStatus,
+    FiatTransactionType,
 };
 
 use crate::{
     model::{FiatMapping, FiatProviderAsset},
-    FiatProvider,
+    FiatProvider, FiatWebhookRequest,
 };
 
 use super::{client::KadoClient, model::Webhook};
 
+// kado publishes no country or limits lists, routing is left to its quote endpoint
 #[async_trait]
 impl FiatProvider for KadoClient {
     fn name(&self) -> FiatProviderName {
@@ -45,6 +47,16 @@ impl FiatProvider for KadoClient {
         Ok(assets)
     }
 
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.webhook_unverified {
+            return Err("kado webhooks are unsigned, set kado.webhook.unverified to accept".into());
+        }
+        request.json()
+    }
+
     async fn webhook(
         &self,
         data: serde_json::Value,
@@ -62,6 +74,7 @@ impl FiatProvider for KadoClient {
             provider_id: Self::NAME.id(),
             provider_transaction_id: data.data.id,
             status,
+            transaction_type: FiatTransactionType::Buy,
             fiat_amount: data.data.buy_amount.amount.unwrap_or_default(),
             fiat_currency: data.data.currency_type,
             transaction_hash: Some(data.data.tx_hash),
@@ -1,11 +1,15 @@
-use crate::model::{FiatMapping, FiatProviderAsset};
+use crate::{
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry},
+    webhook::{verify_hmac_sha256, verify_timestamp},
+    FiatWebhookRequest,
+};
 use hex;
-use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote};
+use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest};
 use reqwest::Client;
 use sha2::{Digest, Sha512};
 use url::Url;
 
-use super::model::{Asset, Currencies, Quote, QuoteQuery, Response};
+use super::model::{Asset, Country, Currencies, Quote, QuoteQuery, Response};
 
 const MERCURYO_API_BASE_URL: &str = "https://api.mercuryo.io";
 const MERCURYO_REDIRECT_URL: &str = "https://exchange.mercuryo.io";
@@ -14,16 +18,24 @@ pub struct MercuryoClient {
     // widget
     pub widget_id: String,
     pub secret_key: String,
+    // callbacks
+    pub webhook_secret: String,
 }
 
 impl MercuryoClient {
     pub const NAME: FiatProviderName = FiatProviderName::Mercuryo;
 
-    pub fn new(client: Client, widget_id: String, secret_key: String) -> Self {
+    pub fn new(
+        client: Client,
+        widget_id: String,
+        secret_key: String,
+        webhook_secret: String,
+    ) -> Self {
         MercuryoClient {
             client,
             widget_id,
             secret_key,
+            webhook_secret,
         }
     }
 
@@ -53,6 +65,32 @@ impl MercuryoClient {
         Ok(quote.data)
     }
 
+    pub async fn get_quote_sell(
+        &self,
+        fiat_currency: String,
+        symbol: String,
+        crypto_amount: f64,
+        network: String,
+    ) -> Result<Quote, Box<dyn std::error::Error + Send + Sync>> {
+        let query = QuoteQuery {
+            from: symbol.clone(),
+            to: fiat_currency.clone(),
+            amount: crypto_amount,
+            network: network.clone(),
+            widget_id: self.widget_id.clone(),
+        };
+        let url = format!("{}/v1.6/widget/sell/rate", MERCURYO_API_BASE_URL);
+        let quote = self
+            .client
+            .get(url.as_str())
+            .query(&query)
+            .send()
+            .await?
+            .json::<Response<Quote>>()
+            .await?;
+        Ok(quote.data)
+    }
+
     pub async fn get_assets(&self) -> Result<Vec<Asset>, Box<dyn std::error::Error + Send + Sync>> {
         let url = format!("{}/v1.6/lib/currencies", MERCURYO_API_BASE_URL);
         let response = self
@@ -65,6 +103,30 @@ impl MercuryoClient {
         Ok(response.data.config.crypto_currencies)
     }
 
+    // countries where card payments are available
+    pub async fn get_countries(
+        &self,
+    ) -> Result<Vec<Country>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/v1.6/public/card-countries", MERCURYO_API_BASE_URL);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json::<Response<Vec<Country>>>()
+            .await?;
+        Ok(response.data)
+    }
+
+    // only supported countries are listed
+    pub fn map_country(country: Country) -> FiatProviderCountry {
+        FiatProviderCountry {
+            alpha2: country.code.to_uppercase(),
+            is_allowed: true,
+            is_sell_allowed: true,
+        }
+    }
+
     pub fn map_asset(asset: Asset) -> Option<FiatProviderAsset> {
         let chain = super::mapper::map_asset_chain(asset.network.clone());
         let token_id = if asset.contract.is_empty() {
@@ -91,16 +153,73 @@ impl MercuryoClient {
         FiatQuote {
             provider: Self::NAME.as_fiat_provider(),
             fiat_amount: request.fiat_amount,
-            fiat_currency: request.fiat_currency,
+            fiat_currency: request.fiat_currency.clone(),
             crypto_amount: quote.clone().amount.parse::<f64>().unwrap_or_default(),
             redirect_url: self.redirect_url(
                 quote.clone(),
                 request_map.network.unwrap_or_default(),
                 request.wallet_address,
             ),
+            fee_provider: Quote::fee_amount(&quote.fee, &request.fiat_currency),
+            fee_network: 0.0,
+            fee_partner: Quote::fee_amount(&quote.partner_fee, &request.fiat_currency),
+            exchange_rate: quote.rate.unwrap_or_default().parse().unwrap_or_default(),
+            payment_method: None,
         }
     }
 
+    pub fn get_sell_fiat_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+        quote: Quote,
+    ) -> FiatQuote {
+        FiatQuote {
+            provider: Self::NAME.as_fiat_provider(),
+            fiat_amount: quote.clone().fiat_amount.parse::<f64>().unwrap_or_default(),
+            fiat_currency: request.fiat_currency.clone(),
+            crypto_amount: request.crypto_amount,
+            redirect_url: self.sell_redirect_url(
+                quote.clone(),
+                request.fiat_currency.clone(),
+                request_map.network.unwrap_or_default(),
+                request.wallet_address,
+            ),
+            fee_provider: Quote::fee_amount(&quote.fee, &request.fiat_currency),
+            fee_network: 0.0,
+            fee_partner: Quote::fee_amount(&quote.partner_fee, &request.fiat_currency),
+            exchange_rate: quote.rate.unwrap_or_default().parse().unwrap_or_default(),
+            payment_method: None,
+        }
+    }
+
+    pub fn sell_redirect_url(
+        &self,
+        quote: Quote,
+        fiat_currency: String,
+        network: String,
+        address: String,
+    ) -> String {
+        let mut components = Url::parse(MERCURYO_REDIRECT_URL).unwrap();
+        let signature_content = format!("{}{}", address, self.secret_key);
+        let signature = hex::encode(Sha512::digest(signature_content));
+        let id = uuid::Uuid::new_v4().to_string();
+
+        components
+            .query_pairs_mut()
+            .append_pair("widget_id", self.widget_id.as_str())
+            .append_pair("type", "sell")
+            .append_pair("merchant_transaction_id", id.as_str())
+            .append_pair("amount", &quote.amount)
+            .append_pair("currency", &quote.currency)
+            .append_pair("fiat_currency", &fiat_currency)
+            .append_pair("address", &address)
+            .append_pair("network", &network)
+            .append_pair("signature", &signature);
+
+        components.as_str().to_string()
+    }
+
     pub fn redirect_url(&self, quote: Quote, network: String, address: String) -> String {
         let mut components = Url::parse(MERCURYO_REDIRECT_URL).unwrap();
         let signature_content = format!("{}{}", address, self.secret_key);
@@ -119,4 +238,59 @@ impl MercuryoClient {
 
         return components.as_str().to_string();
     }
+
+    // https://github.com/mercuryoio/api-migration-docs/blob/master/Widget_API_Mercuryo_v1.6.md#21-callbacks-signature
+    pub fn verify_webhook_signature(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let signature = request.header("X-Signature")?;
+        verify_hmac_sha256(
+            self.webhook_secret.as_bytes(),
+            &request.body,
+            &hex::decode(signature)?,
+        )?;
+        // no timestamp header, updated_at_ts of the signed body bounds replays
+        let timestamp = request.json()?["data"]["updated_at_ts"]
+            .as_u64()
+            .ok_or("missing webhook timestamp")?;
+        verify_timestamp(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_sell_fiat_quote() {
+        let client = MercuryoClient::new(
+            Client::new(),
+            "widget_id".to_string(),
+            "secret_key".to_string(),
+            "webhook_secret".to_string(),
+        );
+        let request: FiatSellRequest = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/sell_request.json"
+        )))
+        .unwrap();
+        let response: Response<Quote> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/mercuryo/sell_quote.json"
+        )))
+        .unwrap();
+        let request_map = FiatMapping {
+            symbol: "ETH".to_string(),
+            network: Some("ETHEREUM".to_string()),
+        };
+
+        let quote = client.get_sell_fiat_quote(request, request_map, response.data);
+
+        assert_eq!(quote.fiat_amount, 980.0);
+        assert_eq!(quote.crypto_amount, 0.5);
+        assert_eq!(quote.fee_provider, 15.0);
+        assert_eq!(quote.fee_partner, 5.0);
+        assert_eq!(quote.exchange_rate, 2000.0);
+    }
 }
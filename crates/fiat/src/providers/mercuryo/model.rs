@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
@@ -10,6 +12,19 @@ pub struct Quote {
     pub amount: String,
     pub currency: String,
     pub fiat_amount: String,
+    pub rate: Option<String>,
+    // fee amounts keyed by currency code
+    pub fee: Option<HashMap<String, String>>,
+    pub partner_fee: Option<HashMap<String, String>>,
+}
+
+impl Quote {
+    pub fn fee_amount(fee: &Option<HashMap<String, String>>, currency: &str) -> f64 {
+        fee.as_ref()
+            .and_then(|x| x.get(&currency.to_uppercase()))
+            .and_then(|x| x.parse::<f64>().ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -38,6 +53,11 @@ pub struct Asset {
     pub contract: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct Country {
+    pub code: String,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Webhook {
     pub data: WebhookData,
@@ -46,6 +66,8 @@ pub struct Webhook {
 #[derive(Debug, Deserialize, Clone)]
 pub struct WebhookData {
     pub id: String,
+    #[serde(rename = "type")]
+    pub transaction_type: String,
     pub status: String,
     pub fiat_amount: String,
     pub fee: Option<String>,
@@ -1,11 +1,12 @@
 use async_trait::async_trait;
 use primitives::{
-    FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction, FiatTransactionStatus,
+    FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest, FiatTransaction,
+    FiatTransactionStatus, FiatTransactionType,
 };
 
 use crate::{
-    model::{FiatMapping, FiatProviderAsset},
-    FiatProvider,
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry},
+    FiatProvider, FiatWebhookRequest,
 };
 
 use super::{client::MercuryoClient, model::Webhook};
@@ -33,6 +34,23 @@ impl FiatProvider for MercuryoClient {
         Ok(self.get_fiat_quote(request, request_map.clone(), quote))
     }
 
+    async fn get_sell_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let quote = self
+            .get_quote_sell(
+                request.fiat_currency.clone(),
+                request_map.symbol.clone(),
+                request.crypto_amount,
+                request_map.network.clone().unwrap_or_default(),
+            )
+            .await?;
+
+        Ok(self.get_sell_fiat_quote(request, request_map.clone(), quote))
+    }
+
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>> {
@@ -45,6 +63,26 @@ impl FiatProvider for MercuryoClient {
         Ok(assets)
     }
 
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        let countries = self
+            .get_countries()
+            .await?
+            .into_iter()
+            .map(Self::map_country)
+            .collect();
+        Ok(countries)
+    }
+
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.verify_webhook_signature(request)?;
+        request.json()
+    }
+
     // full transaction: https://github.com/mercuryoio/api-migration-docs/blob/master/Widget_API_Mercuryo_v1.6.md#22-callbacks-response-body
     async fn webhook(
         &self,
@@ -59,6 +97,10 @@ impl FiatProvider for MercuryoClient {
             "paid" | "completed" | "succeeded" => FiatTransactionStatus::Complete,
             _ => FiatTransactionStatus::Unknown,
         };
+        let transaction_type = match data.transaction_type.as_str() {
+            "sell" => FiatTransactionType::Sell,
+            _ => FiatTransactionType::Buy,
+        };
 
         let transaction = FiatTransaction {
             asset_id: None,
@@ -66,6 +108,7 @@ impl FiatProvider for MercuryoClient {
             provider_id: Self::NAME.id(),
             provider_transaction_id: data.merchant_transaction_id.unwrap_or(data.id),
             status,
+            transaction_type,
             fiat_amount: data.fiat_amount.parse::<f64>().unwrap_or_default(),
             fiat_currency: data.fiat_currency,
             transaction_hash: data.tx.clone().and_then(|x| x.id),
@@ -1,10 +1,16 @@
-use crate::model::{filter_token_id, FiatProviderAsset};
+use crate::{
+    model::{filter_token_id, FiatProviderAsset, FiatProviderCountry, FiatProviderLimit},
+    webhook::{verify_hmac_sha256, verify_timestamp},
+    FiatWebhookRequest,
+};
 
-use super::model::{Asset, MoonPayBuyQuote, MoonPayIpAddress};
+use super::model::{Asset, Country, Currency, MoonPayBuyQuote, MoonPayIpAddress, MoonPaySellQuote};
 use base64::{engine::general_purpose, Engine as _};
 use hmac::{Hmac, Mac};
 use primitives::{
-    fiat_quote::FiatQuote, fiat_quote_request::FiatBuyRequest, Chain, FiatProviderName,
+    fiat_quote::FiatQuote,
+    fiat_quote_request::{FiatBuyRequest, FiatSellRequest},
+    Chain, FiatProviderName, FiatTransactionStatus,
 };
 use reqwest::Client;
 use sha2::Sha256;
@@ -14,19 +20,27 @@ pub struct MoonPayClient {
     client: Client,
     api_key: String,
     secret_key: String,
+    webhook_secret: String,
 }
 
 const MOONPAY_API_BASE_URL: &str = "https://api.moonpay.com";
 const MOONPAY_REDIRECT_URL: &str = "https://buy.moonpay.com";
+const MOONPAY_SELL_REDIRECT_URL: &str = "https://sell.moonpay.com";
 
 impl MoonPayClient {
     pub const NAME: FiatProviderName = FiatProviderName::MoonPay;
 
-    pub fn new(client: Client, api_key: String, secret_key: String) -> Self {
+    pub fn new(
+        client: Client,
+        api_key: String,
+        secret_key: String,
+        webhook_secret: String,
+    ) -> Self {
         Self {
             client,
             api_key,
             secret_key,
+            webhook_secret,
         }
     }
 
@@ -71,6 +85,32 @@ impl MoonPayClient {
         Ok(quote)
     }
 
+    pub async fn get_sell_quote(
+        &self,
+        symbol: String,
+        fiat_currency: String,
+        crypto_amount: f64,
+    ) -> Result<MoonPaySellQuote, reqwest::Error> {
+        let url = format!(
+            "{}/v3/currencies/{}/sell_quote/?quoteCurrencyCode={}&baseCurrencyAmount={}&areFeesIncluded={}&apiKey={}",
+            MOONPAY_API_BASE_URL,
+            symbol,
+            fiat_currency,
+            crypto_amount,
+            "true",
+            self.api_key,
+        );
+
+        let quote = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json::<MoonPaySellQuote>()
+            .await?;
+        Ok(quote)
+    }
+
     pub async fn get_assets(&self) -> Result<Vec<Asset>, Box<dyn std::error::Error + Send + Sync>> {
         let url = format!("{}/v3/currencies", MOONPAY_API_BASE_URL);
         let assets = self
@@ -83,6 +123,34 @@ impl MoonPayClient {
         Ok(assets)
     }
 
+    pub async fn get_countries(
+        &self,
+    ) -> Result<Vec<Country>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/v3/countries", MOONPAY_API_BASE_URL);
+        let countries = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json::<Vec<Country>>()
+            .await?;
+        Ok(countries)
+    }
+
+    pub async fn get_currencies(
+        &self,
+    ) -> Result<Vec<Currency>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/v3/currencies", MOONPAY_API_BASE_URL);
+        let currencies = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json::<Vec<Currency>>()
+            .await?;
+        Ok(currencies)
+    }
+
     pub async fn get_transactions(
         &self,
     ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
@@ -112,6 +180,43 @@ impl MoonPayClient {
         })
     }
 
+    // https://dev.moonpay.com/reference/reference-webhooks-buy
+    pub fn map_status(status: &str) -> FiatTransactionStatus {
+        match status {
+            "waitingPayment" | "waitingAuthorization" | "waitingForDeposit" | "pending" => {
+                FiatTransactionStatus::Pending
+            }
+            "failed" | "refunded" => FiatTransactionStatus::Failed,
+            "completed" => FiatTransactionStatus::Complete,
+            _ => FiatTransactionStatus::Unknown,
+        }
+    }
+
+    // is_allowed does not cover selling, it can be disabled for an ip that can buy
+    pub fn is_sell_allowed(ip_address: &MoonPayIpAddress) -> bool {
+        ip_address.is_allowed && ip_address.is_sell_allowed
+    }
+
+    pub fn map_country(country: Country) -> FiatProviderCountry {
+        FiatProviderCountry {
+            alpha2: country.alpha2,
+            is_allowed: country.is_buy_allowed,
+            is_sell_allowed: country.is_sell_allowed,
+        }
+    }
+
+    pub fn map_limit(currency: Currency) -> Option<FiatProviderLimit> {
+        if currency.currency_type != "fiat" {
+            return None;
+        }
+        Some(FiatProviderLimit {
+            fiat_currency: currency.code.to_uppercase(),
+            payment_method: None,
+            min_amount: currency.min_buy_amount?,
+            max_amount: currency.max_buy_amount?,
+        })
+    }
+
     pub fn map_asset_chain(asset: Asset) -> Option<Chain> {
         match asset.metadata?.network_code.as_str() {
             "ethereum" => Some(Chain::Ethereum),
@@ -145,7 +250,15 @@ impl MoonPayClient {
             fiat_amount: request.clone().fiat_amount,
             fiat_currency: request.clone().fiat_currency,
             crypto_amount: quote.quote_currency_amount,
-            redirect_url: self.redirect_url(request.clone(), quote),
+            redirect_url: self.redirect_url(request.clone(), quote.clone()),
+            fee_provider: quote.fee_amount.unwrap_or_default(),
+            fee_network: quote.network_fee_amount.unwrap_or_default(),
+            fee_partner: quote.extra_fee_amount.unwrap_or_default(),
+            exchange_rate: quote.quote_currency_price.unwrap_or_default(),
+            payment_method: quote
+                .payment_method
+                .as_deref()
+                .and_then(super::mapper::map_payment_method),
         }
     }
 
@@ -170,6 +283,47 @@ impl MoonPayClient {
         components.as_str().to_string()
     }
 
+    pub fn get_sell_fiat_quote(
+        &self,
+        request: FiatSellRequest,
+        quote: MoonPaySellQuote,
+    ) -> FiatQuote {
+        FiatQuote {
+            provider: Self::NAME.as_fiat_provider(),
+            fiat_amount: quote.quote_currency_amount,
+            fiat_currency: request.clone().fiat_currency,
+            crypto_amount: request.crypto_amount,
+            redirect_url: self.sell_redirect_url(request.clone(), quote.clone()),
+            fee_provider: quote.fee_amount.unwrap_or_default(),
+            fee_network: 0.0,
+            fee_partner: quote.extra_fee_amount.unwrap_or_default(),
+            exchange_rate: quote.base_currency_price.unwrap_or_default(),
+            payment_method: quote
+                .payment_method
+                .as_deref()
+                .and_then(super::mapper::map_payment_method),
+        }
+    }
+
+    pub fn sell_redirect_url(&self, request: FiatSellRequest, quote: MoonPaySellQuote) -> String {
+        let mut components = Url::parse(MOONPAY_SELL_REDIRECT_URL).unwrap();
+
+        components
+            .query_pairs_mut()
+            .append_pair("apiKey", &self.api_key)
+            .append_pair("baseCurrencyCode", &quote.base_currency_code)
+            .append_pair("baseCurrencyAmount", &request.crypto_amount.to_string())
+            .append_pair("quoteCurrencyCode", &request.fiat_currency.to_lowercase())
+            .append_pair("refundWalletAddress", request.wallet_address.as_str());
+
+        let query = components.query().unwrap();
+        let signature = self.generate_signature(format!("?{}", &query).as_str());
+        components
+            .query_pairs_mut()
+            .append_pair("signature", &signature);
+        components.as_str().to_string()
+    }
+
     fn generate_signature(&self, query: &str) -> String {
         type HmacSha256 = Hmac<Sha256>;
         let mut mac = HmacSha256::new_from_slice(self.secret_key.as_bytes())
@@ -179,4 +333,156 @@ impl MoonPayClient {
         let signature = result.into_bytes();
         general_purpose::STANDARD.encode(signature)
     }
+
+    // https://dev.moonpay.com/docs/ramps-sdk-webhooks#webhook-signature
+    pub fn verify_webhook_signature(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let header = request.header("Moonpay-Signature-V2")?;
+        let (timestamp, signature) = Self::parse_signature_header(header)?;
+        verify_timestamp(timestamp.parse()?)?;
+
+        let payload = [
+            timestamp.as_bytes(),
+            b".".as_slice(),
+            request.body.as_slice(),
+        ]
+        .concat();
+        verify_hmac_sha256(
+            self.webhook_secret.as_bytes(),
+            &payload,
+            &hex::decode(signature)?,
+        )
+    }
+
+    // t=1492774577,s=5257a869e7ecebeda32affa62cdca3fa51cad7e77a0e56ff536d0ce8e108d8bd
+    fn parse_signature_header(
+        header: &str,
+    ) -> Result<(&str, &str), Box<dyn std::error::Error + Send + Sync>> {
+        let mut timestamp = None;
+        let mut signature = None;
+        for part in header.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => timestamp = Some(value),
+                Some(("s", value)) => signature = Some(value),
+                _ => {}
+            }
+        }
+        Ok((
+            timestamp.ok_or("missing signature timestamp")?,
+            signature.ok_or("missing signature")?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::*;
+
+    fn webhook_request(timestamp: u64, body: &str, secret: &str) -> FiatWebhookRequest {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.{}", timestamp, body).as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+        let headers = HashMap::from([(
+            "Moonpay-Signature-V2".to_string(),
+            format!("t={},s={}", timestamp, signature),
+        )]);
+        FiatWebhookRequest::new("/".to_string(), headers, body.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_map_status() {
+        assert_eq!(
+            MoonPayClient::map_status("pending"),
+            FiatTransactionStatus::Pending
+        );
+        assert_eq!(
+            MoonPayClient::map_status("completed"),
+            FiatTransactionStatus::Complete
+        );
+        assert_eq!(
+            MoonPayClient::map_status("failed"),
+            FiatTransactionStatus::Failed
+        );
+    }
+
+    #[test]
+    fn test_is_sell_allowed() {
+        let ip_address = MoonPayIpAddress {
+            is_buy_allowed: true,
+            is_sell_allowed: false,
+            is_allowed: true,
+        };
+        assert!(!MoonPayClient::is_sell_allowed(&ip_address));
+
+        let ip_address = MoonPayIpAddress {
+            is_buy_allowed: true,
+            is_sell_allowed: true,
+            is_allowed: true,
+        };
+        assert!(MoonPayClient::is_sell_allowed(&ip_address));
+    }
+
+    #[test]
+    fn test_verify_webhook_signature() {
+        let client = MoonPayClient::new(
+            Client::new(),
+            "api_key".to_string(),
+            "secret_key".to_string(),
+            "webhook_secret".to_string(),
+        );
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let body = r#"{"type":"transaction_updated"}"#;
+
+        assert!(client
+            .verify_webhook_signature(&webhook_request(now, body, "webhook_secret"))
+            .is_ok());
+        assert!(client
+            .verify_webhook_signature(&webhook_request(now, body, "other_secret"))
+            .is_err());
+        assert!(client
+            .verify_webhook_signature(&webhook_request(now - 3600, body, "webhook_secret"))
+            .is_err());
+
+        let mut request = webhook_request(now, body, "webhook_secret");
+        request.body = br#"{"type":"transaction_failed"}"#.to_vec();
+        assert!(client.verify_webhook_signature(&request).is_err());
+    }
+
+    #[test]
+    fn test_get_sell_fiat_quote() {
+        let client = MoonPayClient::new(
+            Client::new(),
+            "api_key".to_string(),
+            "secret_key".to_string(),
+            "webhook_secret".to_string(),
+        );
+        let request: FiatSellRequest = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/sell_request.json"
+        )))
+        .unwrap();
+        let response: MoonPaySellQuote = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/moonpay/sell_quote.json"
+        )))
+        .unwrap();
+
+        let quote = client.get_sell_fiat_quote(request, response);
+
+        assert_eq!(quote.fiat_amount, 978.5);
+        assert_eq!(quote.crypto_amount, 0.5);
+        assert_eq!(quote.fee_provider, 16.5);
+        assert_eq!(quote.fee_partner, 5.0);
+        assert_eq!(quote.exchange_rate, 2000.0);
+    }
 }
@@ -1,4 +1,4 @@
-use primitives::Chain;
+use primitives::{Chain, FiatPaymentMethod};
 
 use super::model::Asset;
 
@@ -28,3 +28,15 @@ pub fn map_asset_chain(asset: Asset) -> Option<Chain> {
         _ => None,
     }
 }
+
+pub fn map_payment_method(payment_method: &str) -> Option<FiatPaymentMethod> {
+    match payment_method {
+        "credit_debit_card" => Some(FiatPaymentMethod::Card),
+        "apple_pay" => Some(FiatPaymentMethod::ApplePay),
+        "google_pay" => Some(FiatPaymentMethod::GooglePay),
+        "sepa_bank_transfer" | "gbp_bank_transfer" | "ach_bank_transfer" => {
+            Some(FiatPaymentMethod::BankTransfer)
+        }
+        _ => None,
+    }
+}
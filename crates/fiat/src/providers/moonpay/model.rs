@@ -5,12 +5,30 @@ use serde::Deserialize;
 pub struct MoonPayBuyQuote {
     pub quote_currency_amount: f64,
     pub quote_currency_code: String,
+    pub quote_currency_price: Option<f64>,
+    pub fee_amount: Option<f64>,
+    pub extra_fee_amount: Option<f64>,
+    pub network_fee_amount: Option<f64>,
+    pub payment_method: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoonPaySellQuote {
+    pub base_currency_amount: f64,
+    pub quote_currency_amount: f64,
+    pub base_currency_code: String,
+    pub base_currency_price: Option<f64>,
+    pub fee_amount: Option<f64>,
+    pub extra_fee_amount: Option<f64>,
+    pub payment_method: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MoonPayIpAddress {
     pub is_buy_allowed: bool,
+    pub is_sell_allowed: bool,
     pub is_allowed: bool,
 }
 
@@ -22,6 +40,24 @@ pub struct Asset {
     pub is_suspended: Option<bool>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Country {
+    pub alpha2: String,
+    pub is_buy_allowed: bool,
+    pub is_sell_allowed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Currency {
+    pub code: String,
+    #[serde(rename = "type")]
+    pub currency_type: String,
+    pub min_buy_amount: Option<f64>,
+    pub max_buy_amount: Option<f64>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CurrencyMetadata {
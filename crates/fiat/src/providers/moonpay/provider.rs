@@ -1,12 +1,13 @@
 use crate::{
-    model::{FiatMapping, FiatProviderAsset},
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry, FiatProviderLimit},
     providers::moonpay::model::{Data, Webhook},
-    FiatProvider,
+    FiatProvider, FiatWebhookRequest,
 };
 use async_trait::async_trait;
 
 use primitives::{
-    AssetId, FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction, FiatTransactionStatus,
+    AssetId, FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest, FiatTransaction,
+    FiatTransactionType,
 };
 
 use super::client::MoonPayClient;
@@ -38,6 +39,27 @@ impl FiatProvider for MoonPayClient {
         Ok(self.get_fiat_quote(request, quote))
     }
 
+    async fn get_sell_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let ip_address_check = self.get_ip_address(request.clone().ip_address).await?;
+        if !Self::is_sell_allowed(&ip_address_check) {
+            return Err("sell is not allowed".into());
+        }
+
+        let quote = self
+            .get_sell_quote(
+                request_map.symbol.to_lowercase(),
+                request.fiat_currency.to_lowercase(),
+                request.crypto_amount,
+            )
+            .await?;
+
+        Ok(self.get_sell_fiat_quote(request, quote))
+    }
+
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>> {
@@ -50,6 +72,38 @@ impl FiatProvider for MoonPayClient {
         Ok(assets)
     }
 
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        let countries = self
+            .get_countries()
+            .await?
+            .into_iter()
+            .map(Self::map_country)
+            .collect();
+        Ok(countries)
+    }
+
+    async fn get_limits(
+        &self,
+    ) -> Result<Vec<FiatProviderLimit>, Box<dyn std::error::Error + Send + Sync>> {
+        let limits = self
+            .get_currencies()
+            .await?
+            .into_iter()
+            .flat_map(Self::map_limit)
+            .collect();
+        Ok(limits)
+    }
+
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.verify_webhook_signature(request)?;
+        request.json()
+    }
+
     // full transaction: https://dev.moonpay.com/reference/reference-webhooks-buy
     async fn webhook(
         &self,
@@ -59,12 +113,13 @@ impl FiatProvider for MoonPayClient {
         let asset = Self::map_asset(payload.data.currency).unwrap();
         let asset_id = AssetId::from(asset.chain.unwrap(), asset.token_id);
 
-        let status = match payload.transaction_type.as_str() {
-            "transaction_created" => FiatTransactionStatus::Pending,
-            "transaction_failed" => FiatTransactionStatus::Failed,
-            "transaction_updated" => FiatTransactionStatus::Complete,
-            _ => FiatTransactionStatus::Unknown,
+        // sell orders are reported with the same events prefixed by sell_
+        let transaction_type = match payload.transaction_type.starts_with("sell_") {
+            true => FiatTransactionType::Sell,
+            false => FiatTransactionType::Buy,
         };
+        // transaction_updated is sent for every step, the order status tells where it is
+        let status = Self::map_status(&payload.data.status);
 
         let currency_amount = payload.data.base_currency_amount;
         let fee_provider = payload.data.fee_amount.unwrap_or_default();
@@ -78,6 +133,7 @@ impl FiatProvider for MoonPayClient {
             provider_id: Self::NAME.id(),
             provider_transaction_id: payload.data.id,
             status,
+            transaction_type,
             fiat_amount,
             fiat_currency: payload.data.base_currency.code.to_uppercase(),
             transaction_hash: payload.data.crypto_transaction_id,
@@ -1,15 +1,30 @@
+use base64::{engine::general_purpose, Engine as _};
 use bigdecimal::ToPrimitive;
-use primitives::{Chain, FiatBuyRequest, FiatProviderName, FiatQuote, NumberFormatter};
+use p256::{
+    ecdsa::{signature::Verifier, Signature, VerifyingKey},
+    pkcs8::DecodePublicKey,
+};
+use primitives::{
+    Chain, FiatBuyRequest, FiatPaymentMethod, FiatProviderName, FiatQuote, FiatSellRequest,
+    NumberFormatter,
+};
 use reqwest::Client;
 use url::Url;
 
-use crate::model::FiatProviderAsset;
+use crate::{
+    model::{exchange_rate, FiatProviderAsset, FiatProviderCountry},
+    webhook::verify_datetime,
+    FiatWebhookRequest,
+};
 
-use super::model::{Quote, QuoteAsset, QuoteAssets, QuoteRequest};
+use super::model::{
+    Country, Quote, QuoteAsset, QuoteAssets, QuoteRequest, SellQuote, SellQuoteRequest,
+};
 
 pub struct RampClient {
     client: Client,
     api_key: String,
+    webhook_public_key: String,
 }
 
 const RAMP_API_BASE_URL: &str = "https://api.ramp.network";
@@ -18,8 +33,12 @@ const RAMP_REDIRECT_URL: &str = "https://app.ramp.network";
 impl RampClient {
     pub const NAME: FiatProviderName = FiatProviderName::Ramp;
 
-    pub fn new(client: Client, api_key: String) -> RampClient {
-        RampClient { client, api_key }
+    pub fn new(client: Client, api_key: String, webhook_public_key: String) -> RampClient {
+        RampClient {
+            client,
+            api_key,
+            webhook_public_key,
+        }
     }
 
     pub async fn get_supported_assets(
@@ -41,6 +60,30 @@ impl RampClient {
         Ok(assets)
     }
 
+    pub async fn get_countries(
+        &self,
+    ) -> Result<Vec<Country>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/api/host-api/v3/countries", RAMP_API_BASE_URL);
+        let countries = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json::<Vec<Country>>()
+            .await?;
+        Ok(countries)
+    }
+
+    // only supported countries are listed
+    pub fn map_country(country: Country) -> FiatProviderCountry {
+        let is_allowed = country.card_payments_enabled.unwrap_or(true);
+        FiatProviderCountry {
+            alpha2: country.code.to_uppercase(),
+            is_allowed,
+            is_sell_allowed: is_allowed,
+        }
+    }
+
     pub fn map_asset(asset: QuoteAsset) -> Option<FiatProviderAsset> {
         let chain = Self::map_asset_chain(asset.chain.clone());
         let token_id = asset.token_id();
@@ -100,19 +143,48 @@ impl RampClient {
         Ok(quote)
     }
 
+    pub async fn get_client_sell_quote(
+        &self,
+        request: SellQuoteRequest,
+    ) -> Result<SellQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!(
+            "{}/api/host-api/v3/offramp/quote/all?hostApiKey={}",
+            RAMP_API_BASE_URL, self.api_key
+        );
+        let quote = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?
+            .json::<SellQuote>()
+            .await?;
+        Ok(quote)
+    }
+
     pub fn get_fiat_quote(&self, request: FiatBuyRequest, quote: Quote) -> FiatQuote {
         let crypto_amount = NumberFormatter::big_decimal_value(
             quote.clone().card_payment.crypto_amount.as_str(),
             quote.asset.decimals,
         )
+        .unwrap_or_default()
+        .to_f64()
         .unwrap_or_default();
+        let applied_fee = quote.card_payment.applied_fee.unwrap_or_default();
+        let network_fee = quote.card_payment.network_fee.unwrap_or_default();
+        let host_fee_cut = quote.card_payment.host_fee_cut.unwrap_or_default();
 
         FiatQuote {
             provider: Self::NAME.as_fiat_provider(),
             fiat_amount: request.clone().fiat_amount,
             fiat_currency: request.clone().fiat_currency,
-            crypto_amount: crypto_amount.to_f64().unwrap_or_default(),
+            crypto_amount,
             redirect_url: self.redirect_url(request.clone(), quote.clone()),
+            fee_provider: (applied_fee - network_fee - host_fee_cut).max(0.0),
+            fee_network: network_fee,
+            fee_partner: host_fee_cut,
+            exchange_rate: exchange_rate(request.fiat_amount - applied_fee, crypto_amount),
+            payment_method: Some(FiatPaymentMethod::Card),
         }
     }
 
@@ -133,4 +205,108 @@ impl RampClient {
 
         components.as_str().to_string()
     }
+
+    pub fn get_sell_fiat_quote(
+        &self,
+        request: FiatSellRequest,
+        quote: SellQuote,
+        crypto_amount: String,
+    ) -> FiatQuote {
+        let applied_fee = quote.card.applied_fee.unwrap_or_default();
+        let network_fee = quote.card.network_fee.unwrap_or_default();
+        let host_fee_cut = quote.card.host_fee_cut.unwrap_or_default();
+
+        FiatQuote {
+            provider: Self::NAME.as_fiat_provider(),
+            fiat_amount: quote.card.fiat_value,
+            fiat_currency: request.clone().fiat_currency,
+            crypto_amount: request.crypto_amount,
+            redirect_url: self.sell_redirect_url(request.clone(), quote.clone(), crypto_amount),
+            fee_provider: (applied_fee - network_fee - host_fee_cut).max(0.0),
+            fee_network: network_fee,
+            fee_partner: host_fee_cut,
+            exchange_rate: exchange_rate(
+                quote.card.fiat_value + applied_fee,
+                request.crypto_amount,
+            ),
+            payment_method: Some(FiatPaymentMethod::Card),
+        }
+    }
+
+    pub fn sell_redirect_url(
+        &self,
+        request: FiatSellRequest,
+        quote: SellQuote,
+        crypto_amount: String,
+    ) -> String {
+        let mut components = Url::parse(RAMP_REDIRECT_URL).unwrap();
+        components
+            .query_pairs_mut()
+            .append_pair("hostApiKey", &self.api_key)
+            .append_pair("defaultFlow", "OFFRAMP")
+            .append_pair("enabledFlows", "OFFRAMP")
+            .append_pair("offrampAsset", &quote.asset.crypto_asset_symbol())
+            .append_pair("swapAmount", &crypto_amount)
+            .append_pair("fiatCurrency", &request.fiat_currency)
+            .append_pair("userAddress", request.wallet_address.as_str());
+
+        components.as_str().to_string()
+    }
+
+    // https://docs.ramp.network/webhooks#securing-webhooks
+    // X-Body-Signature is an ECDSA (P-256, SHA-256) signature of the body with keys sorted
+    pub fn verify_webhook_signature(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let signature = general_purpose::STANDARD.decode(request.header("X-Body-Signature")?)?;
+        let signature = Signature::from_der(&signature).map_err(|_| "invalid webhook signature")?;
+        let public_key = VerifyingKey::from_public_key_pem(&self.webhook_public_key)
+            .map_err(|_| "invalid webhook public key")?;
+        // serde_json maps are ordered by key, same as stable stringify used by ramp
+        let payload = serde_json::to_string(&request.json()?)?;
+
+        public_key
+            .verify(payload.as_bytes(), &signature)
+            .map_err(|_| "invalid webhook signature")?;
+        // no timestamp header, updatedAt of the signed purchase bounds replays
+        let updated_at = request.json()?["purchase"]["updatedAt"]
+            .as_str()
+            .ok_or("missing webhook timestamp")?
+            .to_string();
+        verify_datetime(&updated_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_sell_fiat_quote() {
+        let client = RampClient::new(
+            Client::new(),
+            "api_key".to_string(),
+            "public_key".to_string(),
+        );
+        let request: FiatSellRequest = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/sell_request.json"
+        )))
+        .unwrap();
+        let response: SellQuote = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/ramp/sell_quote.json"
+        )))
+        .unwrap();
+
+        let quote = client.get_sell_fiat_quote(request, response, "500000000000000000".to_string());
+
+        assert_eq!(quote.fiat_amount, 976.0);
+        assert_eq!(quote.crypto_amount, 0.5);
+        assert_eq!(quote.fee_provider, 16.0);
+        assert_eq!(quote.fee_network, 3.0);
+        assert_eq!(quote.fee_partner, 5.0);
+        assert_eq!(quote.exchange_rate, 2000.0);
+    }
 }
@@ -14,7 +14,10 @@ pub struct QuoteData {
     pub crypto_amount: String,
     //fiat_value: u32,
     //base_ramp_fee: f64,
-    //applied_fee: f64,
+    // total fee, includes network fee and host fee cut
+    pub applied_fee: Option<f64>,
+    pub network_fee: Option<f64>,
+    pub host_fee_cut: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -60,6 +63,37 @@ pub struct QuoteRequest {
     pub fiat_value: f64,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SellQuoteRequest {
+    pub crypto_asset_symbol: String,
+    pub fiat_currency: String,
+    pub crypto_amount: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SellQuote {
+    #[serde(rename = "CARD")]
+    pub card: SellQuoteData,
+    pub asset: QuoteAsset,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SellQuoteData {
+    pub fiat_value: f64,
+    pub applied_fee: Option<f64>,
+    pub network_fee: Option<f64>,
+    pub host_fee_cut: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Country {
+    pub code: String,
+    pub card_payments_enabled: Option<bool>,
+}
+
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Webhook {
@@ -1,17 +1,18 @@
 use primitives::{
-    AssetId, FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction, FiatTransactionStatus,
+    AssetId, FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest, FiatTransaction,
+    FiatTransactionStatus, FiatTransactionType, NumberFormatter,
 };
 
 use crate::{
-    model::{FiatMapping, FiatProviderAsset},
-    FiatProvider,
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry},
+    FiatProvider, FiatWebhookRequest,
 };
 
 use async_trait::async_trait;
 
 use super::{
     client::RampClient,
-    model::{QuoteRequest, Webhook},
+    model::{QuoteRequest, SellQuoteRequest, Webhook},
 };
 
 #[async_trait]
@@ -53,6 +54,42 @@ impl FiatProvider for RampClient {
         Ok(self.get_fiat_quote(request.clone(), quote))
     }
 
+    async fn get_sell_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let assets = self
+            .get_supported_assets(request.clone().fiat_currency, request.clone().ip_address)
+            .await?
+            .assets;
+
+        let crypto_asset_symbol = format!(
+            "{}_{}",
+            request_map.network.unwrap_or_default(),
+            request_map.symbol,
+        );
+
+        let asset = assets
+            .iter()
+            .find(|x| x.crypto_asset_symbol() == crypto_asset_symbol)
+            .ok_or("asset not supported")?;
+
+        // ramp expects crypto amount in the smallest unit
+        let crypto_amount =
+            NumberFormatter::value_from_amount(&request.crypto_amount.to_string(), asset.decimals)
+                .ok_or("invalid crypto amount")?;
+
+        let payload = SellQuoteRequest {
+            crypto_asset_symbol,
+            fiat_currency: request.clone().fiat_currency,
+            crypto_amount: crypto_amount.clone(),
+        };
+        let quote = self.get_client_sell_quote(payload).await?;
+
+        Ok(self.get_sell_fiat_quote(request, quote, crypto_amount))
+    }
+
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>> {
@@ -66,6 +103,26 @@ impl FiatProvider for RampClient {
         Ok(assets)
     }
 
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        let countries = self
+            .get_countries()
+            .await?
+            .into_iter()
+            .map(Self::map_country)
+            .collect();
+        Ok(countries)
+    }
+
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.verify_webhook_signature(request)?;
+        request.json()
+    }
+
     // full transaction: https://docs.ramp.network/webhooks#example-using-expressjs
     async fn webhook(
         &self,
@@ -91,6 +148,7 @@ impl FiatProvider for RampClient {
             provider_id: Self::NAME.id(),
             provider_transaction_id: payload.purchase_view_token,
             status,
+            transaction_type: FiatTransactionType::Buy,
             fiat_amount: payload.fiat_value,
             fiat_currency: payload.fiat_currency,
             transaction_hash: payload.final_tx_hash,
@@ -1,9 +1,17 @@
-use crate::model::FiatProviderAsset;
-use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote};
+use crate::{
+    model::{exchange_rate, FiatProviderAsset, FiatProviderCountry, FiatProviderLimit},
+    webhook::{verify_hmac_sha256, verify_timestamp},
+    FiatWebhookRequest,
+};
+use base64::{engine::general_purpose, Engine as _};
+use primitives::{FiatBuyRequest, FiatPaymentMethod, FiatProviderName, FiatQuote, FiatSellRequest};
 use reqwest::Client;
 use url::Url;
 
-use super::model::{Asset, TransakQuote, TransakResponse};
+use super::model::{
+    Asset, Country, FiatCurrency, TransakQuote, TransakResponse, WebhookEncrypted,
+    WebhookJwtClaims, WebhookJwtHeader,
+};
 
 const TRANSAK_API_URL: &str = "https://api.transak.com";
 const TRANSAK_REDIRECT_URL: &str = "https://global.transak.com";
@@ -12,13 +20,18 @@ const TRANSAK_REDIRECT_URL: &str = "https://global.transak.com";
 pub struct TransakClient {
     pub client: Client,
     pub api_key: String,
+    pub webhook_secret: String,
 }
 
 impl TransakClient {
     pub const NAME: FiatProviderName = FiatProviderName::Transak;
 
-    pub fn new(client: Client, api_key: String) -> Self {
-        TransakClient { client, api_key }
+    pub fn new(client: Client, api_key: String, webhook_secret: String) -> Self {
+        TransakClient {
+            client,
+            api_key,
+            webhook_secret,
+        }
     }
 
     pub async fn get_buy_quote(
@@ -42,16 +55,49 @@ impl TransakClient {
         Ok(transak_quote)
     }
 
+    pub async fn get_sell_quote(
+        &self,
+        symbol: String,
+        fiat_currency: String,
+        crypto_amount: f64,
+        network: String,
+        ip_address: String,
+    ) -> Result<TransakQuote, reqwest::Error> {
+        let url = format!(
+            "{}/api/v2/currencies/price?ipAddress={}&fiatCurrency={}&cryptoCurrency={}&isBuyOrSell=sell&cryptoAmount={}&network={}&partnerApiKey={}",
+            TRANSAK_API_URL, ip_address, fiat_currency, symbol, crypto_amount, network, self.api_key
+        );
+
+        let response = self.client.get(&url).send().await?;
+        let transak_quote = response
+            .json::<TransakResponse<TransakQuote>>()
+            .await?
+            .response;
+        Ok(transak_quote)
+    }
+
     pub fn get_fiat_quote(&self, request: FiatBuyRequest, quote: TransakQuote) -> FiatQuote {
+        let fees = quote.fees();
+        let total_fee = fees.provider + fees.network + fees.partner;
+        let payment_method = Self::map_quote_payment_method(&quote);
         FiatQuote {
             provider: Self::NAME.as_fiat_provider(),
             fiat_amount: request.fiat_amount,
             fiat_currency: request.fiat_currency,
             crypto_amount: quote.crypto_amount,
-            redirect_url: self.redirect_url(quote, request.wallet_address),
+            redirect_url: self.redirect_url(quote.clone(), request.wallet_address),
+            fee_provider: fees.provider,
+            fee_network: fees.network,
+            fee_partner: fees.partner,
+            exchange_rate: exchange_rate(request.fiat_amount - total_fee, quote.crypto_amount),
+            payment_method,
         }
     }
 
+    fn map_quote_payment_method(quote: &TransakQuote) -> Option<FiatPaymentMethod> {
+        super::mapper::map_payment_method(quote.payment_method.as_deref()?)
+    }
+
     pub fn redirect_url(&self, quote: TransakQuote, address: String) -> String {
         let mut components = Url::parse(TRANSAK_REDIRECT_URL).unwrap();
 
@@ -68,6 +114,40 @@ impl TransakClient {
         return components.as_str().to_string();
     }
 
+    pub fn get_sell_fiat_quote(&self, request: FiatSellRequest, quote: TransakQuote) -> FiatQuote {
+        let fees = quote.fees();
+        let total_fee = fees.provider + fees.network + fees.partner;
+        let payment_method = Self::map_quote_payment_method(&quote);
+        FiatQuote {
+            provider: Self::NAME.as_fiat_provider(),
+            fiat_amount: quote.fiat_amount,
+            fiat_currency: request.fiat_currency,
+            crypto_amount: request.crypto_amount,
+            redirect_url: self.sell_redirect_url(quote.clone(), request.wallet_address),
+            fee_provider: fees.provider,
+            fee_network: fees.network,
+            fee_partner: fees.partner,
+            exchange_rate: exchange_rate(quote.fiat_amount + total_fee, request.crypto_amount),
+            payment_method,
+        }
+    }
+
+    pub fn sell_redirect_url(&self, quote: TransakQuote, address: String) -> String {
+        let mut components = Url::parse(TRANSAK_REDIRECT_URL).unwrap();
+
+        components
+            .query_pairs_mut()
+            .append_pair("apiKey", self.api_key.as_str())
+            .append_pair("productsAvailed", "SELL")
+            .append_pair("cryptoAmount", &quote.crypto_amount.to_string())
+            .append_pair("fiatCurrency", &quote.fiat_currency)
+            .append_pair("cryptoCurrencyCode", &quote.crypto_currency)
+            .append_pair("network", &quote.network.to_string())
+            .append_pair("walletAddress", &address);
+
+        components.as_str().to_string()
+    }
+
     pub async fn get_supported_assets(&self) -> Result<Vec<Asset>, reqwest::Error> {
         let url = format!("{}/api/v2/currencies/crypto-currencies", TRANSAK_API_URL);
         let response = self.client.get(&url).send().await?;
@@ -78,6 +158,51 @@ impl TransakClient {
         Ok(assets)
     }
 
+    pub async fn get_countries(&self) -> Result<Vec<Country>, reqwest::Error> {
+        let url = format!("{}/api/v2/countries", TRANSAK_API_URL);
+        let response = self.client.get(&url).send().await?;
+        let countries = response
+            .json::<TransakResponse<Vec<Country>>>()
+            .await?
+            .response;
+        Ok(countries)
+    }
+
+    pub async fn get_fiat_currencies(&self) -> Result<Vec<FiatCurrency>, reqwest::Error> {
+        let url = format!("{}/api/v2/currencies/fiat-currencies", TRANSAK_API_URL);
+        let response = self.client.get(&url).send().await?;
+        let currencies = response
+            .json::<TransakResponse<Vec<FiatCurrency>>>()
+            .await?
+            .response;
+        Ok(currencies)
+    }
+
+    pub fn map_country(country: Country) -> FiatProviderCountry {
+        FiatProviderCountry {
+            alpha2: country.alpha2,
+            is_allowed: country.is_allowed,
+            is_sell_allowed: country.is_allowed,
+        }
+    }
+
+    // payment options without a known method are still counted as limits for the currency
+    pub fn map_limits(currency: FiatCurrency) -> Vec<FiatProviderLimit> {
+        currency
+            .payment_options
+            .into_iter()
+            .filter(|x| x.is_active.unwrap_or(true))
+            .flat_map(|x| {
+                Some(FiatProviderLimit {
+                    fiat_currency: currency.symbol.to_uppercase(),
+                    payment_method: super::mapper::map_payment_method(&x.id),
+                    min_amount: x.min_amount?,
+                    max_amount: x.max_amount?,
+                })
+            })
+            .collect()
+    }
+
     pub fn map_asset(asset: Asset) -> Option<FiatProviderAsset> {
         let chain = super::mapper::map_asset_chain(asset.clone());
         let token_id = asset.clone().address.filter(|contract_address| {
@@ -93,4 +218,69 @@ impl TransakClient {
             enabled: asset.is_allowed,
         })
     }
+
+    // https://docs.transak.com/docs/webhooks
+    // body is {"data": "<jwt>"}, signed with HS256 using the partner access token
+    pub fn verify_webhook_token(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        let token = serde_json::from_slice::<WebhookEncrypted>(&request.body)?.data;
+        let parts: Vec<&str> = token.split('.').collect();
+        let (header, claims, signature) = match parts.as_slice() {
+            [header, claims, signature] => (*header, *claims, *signature),
+            _ => return Err("invalid webhook token".into()),
+        };
+
+        let jwt_header: WebhookJwtHeader =
+            serde_json::from_slice(&general_purpose::URL_SAFE_NO_PAD.decode(header)?)?;
+        if jwt_header.alg != "HS256" {
+            return Err(format!("unsupported webhook token algorithm: {}", jwt_header.alg).into());
+        }
+        verify_hmac_sha256(
+            self.webhook_secret.as_bytes(),
+            format!("{}.{}", header, claims).as_bytes(),
+            &general_purpose::URL_SAFE_NO_PAD.decode(signature)?,
+        )?;
+
+        let claims = general_purpose::URL_SAFE_NO_PAD.decode(claims)?;
+        if let Some(iat) = serde_json::from_slice::<WebhookJwtClaims>(&claims)?.iat {
+            verify_timestamp(iat)?;
+        }
+        Ok(serde_json::from_slice(&claims)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_sell_fiat_quote() {
+        let client = TransakClient::new(
+            Client::new(),
+            "api_key".to_string(),
+            "webhook_secret".to_string(),
+        );
+        let request: FiatSellRequest = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/sell_request.json"
+        )))
+        .unwrap();
+        let response: TransakResponse<TransakQuote> = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/transak/sell_quote.json"
+        )))
+        .unwrap();
+
+        let quote = client.get_sell_fiat_quote(request, response.response);
+
+        assert_eq!(quote.fiat_amount, 975.0);
+        assert_eq!(quote.crypto_amount, 0.5);
+        assert_eq!(quote.fee_provider, 15.0);
+        assert_eq!(quote.fee_network, 6.0);
+        assert_eq!(quote.fee_partner, 4.0);
+        assert_eq!(quote.exchange_rate, 2000.0);
+        assert_eq!(quote.payment_method, Some(FiatPaymentMethod::Card));
+    }
 }
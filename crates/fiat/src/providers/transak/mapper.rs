@@ -1,7 +1,19 @@
-use primitives::Chain;
+use primitives::{Chain, FiatPaymentMethod};
 
 use super::model::Asset;
 
+pub fn map_payment_method(id: &str) -> Option<FiatPaymentMethod> {
+    match id {
+        "credit_debit_card" => Some(FiatPaymentMethod::Card),
+        "apple_pay" => Some(FiatPaymentMethod::ApplePay),
+        "google_pay" => Some(FiatPaymentMethod::GooglePay),
+        "sepa_bank_transfer" | "gbp_bank_transfer" | "pm_us_wire_bank_transfer" => {
+            Some(FiatPaymentMethod::BankTransfer)
+        }
+        _ => None,
+    }
+}
+
 pub fn map_asset_chain(asset: Asset) -> Option<Chain> {
     match asset.network.name.as_str() {
         "ethereum" => Some(Chain::Ethereum),
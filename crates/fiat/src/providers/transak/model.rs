@@ -9,6 +9,39 @@ pub struct TransakQuote {
     pub crypto_currency: String,
     pub crypto_amount: f64,
     pub network: String,
+    pub payment_method: Option<String>,
+    pub fee_breakdown: Option<Vec<TransakFee>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransakFee {
+    pub id: String,
+    pub value: f64,
+}
+
+// fees in fiat currency
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransakFees {
+    pub provider: f64,
+    pub network: f64,
+    pub partner: f64,
+}
+
+impl TransakQuote {
+    pub fn fees(&self) -> TransakFees {
+        self.fee_breakdown
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .fold(TransakFees::default(), |mut fees, fee| {
+                match fee.id.as_str() {
+                    "network_fee" => fees.network += fee.value,
+                    "partner_fee" => fees.partner += fee.value,
+                    _ => fees.provider += fee.value,
+                }
+                fees
+            })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,3 +64,41 @@ pub struct Asset {
 pub struct AssetNetwork {
     pub name: String,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Country {
+    pub alpha2: String,
+    pub is_allowed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatCurrency {
+    pub symbol: String,
+    pub payment_options: Vec<PaymentOption>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentOption {
+    pub id: String,
+    pub is_active: Option<bool>,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookEncrypted {
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookJwtHeader {
+    pub alg: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookJwtClaims {
+    pub iat: Option<u64>,
+}
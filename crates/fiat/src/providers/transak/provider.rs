@@ -1,9 +1,9 @@
 use crate::{
-    model::{FiatMapping, FiatProviderAsset},
-    FiatProvider,
+    model::{FiatMapping, FiatProviderAsset, FiatProviderCountry, FiatProviderLimit},
+    FiatProvider, FiatWebhookRequest,
 };
 use async_trait::async_trait;
-use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote, FiatTransaction};
+use primitives::{FiatBuyRequest, FiatProviderName, FiatQuote, FiatSellRequest, FiatTransaction};
 
 use super::client::TransakClient;
 
@@ -31,6 +31,24 @@ impl FiatProvider for TransakClient {
         Ok(self.get_fiat_quote(request, quote))
     }
 
+    async fn get_sell_quote(
+        &self,
+        request: FiatSellRequest,
+        request_map: FiatMapping,
+    ) -> Result<FiatQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let quote = self
+            .get_sell_quote(
+                request_map.symbol.clone(),
+                request.fiat_currency.clone(),
+                request.crypto_amount,
+                request_map.network.unwrap_or_default(),
+                request.ip_address.clone(),
+            )
+            .await?;
+
+        Ok(self.get_sell_fiat_quote(request, quote))
+    }
+
     async fn get_assets(
         &self,
     ) -> Result<Vec<FiatProviderAsset>, Box<dyn std::error::Error + Send + Sync>> {
@@ -43,6 +61,37 @@ impl FiatProvider for TransakClient {
         Ok(assets)
     }
 
+    async fn get_countries(
+        &self,
+    ) -> Result<Vec<FiatProviderCountry>, Box<dyn std::error::Error + Send + Sync>> {
+        let countries = self
+            .get_countries()
+            .await?
+            .into_iter()
+            .map(Self::map_country)
+            .collect();
+        Ok(countries)
+    }
+
+    async fn get_limits(
+        &self,
+    ) -> Result<Vec<FiatProviderLimit>, Box<dyn std::error::Error + Send + Sync>> {
+        let limits = self
+            .get_fiat_currencies()
+            .await?
+            .into_iter()
+            .flat_map(Self::map_limits)
+            .collect();
+        Ok(limits)
+    }
+
+    fn verify_webhook(
+        &self,
+        request: &FiatWebhookRequest,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.verify_webhook_token(request)
+    }
+
     async fn webhook(
         &self,
         _data: serde_json::Value,
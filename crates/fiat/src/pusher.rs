@@ -0,0 +1,108 @@
+use api_connector::pusher::model::{Message, Response};
+use api_connector::DevicePusher;
+use primitives::{
+    Device, FiatTransaction, FiatTransactionStatus, FiatTransactionType,
+    PushNotificationFiatTransaction, PushNotificationTypes,
+};
+
+// prepared while holding the fiat client, sent once it is released
+#[derive(Debug, Clone)]
+pub struct FiatNotification {
+    pub device: Device,
+    pub transaction: FiatTransaction,
+    pub message: Message,
+}
+
+pub struct FiatPusher {
+    pusher: DevicePusher,
+}
+
+impl FiatPusher {
+    pub fn new(url: String, ios_topic: String) -> Self {
+        Self {
+            pusher: DevicePusher::new(url, ios_topic),
+        }
+    }
+
+    // only final order states are pushed, pending updates are silent
+    pub fn message(transaction: &FiatTransaction, provider_name: &str) -> Option<Message> {
+        let action = match transaction.transaction_type {
+            FiatTransactionType::Buy => "Purchase",
+            FiatTransactionType::Sell => "Sale",
+        };
+        let message = format!(
+            "{} {} via {}",
+            transaction.fiat_amount, transaction.fiat_currency, provider_name
+        );
+        let title = match transaction.status {
+            FiatTransactionStatus::Complete => {
+                format!("{} of {} completed", action, transaction.symbol)
+            }
+            FiatTransactionStatus::Failed => format!("{} of {} failed", action, transaction.symbol),
+            FiatTransactionStatus::Pending | FiatTransactionStatus::Unknown => return None,
+        };
+        Some(Message {
+            title,
+            message: Some(message),
+        })
+    }
+
+    pub async fn push(&self, notification: FiatNotification) -> Result<Response, reqwest::Error> {
+        let data = PushNotificationFiatTransaction {
+            notification_type: PushNotificationTypes::FiatTransaction,
+            data: notification.transaction,
+        };
+        self.pusher
+            .push(
+                &notification.device,
+                notification.message,
+                serde_json::to_value(data).ok(),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(
+        status: FiatTransactionStatus,
+        transaction_type: FiatTransactionType,
+    ) -> FiatTransaction {
+        FiatTransaction {
+            asset_id: None,
+            provider_id: "moonpay".to_string(),
+            provider_transaction_id: "1".to_string(),
+            status,
+            transaction_type,
+            symbol: "ETH".to_string(),
+            fiat_amount: 100.0,
+            fiat_currency: "USD".to_string(),
+            transaction_hash: None,
+            address: None,
+            fee_provider: 0.0,
+            fee_network: 0.0,
+            fee_partner: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        let buy = transaction(FiatTransactionStatus::Complete, FiatTransactionType::Buy);
+        let message = FiatPusher::message(&buy, "MoonPay").unwrap();
+        assert_eq!(message.title, "Purchase of ETH completed");
+        assert_eq!(message.message, Some("100 USD via MoonPay".to_string()));
+
+        let failed = transaction(FiatTransactionStatus::Failed, FiatTransactionType::Buy);
+        let message = FiatPusher::message(&failed, "MoonPay").unwrap();
+        assert_eq!(message.title, "Purchase of ETH failed");
+
+        let sell = transaction(FiatTransactionStatus::Complete, FiatTransactionType::Sell);
+        let message = FiatPusher::message(&sell, "MoonPay").unwrap();
+        assert_eq!(message.title, "Sale of ETH completed");
+
+        let pending = transaction(FiatTransactionStatus::Pending, FiatTransactionType::Buy);
+        assert!(FiatPusher::message(&pending, "MoonPay").is_none());
+    }
+}
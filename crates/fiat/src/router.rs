@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+use maxminddb::{geoip2, Reader};
+use primitives::{FiatBuyRequest, FiatProviderExclusionReason, FiatTransactionType};
+
+use crate::model::{FiatProviderCountry, FiatProviderLimit};
+
+// Filters providers by the user's country, fiat currency, payment method and purchase limits.
+// Routing data is fetched from provider apis and refreshed once `ttl` has passed,
+// a failed fetch keeps the previous data for the provider.
+pub struct FiatRouter {
+    geoip: Option<Reader<Vec<u8>>>,
+    ttl: Duration,
+    updated_at: Option<Instant>,
+    countries: HashMap<String, Vec<FiatProviderCountry>>,
+    limits: HashMap<String, Vec<FiatProviderLimit>>,
+}
+
+impl FiatRouter {
+    pub fn new(geoip_path: &str, ttl: u64) -> Self {
+        let geoip = if geoip_path.is_empty() {
+            None
+        } else {
+            match Reader::open_readfile(geoip_path) {
+                Ok(reader) => Some(reader),
+                Err(err) => {
+                    println!("fiat router geoip database {} error: {}", geoip_path, err);
+                    None
+                }
+            }
+        };
+        Self {
+            geoip,
+            ttl: Duration::from_secs(ttl),
+            updated_at: None,
+            countries: HashMap::new(),
+            limits: HashMap::new(),
+        }
+    }
+
+    // ISO 3166-1 alpha-2 country code for the ip address
+    pub fn get_country(&self, ip_address: &str) -> Option<String> {
+        let ip_address = ip_address.parse::<IpAddr>().ok()?;
+        let country: geoip2::Country = self.geoip.as_ref()?.lookup(ip_address).ok()?;
+        country.country?.iso_code.map(|x| x.to_string())
+    }
+
+    pub fn is_outdated(&self) -> bool {
+        match self.updated_at {
+            Some(updated_at) => updated_at.elapsed() > self.ttl,
+            None => true,
+        }
+    }
+
+    // marks the routes as updated, returns false when they are fresh or already being updated
+    pub fn start_update(&mut self) -> bool {
+        if !self.is_outdated() {
+            return false;
+        }
+        self.updated_at = Some(Instant::now());
+        true
+    }
+
+    pub fn set_countries(&mut self, provider_id: &str, countries: Vec<FiatProviderCountry>) {
+        self.countries.insert(provider_id.to_string(), countries);
+    }
+
+    pub fn set_limits(&mut self, provider_id: &str, limits: Vec<FiatProviderLimit>) {
+        self.limits.insert(provider_id.to_string(), limits);
+    }
+
+    pub fn route(
+        &self,
+        provider_id: &str,
+        request: &FiatBuyRequest,
+        country: Option<&str>,
+    ) -> Result<(), FiatProviderExclusionReason> {
+        if let (Some(countries), Some(country)) = (self.countries.get(provider_id), country) {
+            check_country(countries, country, FiatTransactionType::Buy)?;
+        }
+        if let Some(limits) = self.limits.get(provider_id) {
+            check_limits(limits, request)?;
+        }
+        Ok(())
+    }
+
+    // limits are listed in fiat for purchases, sells are only routed by country
+    pub fn route_sell(
+        &self,
+        provider_id: &str,
+        country: Option<&str>,
+    ) -> Result<(), FiatProviderExclusionReason> {
+        if let (Some(countries), Some(country)) = (self.countries.get(provider_id), country) {
+            check_country(countries, country, FiatTransactionType::Sell)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_country(
+    countries: &[FiatProviderCountry],
+    country: &str,
+    transaction_type: FiatTransactionType,
+) -> Result<(), FiatProviderExclusionReason> {
+    if countries.is_empty() {
+        return Ok(());
+    }
+    let is_allowed = countries
+        .iter()
+        .find(|x| x.alpha2.eq_ignore_ascii_case(country))
+        .map(|x| match transaction_type {
+            FiatTransactionType::Buy => x.is_allowed,
+            FiatTransactionType::Sell => x.is_sell_allowed,
+        })
+        .unwrap_or(false);
+    if is_allowed {
+        Ok(())
+    } else {
+        Err(FiatProviderExclusionReason::UnsupportedCountry)
+    }
+}
+
+fn check_limits(
+    limits: &[FiatProviderLimit],
+    request: &FiatBuyRequest,
+) -> Result<(), FiatProviderExclusionReason> {
+    if limits.is_empty() {
+        return Ok(());
+    }
+    let limits: Vec<&FiatProviderLimit> = limits
+        .iter()
+        .filter(|x| x.fiat_currency.eq_ignore_ascii_case(&request.fiat_currency))
+        .collect();
+    if limits.is_empty() {
+        return Err(FiatProviderExclusionReason::UnsupportedCurrency);
+    }
+    let limits: Vec<&FiatProviderLimit> = match &request.payment_method {
+        Some(payment_method) => limits
+            .into_iter()
+            .filter(|x| {
+                x.payment_method.is_none() || x.payment_method.as_ref() == Some(payment_method)
+            })
+            .collect(),
+        None => limits,
+    };
+    if limits.is_empty() {
+        return Err(FiatProviderExclusionReason::UnsupportedPaymentMethod);
+    }
+
+    let amount = request.fiat_amount;
+    if limits
+        .iter()
+        .any(|x| amount >= x.min_amount && amount <= x.max_amount)
+    {
+        Ok(())
+    } else if limits.iter().all(|x| amount < x.min_amount) {
+        Err(FiatProviderExclusionReason::AmountBelowMinimum)
+    } else {
+        Err(FiatProviderExclusionReason::AmountAboveMaximum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::FiatPaymentMethod;
+
+    use super::*;
+
+    fn request(
+        fiat_currency: &str,
+        fiat_amount: f64,
+        payment_method: Option<FiatPaymentMethod>,
+    ) -> FiatBuyRequest {
+        FiatBuyRequest {
+            asset_id: "ethereum".to_string(),
+            ip_address: "1.1.1.1".to_string(),
+            fiat_currency: fiat_currency.to_string(),
+            fiat_amount,
+            wallet_address: "0x".to_string(),
+            payment_method,
+        }
+    }
+
+    fn limit(
+        fiat_currency: &str,
+        payment_method: Option<FiatPaymentMethod>,
+        min_amount: f64,
+        max_amount: f64,
+    ) -> FiatProviderLimit {
+        FiatProviderLimit {
+            fiat_currency: fiat_currency.to_string(),
+            payment_method,
+            min_amount,
+            max_amount,
+        }
+    }
+
+    #[test]
+    fn test_check_country() {
+        let countries = vec![
+            FiatProviderCountry {
+                alpha2: "US".to_string(),
+                is_allowed: true,
+                is_sell_allowed: false,
+            },
+            FiatProviderCountry {
+                alpha2: "CU".to_string(),
+                is_allowed: false,
+                is_sell_allowed: false,
+            },
+        ];
+        let buy = FiatTransactionType::Buy;
+
+        assert_eq!(check_country(&countries, "us", buy.clone()), Ok(()));
+        assert_eq!(
+            check_country(&countries, "US", FiatTransactionType::Sell),
+            Err(FiatProviderExclusionReason::UnsupportedCountry)
+        );
+        assert_eq!(
+            check_country(&countries, "CU", buy.clone()),
+            Err(FiatProviderExclusionReason::UnsupportedCountry)
+        );
+        assert_eq!(
+            check_country(&countries, "FR", buy.clone()),
+            Err(FiatProviderExclusionReason::UnsupportedCountry)
+        );
+        assert_eq!(check_country(&[], "FR", buy), Ok(()));
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let limits = vec![
+            limit("USD", Some(FiatPaymentMethod::Card), 20.0, 5000.0),
+            limit("USD", Some(FiatPaymentMethod::BankTransfer), 50.0, 20000.0),
+            limit("EUR", None, 30.0, 3000.0),
+        ];
+
+        assert_eq!(check_limits(&limits, &request("USD", 100.0, None)), Ok(()));
+        assert_eq!(
+            check_limits(&limits, &request("USD", 10000.0, None)),
+            Ok(())
+        );
+        assert_eq!(
+            check_limits(
+                &limits,
+                &request("USD", 10000.0, Some(FiatPaymentMethod::Card))
+            ),
+            Err(FiatProviderExclusionReason::AmountAboveMaximum)
+        );
+        assert_eq!(
+            check_limits(&limits, &request("USD", 10.0, None)),
+            Err(FiatProviderExclusionReason::AmountBelowMinimum)
+        );
+        assert_eq!(
+            check_limits(
+                &limits,
+                &request("USD", 100.0, Some(FiatPaymentMethod::ApplePay))
+            ),
+            Err(FiatProviderExclusionReason::UnsupportedPaymentMethod)
+        );
+        assert_eq!(
+            check_limits(
+                &limits,
+                &request("EUR", 100.0, Some(FiatPaymentMethod::ApplePay))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_limits(&limits, &request("GBP", 100.0, None)),
+            Err(FiatProviderExclusionReason::UnsupportedCurrency)
+        );
+        assert_eq!(check_limits(&[], &request("GBP", 100.0, None)), Ok(()));
+    }
+}
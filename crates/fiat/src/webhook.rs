@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+// Maximum allowed difference between the signed timestamp and now, older callbacks are treated as replays
+pub const WEBHOOK_TIMESTAMP_TOLERANCE: u64 = 5 * 60; // 5 minutes
+
+// Callbacks inside the timestamp window are only accepted once, the window spans both directions
+pub const WEBHOOK_REPLAY_EXPIRATION: i64 = 2 * WEBHOOK_TIMESTAMP_TOLERANCE as i64;
+
+#[derive(Debug, Clone)]
+pub struct FiatWebhookRequest {
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl FiatWebhookRequest {
+    pub fn new(path: String, headers: HashMap<String, String>, body: Vec<u8>) -> Self {
+        let headers = headers
+            .into_iter()
+            .map(|(key, value)| (key.to_lowercase(), value))
+            .collect();
+        Self {
+            path,
+            headers,
+            body,
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Result<&str, Box<dyn Error + Send + Sync>> {
+        self.headers
+            .get(&name.to_lowercase())
+            .map(|x| x.as_str())
+            .ok_or(format!("missing webhook header: {}", name).into())
+    }
+
+    pub fn json(&self) -> Result<serde_json::Value, Box<dyn Error + Send + Sync>> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    // a replayed callback carries the same signed body
+    pub fn replay_key(&self, provider: &str) -> String {
+        format!(
+            "fiat:webhook:{}:{}",
+            provider,
+            hex::encode(Sha256::digest(&self.body))
+        )
+    }
+}
+
+pub fn verify_hmac_sha256(
+    secret: &[u8],
+    payload: &[u8],
+    signature: &[u8],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if secret.is_empty() {
+        return Err("webhook secret is not configured".into());
+    }
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).map_err(|e| e.to_string())?;
+    mac.update(payload);
+    mac.verify_slice(signature)
+        .map_err(|_| "invalid webhook signature".into())
+}
+
+pub fn verify_timestamp(timestamp: u64) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    verify_timestamp_at(timestamp, now)
+}
+
+// for providers that only sign the body, the event time inside the signed payload is checked
+pub fn verify_datetime(value: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let timestamp = chrono::DateTime::parse_from_rfc3339(value)?.timestamp();
+    verify_timestamp(u64::try_from(timestamp)?)
+}
+
+fn verify_timestamp_at(timestamp: u64, now: u64) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if now.abs_diff(timestamp) > WEBHOOK_TIMESTAMP_TOLERANCE {
+        return Err(format!(
+            "webhook timestamp {} is outside of allowed window",
+            timestamp
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header() {
+        let headers = HashMap::from([("Moonpay-Signature-V2".to_string(), "t=1".to_string())]);
+        let request = FiatWebhookRequest::new("/".to_string(), headers, vec![]);
+
+        assert_eq!(request.header("moonpay-signature-v2").unwrap(), "t=1");
+        assert!(request.header("x-signature").is_err());
+    }
+
+    #[test]
+    fn test_replay_key() {
+        let request = FiatWebhookRequest::new("/".to_string(), HashMap::new(), b"{}".to_vec());
+        let other = FiatWebhookRequest::new("/".to_string(), HashMap::new(), b"{ }".to_vec());
+
+        assert_eq!(
+            request.replay_key("moonpay"),
+            request.clone().replay_key("moonpay")
+        );
+        assert_ne!(request.replay_key("moonpay"), other.replay_key("moonpay"));
+        assert_ne!(request.replay_key("moonpay"), request.replay_key("ramp"));
+    }
+
+    #[test]
+    fn test_verify_hmac_sha256() {
+        let signature =
+            hex::decode("f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8")
+                .unwrap();
+        let payload = b"The quick brown fox jumps over the lazy dog";
+
+        assert!(verify_hmac_sha256(b"key", payload, &signature).is_ok());
+        assert!(verify_hmac_sha256(b"key", b"tampered", &signature).is_err());
+        assert!(verify_hmac_sha256(b"", payload, &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_timestamp_at() {
+        assert!(verify_timestamp_at(1_700_000_000, 1_700_000_000).is_ok());
+        assert!(verify_timestamp_at(1_700_000_000, 1_700_000_300).is_ok());
+        assert!(verify_timestamp_at(1_700_000_000, 1_700_000_301).is_err());
+        assert!(verify_timestamp_at(1_700_000_400, 1_700_000_000).is_err());
+    }
+
+    #[test]
+    fn test_verify_datetime() {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        assert!(verify_datetime(&now).is_ok());
+        assert!(verify_datetime("2021-05-17T12:23:40.000Z").is_err());
+        assert!(verify_datetime("2021-05-17 12:23:40").is_err());
+    }
+}
@@ -1,13 +1,15 @@
 use std::{error::Error, str::FromStr};
 
-use crate::ChainProvider;
+use crate::{
+    broadcast::check_node_status, BalanceProvider, ChainProvider, TransactionBroadcastProvider,
+};
 use async_trait::async_trait;
 use chrono::Utc;
 use num_bigint::BigUint;
-use primitives::{chain::Chain, TransactionState, TransactionType};
+use primitives::{chain::Chain, AssetBalance, AssetId, TransactionState, TransactionType};
 use reqwest_middleware::ClientWithMiddleware;
 
-use super::model::{Block, Ledger, DEPOSIT_EVENT};
+use super::model::{Block, Ledger, Resource, TransactionResponse, DEPOSIT_EVENT};
 
 pub struct AptosClient {
     url: String,
@@ -19,6 +21,37 @@ impl AptosClient {
         Self { url, client }
     }
 
+    pub async fn get_resources(
+        &self,
+        address: &str,
+    ) -> Result<Vec<Resource>, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/v1/accounts/{}/resources", self.url, address);
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<Vec<Resource>>()
+            .await?)
+    }
+
+    // None when the account does not exist or does not hold the resource
+    pub async fn get_resource(
+        &self,
+        address: &str,
+        resource_type: &str,
+    ) -> Result<Option<Resource>, Box<dyn Error + Send + Sync>> {
+        let url = format!(
+            "{}/v1/accounts/{}/resource/{}",
+            self.url, address, resource_type
+        );
+        let response = self.client.get(url).send().await?;
+        if response.status().as_u16() == 404 {
+            return Ok(None);
+        }
+        Ok(Some(response.json::<Resource>().await?))
+    }
+
     pub fn map_transaction(
         &self,
         transaction: super::model::Transaction,
@@ -86,6 +119,67 @@ impl AptosClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for AptosClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let denom = Chain::Aptos.as_denom().unwrap_or_default();
+        let resource_type = format!("0x1::coin::CoinStore<{}>", denom);
+        let balance = self
+            .get_resource(address, &resource_type)
+            .await?
+            .and_then(|x| x.coin_value())
+            .unwrap_or("0".to_string());
+        Ok(AssetBalance::new(Chain::Aptos.as_asset_id(), balance))
+    }
+
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        _token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let denom = Chain::Aptos.as_denom().unwrap_or_default();
+        Ok(self
+            .get_resources(address)
+            .await?
+            .into_iter()
+            .filter_map(|x| {
+                let coin_type = x.coin_type().filter(|x| *x != denom)?.to_string();
+                Some(AssetBalance::new(
+                    AssetId::from(Chain::Aptos, Some(coin_type)),
+                    x.coin_value()?,
+                ))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for AptosClient {
+    // data is the signed transaction in json encoding
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/v1/transactions", self.url);
+        let transaction: serde_json::Value = serde_json::from_str(data)?;
+        let response = self.client.post(url).json(&transaction).send().await?;
+        check_node_status(&response)?;
+        let response = response.json::<TransactionResponse>().await?;
+        match response.hash {
+            Some(hash) => Ok(hash),
+            None => Err(format!(
+                "{} {}",
+                response.error_code.unwrap_or_default(),
+                response.message.unwrap_or_default()
+            )
+            .into()),
+        }
+    }
+}
+
 #[async_trait]
 impl ChainProvider for AptosClient {
     fn get_chain(&self) -> Chain {
@@ -62,3 +62,51 @@ impl Event {
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resource {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub data: serde_json::Value,
+}
+
+impl Resource {
+    // coin type of a 0x1::coin::CoinStore<..> resource
+    pub fn coin_type(&self) -> Option<&str> {
+        self.resource_type
+            .strip_prefix("0x1::coin::CoinStore<")?
+            .strip_suffix('>')
+    }
+
+    pub fn coin_value(&self) -> Option<String> {
+        Some(self.data["coin"]["value"].as_str()?.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionResponse {
+    pub hash: Option<String>,
+    pub message: Option<String>,
+    pub error_code: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coin_type() {
+        let resource = Resource {
+            resource_type: "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>".to_string(),
+            data: serde_json::json!({ "coin": { "value": "100" } }),
+        };
+        assert_eq!(resource.coin_type(), Some("0x1::aptos_coin::AptosCoin"));
+        assert_eq!(resource.coin_value(), Some("100".to_string()));
+
+        let resource = Resource {
+            resource_type: "0x1::account::Account".to_string(),
+            data: serde_json::json!({}),
+        };
+        assert_eq!(resource.coin_type(), None);
+    }
+}
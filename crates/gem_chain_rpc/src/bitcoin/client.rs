@@ -1,13 +1,17 @@
 use std::error::Error;
 
-use crate::ChainProvider;
+use crate::{
+    broadcast::check_node_status, BalanceProvider, ChainProvider, FeeProvider,
+    TransactionBroadcastProvider,
+};
 use async_trait::async_trait;
 use chrono::Utc;
 use primitives::{
-    chain::Chain, transaction_utxo::TransactionInput, TransactionDirection, TransactionType,
+    chain::Chain, transaction_utxo::TransactionInput, AssetBalance, FeePriority, FeeRate,
+    TransactionDirection, TransactionType,
 };
 
-use super::model::{Block, Status, Transaction};
+use super::model::{Address, Block, FeeResult, SendTransaction, Status, Transaction};
 use reqwest_middleware::ClientWithMiddleware;
 
 pub struct BitcoinClient {
@@ -107,6 +111,90 @@ impl BitcoinClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for BitcoinClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/api/v2/address/{}?details=basic", self.url, address);
+        let address = self.client.get(url).send().await?.json::<Address>().await?;
+        Ok(AssetBalance::new(self.chain.as_asset_id(), address.balance))
+    }
+}
+
+impl BitcoinClient {
+    fn fee_blocks(priority: FeePriority) -> i64 {
+        match priority {
+            FeePriority::Slow => 12,
+            FeePriority::Normal => 6,
+            FeePriority::Fast => 1,
+        }
+    }
+
+    // coins per kilobyte to satoshis per vbyte, at least 1 sat/vB.
+    // blockbook returns -1 when it has no estimate for the target
+    fn fee_rate_per_vbyte(value: f64) -> Option<i64> {
+        if value <= 0.0 {
+            return None;
+        }
+        let satoshis_per_kb = (value * 100_000_000.0).round() as i64;
+        Some(((satoshis_per_kb + 999) / 1000).max(1))
+    }
+}
+
+#[async_trait]
+impl FeeProvider for BitcoinClient {
+    // a priority without an estimate takes the rate of the next faster one,
+    // without a fast estimate there is no rate that would not risk getting stuck
+    async fn get_fee_rates(&self) -> Result<Vec<FeeRate>, Box<dyn Error + Send + Sync>> {
+        let mut rates = vec![];
+        let mut faster_rate: Option<i64> = None;
+        for priority in [FeePriority::Fast, FeePriority::Normal, FeePriority::Slow] {
+            let url = format!(
+                "{}/api/v2/estimatefee/{}",
+                self.url,
+                Self::fee_blocks(priority)
+            );
+            let fee = self
+                .client
+                .get(url)
+                .send()
+                .await?
+                .json::<FeeResult>()
+                .await?;
+            let value = Self::fee_rate_per_vbyte(fee.result.parse::<f64>()?)
+                .or(faster_rate)
+                .ok_or(format!(
+                    "no fee estimate for blocks: {}",
+                    Self::fee_blocks(priority)
+                ))?;
+            faster_rate = Some(value);
+            rates.push(FeeRate::new(priority, value.to_string()));
+        }
+        rates.reverse();
+        Ok(rates)
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for BitcoinClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/api/v2/sendtx/", self.url);
+        let response = self.client.post(url).body(data.to_string()).send().await?;
+        check_node_status(&response)?;
+        let response = response.json::<SendTransaction>().await?;
+        match (response.result, response.error) {
+            (Some(hash), _) => Ok(hash),
+            (None, Some(error)) => Err(error.into()),
+            (None, None) => Err("empty sendtx response".into()),
+        }
+    }
+}
+
 #[async_trait]
 impl ChainProvider for BitcoinClient {
     fn get_chain(&self) -> Chain {
@@ -220,4 +308,13 @@ mod tests {
         assert_eq!(finalized.direction, TransactionDirection::Outgoing);
         assert_eq!(finalized.value, "34820917");
     }
+
+    #[test]
+    fn test_fee_rate_per_vbyte() {
+        use super::*;
+
+        assert_eq!(BitcoinClient::fee_rate_per_vbyte(0.00012), Some(12));
+        assert_eq!(BitcoinClient::fee_rate_per_vbyte(0.000001), Some(1));
+        assert_eq!(BitcoinClient::fee_rate_per_vbyte(-1.0), None);
+    }
 }
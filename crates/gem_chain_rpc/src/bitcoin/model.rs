@@ -11,6 +11,23 @@ pub struct Blockbook {
     pub best_height: i64,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Address {
+    pub balance: String,
+}
+
+// fee rate in coins per kilobyte
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FeeResult {
+    pub result: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SendTransaction {
+    pub result: Option<String>,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Block {
@@ -0,0 +1,80 @@
+use std::{error::Error, fmt};
+
+use jsonrpsee::core::ClientError;
+use primitives::TransactionBroadcastErrorCode;
+use reqwest::{Response, StatusCode};
+
+// the node answered with a server error or throttled the request, the body is usually a proxy page
+#[derive(Debug)]
+pub struct NodeUnavailableError {
+    pub status: u16,
+}
+
+impl fmt::Display for NodeUnavailableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "node unavailable, status: {}", self.status)
+    }
+}
+
+impl Error for NodeUnavailableError {}
+
+// client errors still carry the node's rejection in the body, only server errors are checked
+pub fn check_node_status(response: &Response) -> Result<(), NodeUnavailableError> {
+    let status = response.status();
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(NodeUnavailableError {
+            status: status.as_u16(),
+        });
+    }
+    Ok(())
+}
+
+// transport and status errors first, node messages are only matched for actual rejections
+pub fn broadcast_error_code(
+    error: &(dyn Error + Send + Sync + 'static),
+) -> TransactionBroadcastErrorCode {
+    if error.is::<NodeUnavailableError>()
+        || error.is::<reqwest_middleware::Error>()
+        || error.is::<reqwest::Error>()
+    {
+        return TransactionBroadcastErrorCode::NodeUnavailable;
+    }
+    match error.downcast_ref::<ClientError>() {
+        Some(ClientError::Call(error)) => {
+            TransactionBroadcastErrorCode::from_message(error.message())
+        }
+        Some(
+            ClientError::Transport(_)
+            | ClientError::RequestTimeout
+            | ClientError::ParseError(_)
+            | ClientError::RestartNeeded(_),
+        ) => TransactionBroadcastErrorCode::NodeUnavailable,
+        _ => TransactionBroadcastErrorCode::from_message(&error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_error_code() {
+        let error: Box<dyn Error + Send + Sync> = Box::new(NodeUnavailableError { status: 502 });
+        assert_eq!(
+            broadcast_error_code(error.as_ref()),
+            TransactionBroadcastErrorCode::NodeUnavailable
+        );
+
+        let error: Box<dyn Error + Send + Sync> = Box::new(ClientError::RequestTimeout);
+        assert_eq!(
+            broadcast_error_code(error.as_ref()),
+            TransactionBroadcastErrorCode::NodeUnavailable
+        );
+
+        let error: Box<dyn Error + Send + Sync> = "nonce too low".into();
+        assert_eq!(
+            broadcast_error_code(error.as_ref()),
+            TransactionBroadcastErrorCode::InvalidNonce
+        );
+    }
+}
@@ -1,12 +1,21 @@
-use std::error::Error;
+use std::{error::Error, str::FromStr};
 
-use super::model::{BlockResponse, MessageSend, TransactionResponse};
-use crate::ChainProvider;
+use super::model::{
+    BalanceByDenom, Balances, BlockResponse, BroadcastResponse, Delegations, MessageSend,
+    NodeConfig, TransactionResponse,
+};
+use crate::{
+    broadcast::check_node_status, BalanceProvider, ChainProvider, FeeProvider,
+    TransactionBroadcastProvider,
+};
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
 use hex;
-use primitives::{AssetId, Chain, TransactionState, TransactionType};
+use num_bigint::BigUint;
+use primitives::{
+    AssetBalance, AssetId, Chain, FeePriority, FeeRate, TransactionState, TransactionType,
+};
 use reqwest_middleware::ClientWithMiddleware;
 use sha2::{Digest, Sha256};
 
@@ -228,6 +237,38 @@ impl CosmosClient {
         Ok(transaction)
     }
 
+    pub async fn get_balances(
+        &self,
+        address: &str,
+    ) -> Result<Balances, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/cosmos/bank/v1beta1/balances/{}", self.url, address);
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<Balances>()
+            .await?)
+    }
+
+    pub async fn get_balance_by_denom(
+        &self,
+        address: &str,
+        denom: &str,
+    ) -> Result<BalanceByDenom, Box<dyn Error + Send + Sync>> {
+        let url = format!(
+            "{}/cosmos/bank/v1beta1/balances/{}/by_denom?denom={}",
+            self.url, address, denom
+        );
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<BalanceByDenom>()
+            .await?)
+    }
+
     pub async fn get_block(
         &self,
         block: &str,
@@ -247,6 +288,131 @@ impl CosmosClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for CosmosClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let denom = self.chain.as_denom().unwrap_or_default();
+        let balance = self.get_balance_by_denom(address, denom).await?.balance;
+        Ok(AssetBalance::new(self.chain.as_asset_id(), balance.amount))
+    }
+
+    // bank balances other than the staking denom, ibc tokens use the ibc/<hash> denom as token id
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        _token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let denom = self.chain.as_denom().unwrap_or_default();
+        Ok(self
+            .get_balances(address)
+            .await?
+            .balances
+            .into_iter()
+            .filter(|x| x.denom != denom)
+            .map(|x| AssetBalance::new(AssetId::from(self.chain, Some(x.denom)), x.amount))
+            .collect())
+    }
+
+    async fn get_staking_balance(
+        &self,
+        address: &str,
+    ) -> Result<Option<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let denom = self.chain.as_denom().unwrap_or_default();
+        let url = format!(
+            "{}/cosmos/staking/v1beta1/delegations/{}",
+            self.url, address
+        );
+        let delegations = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<Delegations>()
+            .await?;
+        let balance = delegations
+            .delegation_responses
+            .into_iter()
+            .filter(|x| x.balance.denom == denom)
+            .map(|x| BigUint::from_str(&x.balance.amount).unwrap_or_default())
+            .sum::<BigUint>();
+        Ok(Some(AssetBalance::new(
+            self.chain.as_asset_id(),
+            balance.to_string(),
+        )))
+    }
+}
+
+impl CosmosClient {
+    // used when the node does not enforce a minimum gas price. None for chains that are not
+    // priced by gas, thorchain charges a fixed network fee
+    pub fn default_gas_price(chain: Chain) -> Option<f64> {
+        match chain {
+            Chain::Cosmos => Some(0.005),
+            Chain::Osmosis => Some(0.0025),
+            Chain::Celestia => Some(0.002),
+            Chain::Injective => Some(160_000_000.0),
+            Chain::Sei | Chain::Noble => Some(0.1),
+            _ => None,
+        }
+    }
+
+    fn parse_gas_price(value: &str, denom: &str) -> Option<f64> {
+        value
+            .split(',')
+            .find_map(|x| x.trim().strip_suffix(denom)?.parse::<f64>().ok())
+            .filter(|x| *x > 0.0)
+    }
+}
+
+#[async_trait]
+impl FeeProvider for CosmosClient {
+    async fn get_fee_rates(&self) -> Result<Vec<FeeRate>, Box<dyn Error + Send + Sync>> {
+        let denom = self.chain.as_denom().unwrap_or_default();
+        let url = format!("{}/cosmos/base/node/v1beta1/config", self.url);
+        let config = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<NodeConfig>()
+            .await?;
+        let gas_price = Self::parse_gas_price(&config.minimum_gas_price, denom)
+            .or(Self::default_gas_price(self.chain))
+            .ok_or(format!("no gas price for chain: {}", self.chain.as_ref()))?;
+
+        Ok([
+            (FeePriority::Slow, 1.0),
+            (FeePriority::Normal, 1.5),
+            (FeePriority::Fast, 2.0),
+        ]
+        .into_iter()
+        .map(|(priority, multiplier)| FeeRate::new(priority, (gas_price * multiplier).to_string()))
+        .collect())
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for CosmosClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/cosmos/tx/v1beta1/txs", self.url);
+        let body = serde_json::json!({ "tx_bytes": data, "mode": "BROADCAST_MODE_SYNC" });
+        let response = self.client.post(url).json(&body).send().await?;
+        check_node_status(&response)?;
+        let response = response.json::<BroadcastResponse>().await?;
+        // non zero code means the transaction was rejected by check tx
+        if response.tx_response.code != 0 {
+            return Err(response.tx_response.raw_log.into());
+        }
+        Ok(response.tx_response.txhash)
+    }
+}
+
 #[async_trait]
 impl ChainProvider for CosmosClient {
     fn get_chain(&self) -> Chain {
@@ -287,3 +453,31 @@ impl ChainProvider for CosmosClient {
         Ok(transactions)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gas_price() {
+        assert_eq!(
+            CosmosClient::parse_gas_price("0.005000000000000000uatom", "uatom"),
+            Some(0.005)
+        );
+        assert_eq!(
+            CosmosClient::parse_gas_price("0.1ibc/ABC,0.0025uosmo", "uosmo"),
+            Some(0.0025)
+        );
+        assert_eq!(
+            CosmosClient::parse_gas_price("0.000000000000000000uatom", "uatom"),
+            None
+        );
+        assert_eq!(CosmosClient::parse_gas_price("", "uatom"), None);
+    }
+
+    #[test]
+    fn test_default_gas_price() {
+        assert_eq!(CosmosClient::default_gas_price(Chain::Cosmos), Some(0.005));
+        assert_eq!(CosmosClient::default_gas_price(Chain::Thorchain), None);
+    }
+}
@@ -51,6 +51,44 @@ pub struct TransactionResponseData {
     pub events: Vec<TransactionEvent>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balances {
+    pub balances: Vec<Coin>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceByDenom {
+    pub balance: Coin,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delegations {
+    pub delegation_responses: Vec<Delegation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delegation {
+    pub balance: Coin,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeConfig {
+    // comma separated coins, e.g. 0.005uatom
+    pub minimum_gas_price: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BroadcastResponse {
+    pub tx_response: BroadcastResponseData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BroadcastResponseData {
+    pub code: i64,
+    pub txhash: String,
+    pub raw_log: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionEvent {
     #[serde(rename = "type")]
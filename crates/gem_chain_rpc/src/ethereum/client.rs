@@ -1,10 +1,23 @@
 use std::error::Error;
 
-use super::model::{Block, Transaction, TransactionReciept};
-use crate::ChainProvider;
+use super::model::{Block, CallFrame, FeeHistory, Transaction, TransactionReciept};
+use super::simulation::{
+    checksum_address, collect_effects, is_execution_error, is_unlimited_approval,
+};
+use crate::{
+    fee::{fee_percentile, percentile},
+    BalanceProvider, ChainProvider, FeeProvider, SimulationProvider, TransactionBroadcastProvider,
+    TransactionStateProvider, TransactionStatus,
+};
 use async_trait::async_trait;
 use chrono::Utc;
-use gem_evm::address::EthereumAddress;
+use gem_evm::{
+    address::EthereumAddress,
+    erc20::{decode_approve, decode_balance_of, encode_balance_of},
+    multicall3::{
+        decode_aggregate3, encode_aggregate3, MULTICALL3_ADDRESS, MULTICALL3_ADDRESS_ZKSYNC,
+    },
+};
 use jsonrpsee::{
     core::{client::ClientT, params::BatchRequestBuilder},
     http_client::{HttpClient, HttpClientBuilder},
@@ -13,7 +26,9 @@ use jsonrpsee::{
 use num_bigint::BigUint;
 use num_traits::Num;
 use primitives::{
-    chain::Chain, AssetId, TransactionState, TransactionSwapMetadata, TransactionType,
+    chain::Chain, AssetBalance, AssetId, BigIntHex, FeePriority, FeeRate, SimulationApproval,
+    SimulationBalanceChange, SimulationWarning, SimulationWarningType, TransactionSimulation,
+    TransactionSimulationRequest, TransactionState, TransactionSwapMetadata, TransactionType,
 };
 use serde_json::json;
 
@@ -22,6 +37,8 @@ const FUNCTION_ERC20_APPROVE: &str = "0x095ea7b3";
 const FUNCTION_1INCH_SWAP: &str = "0x12aa3caf";
 const CONTRACT_1INCH: &str = "0x1111111254EEB25477B68fb85Ed929f73A960582";
 
+const FEE_HISTORY_BLOCKS: u64 = 20;
+
 const TOPIC_DEPOSIT: &str = "0xe1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c";
 //const TOPIC_TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
 
@@ -76,6 +93,22 @@ impl EthereumClient {
         Ok(self.client.request("eth_getBlockByNumber", params).await?)
     }
 
+    pub async fn call(
+        &self,
+        to: &str,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let params = vec![
+            json!({
+                "to": to,
+                "data": format!("0x{}", hex::encode(data)),
+            }),
+            json!("latest"),
+        ];
+        let result: String = self.client.request("eth_call", params).await?;
+        Ok(hex::decode(result.trim_start_matches("0x"))?)
+    }
+
     fn map_transaction(
         &self,
         transaction: Transaction,
@@ -86,79 +119,19 @@ impl EthereumClient {
         } else {
             TransactionState::Failed
         };
+        let block = transaction.block_number.as_ref()?.as_i32();
+        let fee = reciept.get_fee().to_string();
+        if let Some(transfer) =
+            self.map_transfer(&transaction, state.clone(), block.to_string(), fee.clone())
+        {
+            return Some(transfer);
+        }
+
         let value = transaction.value.value.to_string();
         let nonce = transaction.nonce.as_i32();
-        let block = transaction.block_number.as_i32();
-        let fee = reciept.get_fee().to_string();
         let from = EthereumAddress::parse(&transaction.from)?.to_checksum();
         let to = EthereumAddress::parse(&transaction.to.unwrap_or_default())?.to_checksum();
-
-        // system transfer
-        if transaction.input == "0x" {
-            let transaction = primitives::Transaction::new(
-                transaction.hash.clone(),
-                self.chain.as_asset_id(),
-                from,
-                to,
-                None,
-                TransactionType::Transfer,
-                state,
-                block.to_string(),
-                nonce.to_string(),
-                fee.to_string(),
-                self.chain.as_asset_id(),
-                value,
-                None,
-                None,
-                Utc::now(),
-            );
-            return Some(transaction);
-        }
-        // ERC20 transfer. Only add confirmed
         let input_prefix = transaction.input.chars().take(10).collect::<String>();
-        if (input_prefix.starts_with(FUNCTION_ERC20_TRANSFER)
-            || input_prefix.starts_with(FUNCTION_ERC20_APPROVE))
-            && state == TransactionState::Confirmed
-        {
-            let transaction_type = match input_prefix.as_str() {
-                FUNCTION_ERC20_TRANSFER => TransactionType::Transfer,
-                FUNCTION_ERC20_APPROVE => TransactionType::TokenApproval,
-                _ => TransactionType::Transfer,
-            };
-            let token_id = to.clone();
-            let asset_id = AssetId {
-                chain: self.chain,
-                token_id: Some(token_id),
-            };
-            let value: String = transaction.input.chars().skip(74).take(64).collect();
-            let to_address: String = transaction
-                .input
-                .chars()
-                .skip(34)
-                .take(40)
-                .collect::<String>();
-            let to_address = EthereumAddress::parse(&to_address)?.to_checksum();
-            let value = BigUint::from_str_radix(value.as_str(), 16).unwrap_or_default();
-
-            let transaction = primitives::Transaction::new(
-                transaction.hash.clone(),
-                asset_id,
-                from,
-                to_address.clone(),
-                None,
-                transaction_type,
-                state,
-                block.to_string(),
-                nonce.to_string(),
-                fee.to_string(),
-                self.chain.as_asset_id(),
-                value.to_string(),
-                None,
-                None,
-                Utc::now(),
-            );
-            return Some(transaction);
-        }
 
         if input_prefix.starts_with(FUNCTION_1INCH_SWAP)
             && to == CONTRACT_1INCH
@@ -229,6 +202,397 @@ impl EthereumClient {
 
         None
     }
+
+    // native and erc20 transfers and approvals, included or still pending.
+    // failed erc20 calls are skipped, the transfer did not happen
+    fn map_transfer(
+        &self,
+        transaction: &Transaction,
+        state: TransactionState,
+        block: String,
+        fee: String,
+    ) -> Option<primitives::Transaction> {
+        let value = transaction.value.value.to_string();
+        let nonce = transaction.nonce.as_i32();
+        let from = EthereumAddress::parse(&transaction.from)?.to_checksum();
+        let to =
+            EthereumAddress::parse(transaction.to.as_deref().unwrap_or_default())?.to_checksum();
+
+        // system transfer
+        if transaction.input == "0x" {
+            let transaction = primitives::Transaction::new(
+                transaction.hash.clone(),
+                self.chain.as_asset_id(),
+                from,
+                to,
+                None,
+                TransactionType::Transfer,
+                state,
+                block,
+                nonce.to_string(),
+                fee,
+                self.chain.as_asset_id(),
+                value,
+                None,
+                None,
+                Utc::now(),
+            );
+            return Some(transaction);
+        }
+        // ERC20 transfer
+        let input_prefix = transaction.input.chars().take(10).collect::<String>();
+        if (input_prefix.starts_with(FUNCTION_ERC20_TRANSFER)
+            || input_prefix.starts_with(FUNCTION_ERC20_APPROVE))
+            && state != TransactionState::Failed
+        {
+            let transaction_type = match input_prefix.as_str() {
+                FUNCTION_ERC20_TRANSFER => TransactionType::Transfer,
+                FUNCTION_ERC20_APPROVE => TransactionType::TokenApproval,
+                _ => TransactionType::Transfer,
+            };
+            let token_id = to.clone();
+            let asset_id = AssetId {
+                chain: self.chain,
+                token_id: Some(token_id),
+            };
+            let value: String = transaction.input.chars().skip(74).take(64).collect();
+            let to_address: String = transaction
+                .input
+                .chars()
+                .skip(34)
+                .take(40)
+                .collect::<String>();
+            let to_address = EthereumAddress::parse(&to_address)?.to_checksum();
+            let value = BigUint::from_str_radix(value.as_str(), 16).unwrap_or_default();
+
+            let transaction = primitives::Transaction::new(
+                transaction.hash.clone(),
+                asset_id,
+                from,
+                to_address.clone(),
+                None,
+                transaction_type,
+                state,
+                block,
+                nonce.to_string(),
+                fee,
+                self.chain.as_asset_id(),
+                value.to_string(),
+                None,
+                None,
+                Utc::now(),
+            );
+            return Some(transaction);
+        }
+        None
+    }
+
+    fn simulation_call(request: &TransactionSimulationRequest) -> serde_json::Value {
+        let value = request
+            .value
+            .as_ref()
+            .and_then(|x| BigUint::from_str_radix(x, 10).ok())
+            .unwrap_or_default();
+        json!({
+            "from": request.from,
+            "to": request.to,
+            "value": format!("0x{:x}", value),
+            "data": request.data.clone().unwrap_or("0x".to_string()),
+        })
+    }
+
+    async fn is_contract(&self, address: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let code: String = self
+            .client
+            .request("eth_getCode", vec![json!(address), json!("latest")])
+            .await?;
+        Ok(!code.trim_start_matches("0x").is_empty())
+    }
+
+    // nodes without the debug namespace only tell whether the call succeeds,
+    // approvals are then decoded from the call data and the result is flagged as partial.
+    // only a call rejected by the evm fails the simulation, node errors are returned
+    async fn simulate_call(
+        &self,
+        request: &TransactionSimulationRequest,
+    ) -> Result<TransactionSimulation, Box<dyn Error + Send + Sync>> {
+        let call = Self::simulation_call(request);
+        match self
+            .client
+            .request::<String, _>("eth_call", vec![call, json!("latest")])
+            .await
+        {
+            Ok(_) => {}
+            Err(err) if is_execution_error(&err) => {
+                return Ok(TransactionSimulation::failed(err.to_string()))
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let mut simulation = TransactionSimulation {
+            success: true,
+            error: None,
+            balance_changes: vec![],
+            approvals: vec![],
+            counterparties: vec![],
+            contract: None,
+            warnings: vec![SimulationWarning {
+                warning_type: SimulationWarningType::SimulationUnavailable,
+                address: None,
+            }],
+        };
+        if let Some(value) = request.value.clone().filter(|x| x != "0") {
+            simulation.balance_changes.push(SimulationBalanceChange {
+                asset_id: self.chain.as_asset_id(),
+                value: format!("-{}", value),
+            });
+        }
+        let input = request.data.clone().unwrap_or_default();
+        if input.starts_with(FUNCTION_ERC20_APPROVE) {
+            let data = hex::decode(input.trim_start_matches("0x")).unwrap_or_default();
+            if let (Some(to), Ok((spender, value))) = (&request.to, decode_approve(&data)) {
+                let amount = BigUint::from_str_radix(&value, 10).unwrap_or_default();
+                simulation.approvals.push(SimulationApproval {
+                    asset_id: AssetId::from(self.chain, Some(checksum_address(to))),
+                    spender: spender.clone(),
+                    value,
+                    is_unlimited: is_unlimited_approval(&amount),
+                });
+                simulation.counterparties.push(spender);
+            }
+        }
+        Ok(simulation)
+    }
+}
+
+#[async_trait]
+impl TransactionStateProvider for EthereumClient {
+    async fn get_transaction_status(
+        &self,
+        transaction: &primitives::Transaction,
+    ) -> Result<TransactionStatus, Box<dyn Error + Send + Sync>> {
+        let hash = json!(transaction.hash);
+        let reciept: Option<TransactionReciept> = self
+            .client
+            .request("eth_getTransactionReceipt", vec![hash.clone()])
+            .await?;
+        if let Some(reciept) = reciept {
+            return Ok(if reciept.status == "0x1" {
+                TransactionStatus::Confirmed
+            } else {
+                TransactionStatus::Failed
+            });
+        }
+
+        let pending: Option<serde_json::Value> = self
+            .client
+            .request("eth_getTransactionByHash", vec![hash])
+            .await?;
+        if pending.is_some() {
+            return Ok(TransactionStatus::Pending);
+        }
+
+        // the nonce was used by a speed up or cancel transaction
+        let nonce: BigIntHex = self
+            .client
+            .request(
+                "eth_getTransactionCount",
+                vec![json!(transaction.from), json!("latest")],
+            )
+            .await?;
+        let sequence = transaction.sequence.parse::<u64>()?;
+        if nonce.value > BigUint::from(sequence) {
+            return Ok(TransactionStatus::Replaced);
+        }
+        Ok(TransactionStatus::Dropped)
+    }
+
+    async fn get_pending_transaction(
+        &self,
+        hash: &str,
+    ) -> Result<Option<primitives::Transaction>, Box<dyn Error + Send + Sync>> {
+        let transaction: Option<Transaction> = self
+            .client
+            .request("eth_getTransactionByHash", vec![json!(hash)])
+            .await?;
+        Ok(transaction.and_then(|transaction| {
+            self.map_transfer(
+                &transaction,
+                TransactionState::Pending,
+                "0".to_string(),
+                "0".to_string(),
+            )
+        }))
+    }
+}
+
+#[async_trait]
+impl BalanceProvider for EthereumClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let balance: BigIntHex = self
+            .client
+            .request("eth_getBalance", vec![json!(address), json!("latest")])
+            .await?;
+        Ok(AssetBalance::new(
+            self.chain.as_asset_id(),
+            balance.value.to_string(),
+        ))
+    }
+
+    // erc20 balances in a single eth_call through multicall3, invalid token ids are skipped
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let token_ids: Vec<String> = token_ids
+            .into_iter()
+            .filter(|x| EthereumAddress::parse(x).is_some())
+            .collect();
+        if token_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let call_data = encode_balance_of(address)?;
+        let calls = token_ids
+            .iter()
+            .map(|x| (x.clone(), call_data.clone()))
+            .collect();
+        let multicall = match self.chain {
+            Chain::ZkSync => MULTICALL3_ADDRESS_ZKSYNC,
+            _ => MULTICALL3_ADDRESS,
+        };
+        let result = self.call(multicall, &encode_aggregate3(calls)?).await?;
+        let results = decode_aggregate3(&result)?;
+
+        Ok(token_ids
+            .into_iter()
+            .zip(results)
+            .filter_map(|(token_id, result)| {
+                let balance = decode_balance_of(&result?).ok()?;
+                Some(AssetBalance::new(
+                    AssetId::from(self.chain, Some(token_id)),
+                    balance,
+                ))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl FeeProvider for EthereumClient {
+    // priority fee is the median over recent blocks of each reward percentile
+    async fn get_fee_rates(&self) -> Result<Vec<FeeRate>, Box<dyn Error + Send + Sync>> {
+        let priorities = [FeePriority::Slow, FeePriority::Normal, FeePriority::Fast];
+        let percentiles = priorities
+            .iter()
+            .map(|x| fee_percentile(*x))
+            .collect::<Vec<_>>();
+        let params = vec![
+            json!(format!("0x{:x}", FEE_HISTORY_BLOCKS)),
+            json!("latest"),
+            json!(percentiles),
+        ];
+        let history: FeeHistory = self.client.request("eth_feeHistory", params).await?;
+        let base_fee = history
+            .base_fee_per_gas
+            .last()
+            .map(|x| x.value.clone())
+            .unwrap_or_default();
+        let rewards = history.reward.unwrap_or_default();
+
+        Ok(priorities
+            .into_iter()
+            .enumerate()
+            .map(|(index, priority)| {
+                let values = rewards
+                    .iter()
+                    .filter_map(|x| x.get(index).map(|x| x.value.clone()))
+                    .collect::<Vec<BigUint>>();
+                let priority_fee = percentile(&values, 50);
+                FeeRate {
+                    priority,
+                    value: (base_fee.clone() + priority_fee.clone()).to_string(),
+                    priority_fee: Some(priority_fee.to_string()),
+                }
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SimulationProvider for EthereumClient {
+    async fn simulate_transaction(
+        &self,
+        request: &TransactionSimulationRequest,
+    ) -> Result<TransactionSimulation, Box<dyn Error + Send + Sync>> {
+        // the sender is funded by a state override, so the outcome does not depend on gas funds
+        let params = vec![
+            Self::simulation_call(request),
+            json!("latest"),
+            json!({
+                "tracer": "callTracer",
+                "tracerConfig": { "withLog": true },
+                "stateOverrides": {
+                    (request.from.clone()): { "balance": format!("0x{:x}", u128::MAX) }
+                }
+            }),
+        ];
+        let trace: Result<CallFrame, _> = self.client.request("debug_traceCall", params).await;
+        let mut simulation = match trace {
+            Ok(frame) => {
+                let effects = collect_effects(self.chain, &request.from, &frame);
+                let error = frame.revert_reason.clone().or(frame.error.clone());
+                TransactionSimulation {
+                    success: error.is_none(),
+                    error,
+                    balance_changes: effects.balance_changes,
+                    approvals: effects.approvals,
+                    counterparties: effects.counterparties,
+                    contract: None,
+                    warnings: vec![],
+                }
+            }
+            Err(err) => {
+                println!(
+                    "debug_traceCall error: {}, chain: {}",
+                    err,
+                    self.chain.as_ref()
+                );
+                self.simulate_call(request).await?
+            }
+        };
+
+        if let Some(to) = &request.to {
+            if self.is_contract(to).await? {
+                simulation.contract = Some(checksum_address(to));
+            } else if !simulation.counterparties.contains(&checksum_address(to)) {
+                simulation.counterparties.push(checksum_address(to));
+            }
+        }
+        Ok(simulation)
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for EthereumClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let data = if data.starts_with("0x") {
+            data.to_string()
+        } else {
+            format!("0x{}", data)
+        };
+        let hash: String = self
+            .client
+            .request("eth_sendRawTransaction", vec![json!(data)])
+            .await?;
+        Ok(hash)
+    }
 }
 
 #[async_trait]
@@ -11,7 +11,8 @@ pub struct Block {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
-    pub block_number: BigIntHex,
+    // null while the transaction is pending
+    pub block_number: Option<BigIntHex>,
     pub from: String,
     // pub gas: String,
     // pub gas_price: String,
@@ -52,3 +53,31 @@ pub struct Log {
     pub topics: Vec<String>,
     pub data: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+    // one more entry than blocks, the last one is the base fee of the next block
+    pub base_fee_per_gas: Vec<BigIntHex>,
+    pub reward: Option<Vec<Vec<BigIntHex>>>,
+}
+
+// debug_traceCall frame of the callTracer with logs enabled
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    pub from: String,
+    pub to: Option<String>,
+    pub value: Option<BigIntHex>,
+    pub error: Option<String>,
+    pub revert_reason: Option<String>,
+    pub calls: Option<Vec<CallFrame>>,
+    pub logs: Option<Vec<CallLog>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CallLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+}
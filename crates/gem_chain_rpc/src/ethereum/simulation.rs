@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use gem_evm::address::EthereumAddress;
+use jsonrpsee::core::ClientError;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Num, Zero};
+use primitives::{AssetId, Chain, SimulationApproval, SimulationBalanceChange};
+
+use super::model::{CallFrame, CallLog};
+
+const TOPIC_TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const TOPIC_APPROVAL: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+const TOPIC_APPROVAL_FOR_ALL: &str =
+    "0x17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31";
+
+// uint96 max and above, wallets and dapps commonly use uint256 or uint160 max
+const UNLIMITED_APPROVAL_BITS: u64 = 96;
+
+#[derive(Debug, Default)]
+pub struct SimulationEffects {
+    pub balance_changes: Vec<SimulationBalanceChange>,
+    pub approvals: Vec<SimulationApproval>,
+    pub counterparties: Vec<String>,
+}
+
+// messages nodes answer eth_call with when the evm rejects the call, not only on revert opcodes
+const EXECUTION_ERRORS: [&str; 4] = [
+    "execution reverted",
+    "out of gas",
+    "insufficient funds",
+    "invalid opcode",
+];
+
+// transport and node errors say nothing about the transaction and are not a failed simulation
+pub fn is_execution_error(error: &ClientError) -> bool {
+    match error {
+        ClientError::Call(error) => {
+            let message = error.message().to_lowercase();
+            error.code() == 3 || EXECUTION_ERRORS.iter().any(|x| message.contains(x))
+        }
+        _ => false,
+    }
+}
+
+pub fn is_unlimited_approval(value: &BigUint) -> bool {
+    value.bits() >= UNLIMITED_APPROVAL_BITS
+}
+
+pub fn checksum_address(address: &str) -> String {
+    EthereumAddress::parse(address)
+        .map(|x| x.to_checksum())
+        .unwrap_or(address.to_string())
+}
+
+fn topic_address(topic: &str) -> String {
+    let value = topic.trim_start_matches("0x");
+    checksum_address(&format!("0x{}", &value[value.len().saturating_sub(40)..]))
+}
+
+fn parse_uint(value: &str) -> BigUint {
+    BigUint::from_str_radix(value.trim_start_matches("0x"), 16).unwrap_or_default()
+}
+
+fn is_same_address(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+// balance changes and approvals of the owner, reverted frames are skipped
+pub fn collect_effects(chain: Chain, owner: &str, frame: &CallFrame) -> SimulationEffects {
+    let mut balances: HashMap<Option<String>, BigInt> = HashMap::new();
+    let mut effects = SimulationEffects::default();
+    collect_frame(chain, owner, frame, &mut balances, &mut effects);
+
+    let mut balances = balances
+        .into_iter()
+        .filter(|(_, value)| !value.is_zero())
+        .collect::<Vec<_>>();
+    balances.sort_by(|a, b| a.0.cmp(&b.0));
+    effects.balance_changes = balances
+        .into_iter()
+        .map(|(token_id, value)| SimulationBalanceChange {
+            asset_id: AssetId::from(chain, token_id),
+            value: value.to_string(),
+        })
+        .collect();
+    effects
+}
+
+fn collect_frame(
+    chain: Chain,
+    owner: &str,
+    frame: &CallFrame,
+    balances: &mut HashMap<Option<String>, BigInt>,
+    effects: &mut SimulationEffects,
+) {
+    if frame.error.is_some() {
+        return;
+    }
+    let value = BigInt::from(frame.value.clone().map(|x| x.value).unwrap_or_default());
+    if !value.is_zero() {
+        let to = frame.to.clone().unwrap_or_default();
+        if is_same_address(&frame.from, owner) {
+            *balances.entry(None).or_default() -= value.clone();
+            add_counterparty(effects, &to);
+        }
+        if is_same_address(&to, owner) {
+            *balances.entry(None).or_default() += value;
+        }
+    }
+    for log in frame.logs.clone().unwrap_or_default() {
+        collect_log(chain, owner, &log, balances, effects);
+    }
+    for call in frame.calls.clone().unwrap_or_default() {
+        collect_frame(chain, owner, &call, balances, effects);
+    }
+}
+
+fn collect_log(
+    chain: Chain,
+    owner: &str,
+    log: &CallLog,
+    balances: &mut HashMap<Option<String>, BigInt>,
+    effects: &mut SimulationEffects,
+) {
+    let Some(topic) = log.topics.first() else {
+        return;
+    };
+    let token_id = checksum_address(&log.address);
+    match (topic.as_str(), log.topics.len()) {
+        // erc20 has the amount in data, erc721 the token id in the last topic
+        (TOPIC_TRANSFER, 3) | (TOPIC_TRANSFER, 4) => {
+            let from = topic_address(&log.topics[1]);
+            let to = topic_address(&log.topics[2]);
+            let value = if log.topics.len() == 4 {
+                BigInt::from(1)
+            } else {
+                BigInt::from(parse_uint(&log.data))
+            };
+            if is_same_address(&from, owner) {
+                *balances.entry(Some(token_id.clone())).or_default() -= value.clone();
+                add_counterparty(effects, &to);
+            }
+            if is_same_address(&to, owner) {
+                *balances.entry(Some(token_id)).or_default() += value;
+            }
+        }
+        (TOPIC_APPROVAL, 3) | (TOPIC_APPROVAL, 4) => {
+            if !is_same_address(&topic_address(&log.topics[1]), owner) {
+                return;
+            }
+            let spender = topic_address(&log.topics[2]);
+            let value = if log.topics.len() == 4 {
+                parse_uint(&log.topics[3])
+            } else {
+                parse_uint(&log.data)
+            };
+            // approvals reset to zero revoke access
+            if value.is_zero() && log.topics.len() == 3 {
+                return;
+            }
+            effects.approvals.push(SimulationApproval {
+                asset_id: AssetId::from(chain, Some(token_id)),
+                spender: spender.clone(),
+                is_unlimited: log.topics.len() == 3 && is_unlimited_approval(&value),
+                value: value.to_string(),
+            });
+            add_counterparty(effects, &spender);
+        }
+        (TOPIC_APPROVAL_FOR_ALL, 3) => {
+            if !is_same_address(&topic_address(&log.topics[1]), owner)
+                || parse_uint(&log.data).is_zero()
+            {
+                return;
+            }
+            let operator = topic_address(&log.topics[2]);
+            effects.approvals.push(SimulationApproval {
+                asset_id: AssetId::from(chain, Some(token_id)),
+                spender: operator.clone(),
+                value: "".to_string(),
+                is_unlimited: true,
+            });
+            add_counterparty(effects, &operator);
+        }
+        _ => {}
+    }
+}
+
+fn add_counterparty(effects: &mut SimulationEffects, address: &str) {
+    let address = checksum_address(address);
+    if !address.is_empty() && !effects.counterparties.contains(&address) {
+        effects.counterparties.push(address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "0x514BCb1F9AAbb904e6106Bd1052B66d2706dBbb7";
+    const SPENDER: &str = "0x1111111254EEB25477B68fb85Ed929f73A960582";
+    const TOKEN: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    fn topic(address: &str) -> String {
+        format!(
+            "0x000000000000000000000000{}",
+            address.trim_start_matches("0x").to_lowercase()
+        )
+    }
+
+    #[test]
+    fn test_is_execution_error() {
+        use jsonrpsee::types::ErrorObject;
+
+        let call_error = |code: i32, message: &str| {
+            ClientError::Call(ErrorObject::owned(code, message.to_string(), None::<()>))
+        };
+
+        assert!(is_execution_error(&call_error(
+            3,
+            "execution reverted: STF"
+        )));
+        assert!(is_execution_error(&call_error(
+            -32000,
+            "insufficient funds for gas * price + value"
+        )));
+        assert!(!is_execution_error(&call_error(-32005, "rate limited")));
+        assert!(!is_execution_error(&ClientError::RequestTimeout));
+    }
+
+    #[test]
+    fn test_collect_effects() {
+        let frame = CallFrame {
+            from: OWNER.to_lowercase(),
+            to: Some(TOKEN.to_lowercase()),
+            value: None,
+            error: None,
+            revert_reason: None,
+            calls: None,
+            logs: Some(vec![
+                CallLog {
+                    address: TOKEN.to_lowercase(),
+                    topics: vec![TOPIC_TRANSFER.to_string(), topic(OWNER), topic(SPENDER)],
+                    data: "0x00000000000000000000000000000000000000000000000000000000000003e8"
+                        .to_string(),
+                },
+                CallLog {
+                    address: TOKEN.to_lowercase(),
+                    topics: vec![TOPIC_APPROVAL.to_string(), topic(OWNER), topic(SPENDER)],
+                    data: "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+                        .to_string(),
+                },
+            ]),
+        };
+
+        let effects = collect_effects(Chain::Ethereum, OWNER, &frame);
+
+        assert_eq!(effects.balance_changes.len(), 1);
+        assert_eq!(
+            effects.balance_changes[0].asset_id.token_id,
+            Some(TOKEN.to_string())
+        );
+        assert_eq!(effects.balance_changes[0].value, "-1000");
+        assert_eq!(effects.approvals.len(), 1);
+        assert_eq!(effects.approvals[0].spender, SPENDER);
+        assert!(effects.approvals[0].is_unlimited);
+        assert_eq!(effects.counterparties, vec![SPENDER.to_string()]);
+    }
+
+    #[test]
+    fn test_collect_effects_reverted() {
+        let frame = CallFrame {
+            from: OWNER.to_string(),
+            to: Some(SPENDER.to_string()),
+            value: None,
+            error: Some("execution reverted".to_string()),
+            revert_reason: None,
+            calls: None,
+            logs: None,
+        };
+
+        let effects = collect_effects(Chain::Ethereum, OWNER, &frame);
+
+        assert!(effects.balance_changes.is_empty());
+        assert!(effects.approvals.is_empty());
+    }
+}
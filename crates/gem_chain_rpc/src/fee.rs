@@ -0,0 +1,36 @@
+use primitives::FeePriority;
+
+// percentiles requested from nodes for each priority
+pub fn fee_percentile(priority: FeePriority) -> usize {
+    match priority {
+        FeePriority::Slow => 25,
+        FeePriority::Normal => 50,
+        FeePriority::Fast => 75,
+    }
+}
+
+// nearest rank percentile of unsorted values
+pub fn percentile<T: Ord + Clone + Default>(values: &[T], percentile: usize) -> T {
+    if values.is_empty() {
+        return T::default();
+    }
+    let mut values = values.to_vec();
+    values.sort();
+    let index = (values.len() * percentile).div_ceil(100).max(1) - 1;
+    values[index.min(values.len() - 1)].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<u64> = vec![5, 1, 4, 2, 3];
+        assert_eq!(percentile(&values, 25), 2);
+        assert_eq!(percentile(&values, 50), 3);
+        assert_eq!(percentile(&values, 75), 4);
+        assert_eq!(percentile(&values, 100), 5);
+        assert_eq!(percentile::<u64>(&[], 50), 0);
+    }
+}
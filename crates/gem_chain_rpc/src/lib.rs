@@ -2,8 +2,10 @@
 
 pub mod aptos;
 pub mod bitcoin;
+pub mod broadcast;
 pub mod cosmos;
 pub mod ethereum;
+pub mod fee;
 pub mod near;
 pub mod solana;
 pub mod sui;
@@ -23,7 +25,10 @@ pub use self::tron::client::TronClient;
 pub use self::xrp::client::XRPClient;
 
 use async_trait::async_trait;
-use primitives::{chain::Chain, Transaction};
+use primitives::{
+    chain::Chain, AssetBalance, FeeRate, Transaction, TransactionSimulation,
+    TransactionSimulationRequest,
+};
 
 use std::sync::Arc;
 
@@ -37,6 +42,83 @@ pub trait ChainProvider: Send + Sync {
     ) -> Result<Vec<Transaction>, Box<dyn std::error::Error + Send + Sync>>;
 }
 
+// state of a broadcasted transaction as seen by the node
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionStatus {
+    Pending,
+    Confirmed,
+    Failed,
+    // another transaction with the same nonce was included
+    Replaced,
+    // unknown to the node, either not propagated yet or evicted
+    Dropped,
+}
+
+#[async_trait]
+pub trait TransactionStateProvider: Send + Sync {
+    async fn get_transaction_status(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<TransactionStatus, Box<dyn std::error::Error + Send + Sync>>;
+
+    // the transaction as the node sees it right after broadcast,
+    // None when the node does not know it or it is not a supported transfer
+    async fn get_pending_transaction(
+        &self,
+        hash: &str,
+    ) -> Result<Option<Transaction>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[async_trait]
+pub trait TransactionBroadcastProvider: Send + Sync {
+    // submits a signed payload and returns the transaction hash
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[async_trait]
+pub trait BalanceProvider: Send + Sync {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn std::error::Error + Send + Sync>>;
+
+    // balances of the given tokens, chains whose nodes can list the tokens of an address return
+    // all of them instead. tokens without a balance or that fail to load are omitted
+    async fn get_tokens_balance(
+        &self,
+        _address: &str,
+        _token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(vec![])
+    }
+
+    async fn get_staking_balance(
+        &self,
+        _address: &str,
+    ) -> Result<Option<AssetBalance>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(None)
+    }
+}
+
+#[async_trait]
+pub trait FeeProvider: Send + Sync {
+    // slow, normal and fast rates, see FeeRate for the unit of each chain
+    async fn get_fee_rates(&self)
+        -> Result<Vec<FeeRate>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[async_trait]
+pub trait SimulationProvider: Send + Sync {
+    // effects of an unsigned transaction for the sender, warnings are left to the caller
+    async fn simulate_transaction(
+        &self,
+        request: &TransactionSimulationRequest,
+    ) -> Result<TransactionSimulation, Box<dyn std::error::Error + Send + Sync>>;
+}
+
 #[async_trait]
 impl<T: Send + Sync> ChainProvider for Arc<T>
 where
@@ -5,12 +5,13 @@ use chrono::Utc;
 use jsonrpsee::{
     core::{client::ClientT, params::ObjectParams},
     http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
 };
-use primitives::{Chain, Transaction, TransactionState, TransactionType};
+use primitives::{AssetBalance, Chain, Transaction, TransactionState, TransactionType};
 
-use crate::ChainProvider;
+use crate::{BalanceProvider, ChainProvider, TransactionBroadcastProvider};
 
-use super::model::{Action, Block, BlockHeader, Chunk};
+use super::model::{Account, Action, Block, BlockHeader, Chunk};
 
 pub struct NearClient {
     client: HttpClient,
@@ -87,6 +88,35 @@ impl NearClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for NearClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let mut params = ObjectParams::new();
+        params.insert("request_type", "view_account").unwrap();
+        params.insert("finality", "final").unwrap();
+        params.insert("account_id", address).unwrap();
+        let account: Account = self.client.request("query", params).await?;
+        Ok(AssetBalance::new(Chain::Near.as_asset_id(), account.amount))
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for NearClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let hash: String = self
+            .client
+            .request("broadcast_tx_async", rpc_params![data])
+            .await?;
+        Ok(hash)
+    }
+}
+
 #[async_trait]
 impl ChainProvider for NearClient {
     fn get_chain(&self) -> Chain {
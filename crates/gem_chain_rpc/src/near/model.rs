@@ -46,3 +46,8 @@ pub struct AccessKey {
     pub nonce: i64,
     pub permission: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Account {
+    pub amount: String,
+}
@@ -1,8 +1,12 @@
-use std::error::Error;
+use std::{collections::HashMap, error::Error, str::FromStr};
 
 use crate::{
-    solana::model::{BlockTransactions, InstructionParsed},
-    ChainProvider,
+    fee::{fee_percentile, percentile},
+    solana::model::{
+        Account, BlockTransactions, InstructionParsed, KeyedAccount, PrioritizationFee,
+        SimulateTransaction, TokenAccountInfo, ValueResult,
+    },
+    BalanceProvider, ChainProvider, FeeProvider, SimulationProvider, TransactionBroadcastProvider,
 };
 use async_trait::async_trait;
 use chrono::Utc;
@@ -11,8 +15,11 @@ use jsonrpsee::{
     http_client::{HttpClient, HttpClientBuilder},
     rpc_params,
 };
+use num_bigint::{BigInt, BigUint};
 use primitives::{
-    chain::Chain, AssetId, Transaction, TransactionState, TransactionSwapMetadata, TransactionType,
+    chain::Chain, AssetBalance, AssetId, FeePriority, FeeRate, SimulationApproval,
+    SimulationBalanceChange, Transaction, TransactionSimulation, TransactionSimulationRequest,
+    TransactionState, TransactionSwapMetadata, TransactionType,
 };
 
 use super::model::BlockTransaction;
@@ -29,6 +36,10 @@ const MISSING_OR_SKIPPED_SLOT_ERROR: i32 = -32009;
 const NOT_AVAILABLE_SLOT_ERROR: i32 = -32004;
 const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
 const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const STAKE_PROGRAM_ID: &str = "Stake11111111111111111111111111111111111111";
+// offset of the staker authority in a stake account
+const STAKE_AUTHORITY_OFFSET: usize = 12;
 const JUPITER_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
 
 impl SolanaClient {
@@ -237,6 +248,240 @@ impl SolanaClient {
             token_id: Some(program_id),
         }
     }
+
+    async fn get_token_accounts(
+        &self,
+        address: &str,
+    ) -> Result<Vec<KeyedAccount<TokenAccountInfo>>, Box<dyn Error + Send + Sync>> {
+        let mut result = vec![];
+        for program_id in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+            let params = vec![
+                json!(address),
+                json!({ "programId": program_id }),
+                json!({ "encoding": "jsonParsed" }),
+            ];
+            let accounts: ValueResult<Vec<KeyedAccount<TokenAccountInfo>>> = self
+                .client
+                .request("getTokenAccountsByOwner", params)
+                .await?;
+            result.extend(accounts.value);
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl BalanceProvider for SolanaClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let balance: ValueResult<u64> = self
+            .client
+            .request("getBalance", rpc_params![address])
+            .await?;
+        Ok(AssetBalance::new(
+            Chain::Solana.as_asset_id(),
+            balance.value.to_string(),
+        ))
+    }
+
+    // token accounts of both token programs, balances of the same mint are summed
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        _token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let mut balances: HashMap<String, BigUint> = HashMap::new();
+        for account in self.get_token_accounts(address).await? {
+            let info = account.account.data.parsed.info;
+            *balances.entry(info.mint).or_default() += info.token_amount.amount.value;
+        }
+        Ok(balances
+            .into_iter()
+            .map(|(mint, balance)| {
+                AssetBalance::new(
+                    AssetId::from(Chain::Solana, Some(mint)),
+                    balance.to_string(),
+                )
+            })
+            .collect())
+    }
+
+    async fn get_staking_balance(
+        &self,
+        address: &str,
+    ) -> Result<Option<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let params = vec![
+            json!(STAKE_PROGRAM_ID),
+            json!({
+                "encoding": "jsonParsed",
+                "filters": [{ "memcmp": { "offset": STAKE_AUTHORITY_OFFSET, "bytes": address } }]
+            }),
+        ];
+        let accounts: Vec<KeyedAccount<serde_json::Value>> =
+            self.client.request("getProgramAccounts", params).await?;
+        let balance: u64 = accounts.iter().map(|x| x.account.lamports).sum();
+        Ok(Some(AssetBalance::new(
+            Chain::Solana.as_asset_id(),
+            balance.to_string(),
+        )))
+    }
+}
+
+#[async_trait]
+impl FeeProvider for SolanaClient {
+    // priority fees paid in recent slots, in micro lamports per compute unit
+    async fn get_fee_rates(&self) -> Result<Vec<FeeRate>, Box<dyn Error + Send + Sync>> {
+        let fees: Vec<PrioritizationFee> = self
+            .client
+            .request("getRecentPrioritizationFees", rpc_params![])
+            .await?;
+        let values = fees
+            .iter()
+            .map(|x| x.prioritization_fee)
+            .collect::<Vec<_>>();
+        Ok([FeePriority::Slow, FeePriority::Normal, FeePriority::Fast]
+            .into_iter()
+            .map(|priority| {
+                let value = percentile(&values, fee_percentile(priority));
+                FeeRate::new(priority, value.to_string())
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SimulationProvider for SolanaClient {
+    // sender's lamports and existing token accounts are compared before and after the simulation,
+    // the lamports difference includes the network fee. token accounts created by the transaction are not covered
+    async fn simulate_transaction(
+        &self,
+        request: &TransactionSimulationRequest,
+    ) -> Result<TransactionSimulation, Box<dyn Error + Send + Sync>> {
+        let data = request.data.clone().ok_or("missing transaction data")?;
+        let balance = self.get_balance(&request.from).await?;
+        let token_accounts = self.get_token_accounts(&request.from).await?;
+
+        let addresses = [request.from.clone()]
+            .into_iter()
+            .chain(token_accounts.iter().map(|x| x.pubkey.clone()))
+            .collect::<Vec<_>>();
+        let params = vec![
+            json!(data),
+            json!({
+                "encoding": "base64",
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+                "accounts": { "encoding": "jsonParsed", "addresses": addresses }
+            }),
+        ];
+        let result: ValueResult<SimulateTransaction> =
+            self.client.request("simulateTransaction", params).await?;
+        let result = result.value;
+        if let Some(err) = result.err {
+            return Ok(TransactionSimulation::failed(err.to_string()));
+        }
+        let accounts = result.accounts.unwrap_or_default();
+
+        let mut simulation = TransactionSimulation {
+            success: true,
+            error: None,
+            balance_changes: vec![],
+            approvals: vec![],
+            counterparties: vec![],
+            contract: None,
+            warnings: vec![],
+        };
+
+        let lamports = accounts
+            .first()
+            .cloned()
+            .flatten()
+            .and_then(|x| x["lamports"].as_u64());
+        if let Some(lamports) = lamports {
+            let value = BigInt::from(lamports) - BigInt::from_str(&balance.balance)?;
+            if value != BigInt::from(0) {
+                simulation.balance_changes.push(SimulationBalanceChange {
+                    asset_id: Chain::Solana.as_asset_id(),
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        let mut changes: HashMap<String, BigInt> = HashMap::new();
+        for (pre, post) in token_accounts.iter().zip(accounts.into_iter().skip(1)) {
+            let pre = &pre.account.data.parsed.info;
+            let post = post
+                .and_then(|x| serde_json::from_value::<Account<TokenAccountInfo>>(x).ok())
+                .map(|x| x.data.parsed.info);
+            let post_amount = post
+                .as_ref()
+                .map(|x| x.token_amount.amount.value.clone())
+                .unwrap_or_default();
+            *changes.entry(pre.mint.clone()).or_default() +=
+                BigInt::from(post_amount) - BigInt::from(pre.token_amount.amount.value.clone());
+
+            let Some(post) = post else { continue };
+            let Some(delegate) = post.delegate.clone() else {
+                continue;
+            };
+            let delegated_amount = post
+                .delegated_amount
+                .map(|x| x.amount.value)
+                .unwrap_or_default();
+            let previous_amount = pre
+                .delegated_amount
+                .as_ref()
+                .map(|x| x.amount.value.clone())
+                .unwrap_or_default();
+            if pre.delegate.as_ref() == Some(&delegate) && delegated_amount <= previous_amount {
+                continue;
+            }
+            simulation.approvals.push(SimulationApproval {
+                asset_id: AssetId::from(Chain::Solana, Some(post.mint.clone())),
+                spender: delegate.clone(),
+                value: delegated_amount.to_string(),
+                is_unlimited: delegated_amount == BigUint::from(u64::MAX),
+            });
+            if !simulation.counterparties.contains(&delegate) {
+                simulation.counterparties.push(delegate);
+            }
+        }
+
+        let mut changes = changes
+            .into_iter()
+            .filter(|(_, value)| *value != BigInt::from(0))
+            .collect::<Vec<_>>();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        simulation
+            .balance_changes
+            .extend(
+                changes
+                    .into_iter()
+                    .map(|(mint, value)| SimulationBalanceChange {
+                        asset_id: AssetId::from(Chain::Solana, Some(mint)),
+                        value: value.to_string(),
+                    }),
+            );
+
+        Ok(simulation)
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for SolanaClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let params = vec![
+            json!(data),
+            json!({ "encoding": "base64", "preflightCommitment": "confirmed" }),
+        ];
+        let signature: String = self.client.request("sendTransaction", params).await?;
+        Ok(signature)
+    }
 }
 
 #[async_trait]
@@ -133,3 +133,55 @@ impl TokenBalance {
 pub struct TokenAmount {
     pub amount: BigIntValue,
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ValueResult<T> {
+    pub value: T,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct KeyedAccount<T> {
+    pub pubkey: String,
+    pub account: Account<T>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Account<T> {
+    pub lamports: u64,
+    pub data: AccountData<T>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountData<T> {
+    pub parsed: AccountParsed<T>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountParsed<T> {
+    pub info: T,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccountInfo {
+    pub mint: String,
+    pub token_amount: TokenAmount,
+    pub delegate: Option<String>,
+    pub delegated_amount: Option<TokenAmount>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateTransaction {
+    pub err: Option<serde_json::Value>,
+    pub logs: Option<Vec<String>>,
+    // raw accounts, system accounts are not returned in parsed form
+    pub accounts: Option<Vec<Option<serde_json::Value>>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritizationFee {
+    pub slot: u64,
+    pub prioritization_fee: u64,
+}
@@ -1,6 +1,6 @@
 use std::{error::Error, str::FromStr};
 
-use crate::{sui::model::Digests, ChainProvider};
+use crate::{sui::model::Digests, BalanceProvider, ChainProvider, TransactionBroadcastProvider};
 use async_trait::async_trait;
 use chrono::Utc;
 use jsonrpsee::{
@@ -9,10 +9,14 @@ use jsonrpsee::{
     rpc_params,
 };
 use num_bigint::BigUint;
-use primitives::{chain::Chain, Transaction, TransactionState, TransactionType};
+use primitives::{
+    chain::Chain, AssetBalance, AssetId, Transaction, TransactionState, TransactionType,
+};
 use serde_json::json;
 
-use super::model::{EventStake, EventUnstake, GasUsed};
+use super::model::{
+    CoinBalance, DelegatedStake, EventStake, EventUnstake, ExecuteTransaction, GasUsed,
+};
 
 const SUI_STAKE_EVENT: &str = "0x3::validator::StakingRequestEvent";
 const SUI_UNSTAKE_EVENT: &str = "0x3::validator::UnstakingRequestEvent";
@@ -151,6 +155,88 @@ impl SuiClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for SuiClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let balance: CoinBalance = self
+            .client
+            .request("suix_getBalance", rpc_params![address])
+            .await?;
+        Ok(AssetBalance::new(
+            Chain::Sui.as_asset_id(),
+            balance.total_balance,
+        ))
+    }
+
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        _token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let denom = Chain::Sui.as_denom().unwrap_or_default();
+        let balances: Vec<CoinBalance> = self
+            .client
+            .request("suix_getAllBalances", rpc_params![address])
+            .await?;
+        Ok(balances
+            .into_iter()
+            .filter(|x| x.coin_type != denom)
+            .map(|x| {
+                AssetBalance::new(
+                    AssetId::from(Chain::Sui, Some(x.coin_type)),
+                    x.total_balance,
+                )
+            })
+            .collect())
+    }
+
+    async fn get_staking_balance(
+        &self,
+        address: &str,
+    ) -> Result<Option<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let delegations: Vec<DelegatedStake> = self
+            .client
+            .request("suix_getStakes", rpc_params![address])
+            .await?;
+        let balance = delegations
+            .iter()
+            .flat_map(|x| x.stakes.iter())
+            .map(|x| BigUint::from_str(&x.principal).unwrap_or_default())
+            .sum::<BigUint>();
+        Ok(Some(AssetBalance::new(
+            Chain::Sui.as_asset_id(),
+            balance.to_string(),
+        )))
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for SuiClient {
+    // data is the base64 transaction bytes and signature joined by `_`
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let (tx_bytes, signature) = data
+            .split_once('_')
+            .ok_or("expected transaction bytes and signature")?;
+        let params = vec![
+            json!(tx_bytes),
+            json!([signature]),
+            json!(null),
+            json!("WaitForLocalExecution"),
+        ];
+        let response: ExecuteTransaction = self
+            .client
+            .request("sui_executeTransactionBlock", params)
+            .await?;
+        Ok(response.digest)
+    }
+}
+
 #[async_trait]
 impl ChainProvider for SuiClient {
     fn get_chain(&self) -> Chain {
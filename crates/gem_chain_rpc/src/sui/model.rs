@@ -79,3 +79,25 @@ pub struct EventUnstake {
     pub staker_address: String,
     pub validator_address: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinBalance {
+    pub coin_type: String,
+    pub total_balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegatedStake {
+    pub stakes: Vec<Stake>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stake {
+    pub principal: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecuteTransaction {
+    pub digest: String,
+}
@@ -1,14 +1,18 @@
 use std::error::Error;
 
-use crate::ChainProvider;
+use crate::{
+    broadcast::check_node_status, BalanceProvider, ChainProvider, TransactionBroadcastProvider,
+};
 use async_trait::async_trait;
 use chrono::Utc;
-use gem_ton::address::TonAddress;
-use primitives::{chain::Chain, TransactionState, TransactionType};
+use gem_ton::{address::TonAddress, cell::BagOfCells};
+use primitives::{chain::Chain, AssetBalance, AssetId, TransactionState, TransactionType};
 
 use reqwest_middleware::ClientWithMiddleware;
 
-use super::model::{Blocks, Chainhead, Shards, Transaction, Transactions};
+use super::model::{
+    Account, Blocks, Chainhead, JettonBalances, MessageError, Shards, Transaction, Transactions,
+};
 
 pub struct TonClient {
     url: String,
@@ -131,6 +135,71 @@ impl TonClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for TonClient {
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/v2/accounts/{}", self.url, address);
+        let account = self.client.get(url).send().await?.json::<Account>().await?;
+        Ok(AssetBalance::new(
+            Chain::Ton.as_asset_id(),
+            account.balance.to_string(),
+        ))
+    }
+
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        _token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/v2/accounts/{}/jettons", self.url, address);
+        let jettons = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<JettonBalances>()
+            .await?;
+        Ok(jettons
+            .balances
+            .into_iter()
+            .filter_map(|x| {
+                let token_id = Self::parse_address(&x.jetton.address)?;
+                Some(AssetBalance::new(
+                    AssetId::from(Chain::Ton, Some(token_id)),
+                    x.balance,
+                ))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for TonClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        // tonapi does not return the hash, derive it from the external message cell
+        let hash = hex::encode(BagOfCells::parse_base64(data)?.single_root()?.cell_hash()?);
+        let url = format!("{}/v2/blockchain/message", self.url);
+        let response = self
+            .client
+            .post(url)
+            .json(&serde_json::json!({ "boc": data }))
+            .send()
+            .await?;
+        check_node_status(&response)?;
+        if !response.status().is_success() {
+            let error = response.json::<MessageError>().await?;
+            return Err(error.error.into());
+        }
+        Ok(hash)
+    }
+}
+
 #[async_trait]
 impl ChainProvider for TonClient {
     fn get_chain(&self) -> Chain {
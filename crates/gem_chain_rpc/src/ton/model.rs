@@ -52,3 +52,24 @@ pub struct OutMessage {
 pub struct Address {
     pub address: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Account {
+    pub balance: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JettonBalances {
+    pub balances: Vec<JettonBalance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JettonBalance {
+    pub balance: String,
+    pub jetton: Address,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MessageError {
+    pub error: String,
+}
@@ -7,7 +7,6 @@ impl TronAddress {
         Some(encoded)
     }
 
-    #[allow(dead_code)]
     pub fn from_base58(address: &str) -> Option<String> {
         let decoded = bs58::decode(address).with_check(None).into_vec().ok()?;
         let hex = hex::encode(decoded);
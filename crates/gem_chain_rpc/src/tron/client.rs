@@ -1,15 +1,20 @@
 use std::error::Error;
 
-use crate::ChainProvider;
+use crate::{
+    broadcast::check_node_status, BalanceProvider, ChainProvider, TransactionBroadcastProvider,
+};
 use async_trait::async_trait;
 use chrono::Utc;
 use num_bigint::BigUint;
 use num_traits::Num;
-use primitives::{chain::Chain, AssetId, TransactionState, TransactionType};
+use primitives::{chain::Chain, AssetBalance, AssetId, TransactionState, TransactionType};
 
 use super::{
     address::TronAddress,
-    model::{Block, BlockTransactions, BlockTransactionsInfo, Transaction, TransactionReceiptData},
+    model::{
+        Account, Block, BlockTransactions, BlockTransactionsInfo, BroadcastResponse, Transaction,
+        TransactionReceiptData, TriggerConstantContract,
+    },
 };
 use reqwest_middleware::ClientWithMiddleware;
 
@@ -32,6 +37,50 @@ impl TronClient {
         Ok(response)
     }
 
+    pub async fn get_account(
+        &self,
+        address: &str,
+    ) -> Result<Account, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/wallet/getaccount", self.url);
+        let body = serde_json::json!({ "address": address, "visible": true });
+        let response = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await?
+            .json::<Account>()
+            .await?;
+        Ok(response)
+    }
+
+    pub async fn get_token_balance(
+        &self,
+        address: &str,
+        token_id: &str,
+    ) -> Result<BigUint, Box<dyn Error + Send + Sync>> {
+        // abi encoded address without the 0x41 prefix
+        let owner = TronAddress::from_base58(address).ok_or("invalid address")?;
+        let url = format!("{}/wallet/triggerconstantcontract", self.url);
+        let body = serde_json::json!({
+            "owner_address": address,
+            "contract_address": token_id,
+            "function_selector": "balanceOf(address)",
+            "parameter": format!("{:0>64}", &owner[2..]),
+            "visible": true
+        });
+        let response = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await?
+            .json::<TriggerConstantContract>()
+            .await?;
+        let result = response.constant_result.first().ok_or("empty result")?;
+        Ok(BigUint::from_str_radix(result, 16)?)
+    }
+
     pub async fn get_block_tranactions(
         &self,
         block: i64,
@@ -195,6 +244,106 @@ impl TronClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for TronClient {
+    // not activated accounts are returned without fields
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let account = self.get_account(address).await?;
+        Ok(AssetBalance::new(
+            Chain::Tron.as_asset_id(),
+            account.balance.unwrap_or_default().to_string(),
+        ))
+    }
+
+    // trc20 balances the node lists for the account, requested tokens it does not list are read
+    // from their contracts. tokens that fail to load are skipped
+    async fn get_tokens_balance(
+        &self,
+        address: &str,
+        token_ids: Vec<String>,
+    ) -> Result<Vec<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let account = self.get_account(address).await?;
+        let mut balances: Vec<AssetBalance> = account
+            .trc20
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(|(token_id, balance)| {
+                AssetBalance::new(AssetId::from(Chain::Tron, Some(token_id)), balance)
+            })
+            .collect();
+
+        let token_ids: Vec<String> = token_ids
+            .into_iter()
+            .filter(|x| {
+                !balances
+                    .iter()
+                    .any(|balance| balance.asset_id.token_id.as_ref() == Some(x))
+            })
+            .collect();
+        let futures = token_ids.iter().map(|x| self.get_token_balance(address, x));
+        let results = futures::future::join_all(futures).await;
+        balances.extend(
+            token_ids
+                .into_iter()
+                .zip(results)
+                .filter_map(|(token_id, balance)| {
+                    Some(AssetBalance::new(
+                        AssetId::from(Chain::Tron, Some(token_id)),
+                        balance.ok()?.to_string(),
+                    ))
+                }),
+        );
+        Ok(balances)
+    }
+
+    // stake 2.0 frozen balances for bandwidth and energy
+    async fn get_staking_balance(
+        &self,
+        address: &str,
+    ) -> Result<Option<AssetBalance>, Box<dyn Error + Send + Sync>> {
+        let account = self.get_account(address).await?;
+        let balance: i64 = account
+            .frozen_v2
+            .unwrap_or_default()
+            .iter()
+            .map(|x| x.amount.unwrap_or_default())
+            .sum();
+        Ok(Some(AssetBalance::new(
+            Chain::Tron.as_asset_id(),
+            balance.to_string(),
+        )))
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for TronClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/wallet/broadcasttransaction", self.url);
+        let transaction: serde_json::Value = serde_json::from_str(data)?;
+        let response = self.client.post(url).json(&transaction).send().await?;
+        check_node_status(&response)?;
+        let response = response.json::<BroadcastResponse>().await?;
+        if response.result == Some(true) {
+            if let Some(txid) = response.txid {
+                return Ok(txid);
+            }
+        }
+        let message = response
+            .message
+            .and_then(|x| hex::decode(x).ok())
+            .map(|x| String::from_utf8_lossy(&x).to_string())
+            .unwrap_or_default();
+        Err(format!("{} {}", response.code.unwrap_or_default(), message).into())
+    }
+}
+
 #[async_trait]
 impl ChainProvider for TronClient {
     fn get_chain(&self) -> Chain {
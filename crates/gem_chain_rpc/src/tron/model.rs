@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -83,3 +85,31 @@ pub struct TronLog {
     pub topics: Option<Vec<String>>,
     pub data: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Account {
+    pub balance: Option<i64>,
+    #[serde(rename = "frozenV2")]
+    pub frozen_v2: Option<Vec<AccountFrozen>>,
+    // contract address to balance, one entry per token
+    pub trc20: Option<Vec<HashMap<String, String>>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AccountFrozen {
+    pub amount: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TriggerConstantContract {
+    pub constant_result: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BroadcastResponse {
+    pub result: Option<bool>,
+    pub txid: Option<String>,
+    pub code: Option<String>,
+    // hex encoded
+    pub message: Option<String>,
+}
@@ -1,13 +1,15 @@
 use std::error::Error;
 
-use crate::ChainProvider;
+use crate::{
+    broadcast::check_node_status, BalanceProvider, ChainProvider, TransactionBroadcastProvider,
+};
 use async_trait::async_trait;
 use chrono::DateTime;
-use primitives::{chain::Chain, TransactionState, TransactionType};
+use primitives::{chain::Chain, AssetBalance, TransactionState, TransactionType};
 use reqwest_middleware::ClientWithMiddleware;
 use serde_json::json;
 
-use super::model::{Ledger, LedgerCurrent, LedgerData, LedgerResult};
+use super::model::{AccountInfo, Ledger, LedgerCurrent, LedgerData, LedgerResult, SubmitResult};
 
 pub struct XRPClient {
     url: String,
@@ -115,6 +117,84 @@ impl XRPClient {
     }
 }
 
+#[async_trait]
+impl BalanceProvider for XRPClient {
+    // accounts below the reserve are not created yet
+    async fn get_balance(
+        &self,
+        address: &str,
+    ) -> Result<AssetBalance, Box<dyn Error + Send + Sync>> {
+        let params = json!(
+            {
+                "method": "account_info",
+                "params": [
+                    {
+                        "account": address,
+                        "ledger_index": "current"
+                    }
+                ]
+            }
+        );
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&params)
+            .send()
+            .await?
+            .json::<LedgerResult<AccountInfo>>()
+            .await?;
+        let balance = response
+            .result
+            .account_data
+            .map(|x| x.balance)
+            .unwrap_or("0".to_string());
+        Ok(AssetBalance::new(Chain::Xrp.as_asset_id(), balance))
+    }
+}
+
+#[async_trait]
+impl TransactionBroadcastProvider for XRPClient {
+    async fn broadcast_transaction(
+        &self,
+        data: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let params = json!(
+            {
+                "method": "submit",
+                "params": [
+                    {
+                        "tx_blob": data
+                    }
+                ]
+            }
+        );
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&params)
+            .send()
+            .await?;
+        check_node_status(&response)?;
+        let response = response.json::<LedgerResult<SubmitResult>>().await?.result;
+
+        if let Some(error) = response.error {
+            return Err(
+                format!("{} {}", error, response.error_exception.unwrap_or_default()).into(),
+            );
+        }
+        // queued transactions are still applied in a later ledger
+        match (response.engine_result.as_deref(), response.tx_json) {
+            (Some("tesSUCCESS") | Some("terQUEUED"), Some(tx_json)) => Ok(tx_json.hash),
+            (result, _) => Err(format!(
+                "{} {}",
+                result.unwrap_or_default(),
+                response.engine_result_message.unwrap_or_default()
+            )
+            .into()),
+        }
+    }
+}
+
 #[async_trait]
 impl ChainProvider for XRPClient {
     fn get_chain(&self) -> Chain {
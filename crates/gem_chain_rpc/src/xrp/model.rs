@@ -15,6 +15,31 @@ pub struct LedgerData {
     pub ledger: Ledger,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub account_data: Option<AccountData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountData {
+    #[serde(rename = "Balance")]
+    pub balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitResult {
+    pub engine_result: Option<String>,
+    pub engine_result_message: Option<String>,
+    pub tx_json: Option<SubmitTransaction>,
+    pub error: Option<String>,
+    pub error_exception: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitTransaction {
+    pub hash: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ledger {
     pub close_time: i64,
@@ -0,0 +1,123 @@
+use alloy_core::primitives::{Address, U256};
+use alloy_core::{sol, sol_types::SolCall};
+use anyhow::Error;
+use std::str::FromStr;
+
+sol! {
+    #[derive(Debug, PartialEq)]
+    interface IERC20 {
+        function name() view returns (string);
+        function balanceOf(address account) view returns (uint256);
+        function allowance(address owner, address spender) view returns (uint256);
+        function approve(address spender, uint256 value) returns (bool);
+    }
+}
+
+pub fn encode_name() -> Vec<u8> {
+    IERC20::nameCall {}.abi_encode()
+}
+
+pub fn decode_name(result: &[u8]) -> Result<String, Error> {
+    let decoded = IERC20::nameCall::abi_decode_returns(result, true).map_err(Error::msg)?;
+    Ok(decoded._0)
+}
+
+pub fn encode_balance_of(account: &str) -> Result<Vec<u8>, Error> {
+    let call = IERC20::balanceOfCall {
+        account: Address::from_str(account).map_err(Error::msg)?,
+    };
+    Ok(call.abi_encode())
+}
+
+pub fn decode_balance_of(result: &[u8]) -> Result<String, Error> {
+    let decoded = IERC20::balanceOfCall::abi_decode_returns(result, true).map_err(Error::msg)?;
+    Ok(decoded._0.to_string())
+}
+
+pub fn encode_allowance(owner: &str, spender: &str) -> Result<Vec<u8>, Error> {
+    let call = IERC20::allowanceCall {
+        owner: Address::from_str(owner).map_err(Error::msg)?,
+        spender: Address::from_str(spender).map_err(Error::msg)?,
+    };
+    Ok(call.abi_encode())
+}
+
+pub fn decode_allowance(result: &[u8]) -> Result<String, Error> {
+    let decoded = IERC20::allowanceCall::abi_decode_returns(result, true).map_err(Error::msg)?;
+    Ok(decoded._0.to_string())
+}
+
+pub fn encode_approve(spender: &str, value: &str) -> Result<Vec<u8>, Error> {
+    let call = IERC20::approveCall {
+        spender: Address::from_str(spender).map_err(Error::msg)?,
+        value: U256::from_str(value).map_err(Error::msg)?,
+    };
+    Ok(call.abi_encode())
+}
+
+// spender and value of approve call data
+pub fn decode_approve(data: &[u8]) -> Result<(String, String), Error> {
+    let decoded = IERC20::approveCall::abi_decode(data, true).map_err(Error::msg)?;
+    Ok((decoded.spender.to_checksum(None), decoded.value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_allowance() {
+        let result = encode_allowance(
+            "0x514BCb1F9AAbb904e6106Bd1052B66d2706dBbb7",
+            "0x1111111254EEB25477B68fb85Ed929f73A960582",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode(result),
+            "dd62ed3e000000000000000000000000514bcb1f9aabb904e6106bd1052b66d2706dbbb70000000000000000000000001111111254eeb25477b68fb85ed929f73a960582"
+        );
+    }
+
+    #[test]
+    fn test_encode_balance_of() {
+        let result = encode_balance_of("0x514BCb1F9AAbb904e6106Bd1052B66d2706dBbb7").unwrap();
+
+        assert_eq!(
+            hex::encode(result),
+            "70a08231000000000000000000000000514bcb1f9aabb904e6106bd1052b66d2706dbbb7"
+        );
+    }
+
+    #[test]
+    fn test_decode_allowance() {
+        let result =
+            hex::decode("00000000000000000000000000000000000000000000000000000000000003e8")
+                .unwrap();
+
+        assert_eq!(decode_allowance(&result).unwrap(), "1000");
+    }
+
+    #[test]
+    fn test_decode_approve() {
+        let data = encode_approve("0x1111111254EEB25477B68fb85Ed929f73A960582", "1000").unwrap();
+
+        assert_eq!(
+            decode_approve(&data).unwrap(),
+            (
+                "0x1111111254EEB25477B68fb85Ed929f73A960582".to_string(),
+                "1000".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_encode_approve() {
+        let result = encode_approve("0x1111111254EEB25477B68fb85Ed929f73A960582", "1000").unwrap();
+
+        assert_eq!(
+            hex::encode(result),
+            "095ea7b30000000000000000000000001111111254eeb25477b68fb85ed929f73a96058200000000000000000000000000000000000000000000000000000000000003e8"
+        );
+    }
+}
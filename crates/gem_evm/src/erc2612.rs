@@ -1,3 +1,8 @@
+use alloy_core::primitives::Address;
+use alloy_core::{sol, sol_types::SolCall};
+use anyhow::Error;
+use std::str::FromStr;
+
 #[derive(Debug, PartialEq)]
 pub struct Permit {
     pub value: String,
@@ -6,3 +11,57 @@ pub struct Permit {
     pub r: Vec<u8>,
     pub s: Vec<u8>,
 }
+
+sol! {
+    #[derive(Debug, PartialEq)]
+    interface IERC20Permit {
+        function nonces(address owner) view returns (uint256);
+        function version() view returns (string);
+    }
+}
+
+pub fn encode_nonces(owner: &str) -> Result<Vec<u8>, Error> {
+    let call = IERC20Permit::noncesCall {
+        owner: Address::from_str(owner).map_err(Error::msg)?,
+    };
+    Ok(call.abi_encode())
+}
+
+pub fn decode_nonces(result: &[u8]) -> Result<String, Error> {
+    let decoded = IERC20Permit::noncesCall::abi_decode_returns(result, true).map_err(Error::msg)?;
+    Ok(decoded._0.to_string())
+}
+
+pub fn encode_version() -> Vec<u8> {
+    IERC20Permit::versionCall {}.abi_encode()
+}
+
+pub fn decode_version(result: &[u8]) -> Result<String, Error> {
+    let decoded =
+        IERC20Permit::versionCall::abi_decode_returns(result, true).map_err(Error::msg)?;
+    Ok(decoded._0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_nonces() {
+        let result = encode_nonces("0x514BCb1F9AAbb904e6106Bd1052B66d2706dBbb7").unwrap();
+
+        assert_eq!(
+            hex::encode(result),
+            "7ecebe00000000000000000000000000514bcb1f9aabb904e6106bd1052b66d2706dbbb7"
+        );
+    }
+
+    #[test]
+    fn test_decode_nonces() {
+        let result =
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000001")
+                .unwrap();
+
+        assert_eq!(decode_nonces(&result).unwrap(), "1");
+    }
+}
@@ -1,3 +1,5 @@
 pub mod address;
+pub mod erc20;
 pub mod erc2612;
 pub mod lido;
+pub mod multicall3;
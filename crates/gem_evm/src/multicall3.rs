@@ -0,0 +1,75 @@
+use alloy_core::primitives::{Address, Bytes};
+use alloy_core::{sol, sol_types::SolCall};
+use anyhow::Error;
+use std::str::FromStr;
+
+// same address on most evm chains, see https://www.multicall3.com/deployments
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+pub const MULTICALL3_ADDRESS_ZKSYNC: &str = "0xF9cda624FBC7e059355ce98a31693d299FACd963";
+
+sol! {
+    #[derive(Debug, PartialEq)]
+    struct Call3 {
+        address target;
+        bool allowFailure;
+        bytes callData;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Call3Result {
+        bool success;
+        bytes returnData;
+    }
+
+    #[derive(Debug, PartialEq)]
+    interface IMulticall3 {
+        function aggregate3(Call3[] calldata calls) payable returns (Call3Result[] memory returnData);
+    }
+}
+
+// calls are (target, call data), failed calls do not revert the batch
+pub fn encode_aggregate3(calls: Vec<(String, Vec<u8>)>) -> Result<Vec<u8>, Error> {
+    let mut _calls = vec![];
+    for (target, data) in calls.into_iter() {
+        _calls.push(Call3 {
+            target: Address::from_str(&target).map_err(Error::msg)?,
+            allowFailure: true,
+            callData: Bytes::from(data),
+        });
+    }
+    let call = IMulticall3::aggregate3Call { calls: _calls };
+    Ok(call.abi_encode())
+}
+
+// return data of each call, None for calls that failed
+pub fn decode_aggregate3(result: &[u8]) -> Result<Vec<Option<Vec<u8>>>, Error> {
+    let decoded =
+        IMulticall3::aggregate3Call::abi_decode_returns(result, true).map_err(Error::msg)?;
+    Ok(decoded
+        .returnData
+        .into_iter()
+        .map(|x| {
+            if x.success {
+                Some(x.returnData.to_vec())
+            } else {
+                None
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_aggregate3_selector() {
+        let result = encode_aggregate3(vec![(
+            "0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string(),
+            vec![],
+        )])
+        .unwrap();
+
+        assert_eq!(hex::encode(&result[..4]), "82ad56cb");
+    }
+}
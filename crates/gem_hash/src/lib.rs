@@ -1 +1,2 @@
 pub mod keccak;
+pub mod sha3;
@@ -0,0 +1,9 @@
+pub fn sha3_256(bytes: &[u8]) -> [u8; 32] {
+    use tiny_keccak::{Hasher, Sha3};
+    let mut hasher = Sha3::v256();
+    hasher.update(bytes);
+
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
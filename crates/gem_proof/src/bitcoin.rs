@@ -0,0 +1,172 @@
+use k256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::ProofError;
+
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+const SIGHASH_ALL: u8 = 0x01;
+
+// BIP-322 simple signature, native segwit (P2WPKH) addresses only
+pub fn verify(hrp: &str, address: &str, message: &str, witness: &[u8]) -> Result<(), ProofError> {
+    let (address_hrp, version, program) =
+        bech32::segwit::decode(address).map_err(|_| ProofError::InvalidAddress)?;
+    if address_hrp.to_lowercase() != hrp {
+        return Err(ProofError::InvalidAddress);
+    }
+    if version.to_u8() != 0 || program.len() != 20 {
+        return Err(ProofError::Unsupported(address.to_string()));
+    }
+
+    let items = parse_witness(witness)?;
+    let [signature, public_key] = items.as_slice() else {
+        return Err(ProofError::InvalidSignature);
+    };
+    if hash160(public_key) != program.as_slice() {
+        return Err(ProofError::AddressMismatch);
+    }
+    let (sighash_type, signature) = signature.split_last().ok_or(ProofError::InvalidSignature)?;
+    if *sighash_type != SIGHASH_ALL {
+        return Err(ProofError::InvalidSignature);
+    }
+
+    let signature = Signature::from_der(signature).map_err(|_| ProofError::InvalidSignature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    let public_key =
+        VerifyingKey::from_sec1_bytes(public_key).map_err(|_| ProofError::InvalidPublicKey)?;
+    let sighash = sighash(&program, &message_hash(message));
+    public_key
+        .verify_prehash(&sighash, &signature)
+        .map_err(|_| ProofError::InvalidSignature)
+}
+
+pub fn message_hash(message: &str) -> [u8; 32] {
+    let tag = Sha256::digest(BIP322_TAG);
+    Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(message.as_bytes())
+        .finalize()
+        .into()
+}
+
+// BIP-143 digest of the virtual to_sign transaction spending to_spend
+fn sighash(program: &[u8], message_hash: &[u8; 32]) -> [u8; 32] {
+    let script_pubkey = [&[0x00, 0x14], program].concat();
+
+    let mut to_spend = vec![];
+    to_spend.extend(0u32.to_le_bytes());
+    to_spend.push(1);
+    to_spend.extend([0u8; 32]);
+    to_spend.extend(u32::MAX.to_le_bytes());
+    to_spend.push(34);
+    to_spend.extend([0x00, 0x20]);
+    to_spend.extend(message_hash);
+    to_spend.extend(0u32.to_le_bytes());
+    to_spend.push(1);
+    to_spend.extend(0u64.to_le_bytes());
+    to_spend.push(script_pubkey.len() as u8);
+    to_spend.extend(&script_pubkey);
+    to_spend.extend(0u32.to_le_bytes());
+    let outpoint = [sha256d(&to_spend).as_slice(), &0u32.to_le_bytes()].concat();
+
+    let mut outputs = vec![];
+    outputs.extend(0u64.to_le_bytes());
+    outputs.extend([0x01, 0x6a]);
+
+    let mut preimage = vec![];
+    preimage.extend(0u32.to_le_bytes());
+    preimage.extend(sha256d(&outpoint));
+    preimage.extend(sha256d(&0u32.to_le_bytes()));
+    preimage.extend(&outpoint);
+    preimage.extend([0x19, 0x76, 0xa9, 0x14]);
+    preimage.extend(program);
+    preimage.extend([0x88, 0xac]);
+    preimage.extend(0u64.to_le_bytes());
+    preimage.extend(0u32.to_le_bytes());
+    preimage.extend(sha256d(&outputs));
+    preimage.extend(0u32.to_le_bytes());
+    preimage.extend((SIGHASH_ALL as u32).to_le_bytes());
+    sha256d(&preimage)
+}
+
+fn parse_witness(bytes: &[u8]) -> Result<Vec<Vec<u8>>, ProofError> {
+    let mut offset = 0;
+    let count = read_compact_size(bytes, &mut offset)?;
+    let mut items = vec![];
+    for _ in 0..count {
+        let length = read_compact_size(bytes, &mut offset)?;
+        let item = bytes
+            .get(offset..offset.saturating_add(length))
+            .ok_or(ProofError::InvalidSignature)?;
+        items.push(item.to_vec());
+        offset += length;
+    }
+    if offset != bytes.len() {
+        return Err(ProofError::InvalidSignature);
+    }
+    Ok(items)
+}
+
+fn read_compact_size(bytes: &[u8], offset: &mut usize) -> Result<usize, ProofError> {
+    let first = *bytes.get(*offset).ok_or(ProofError::InvalidSignature)?;
+    *offset += 1;
+    let size = match first {
+        0xfd => 2,
+        0xfe => 4,
+        0xff => 8,
+        _ => return Ok(first as usize),
+    };
+    let value = bytes
+        .get(*offset..*offset + size)
+        .ok_or(ProofError::InvalidSignature)?
+        .iter()
+        .rev()
+        .fold(0usize, |value, byte| (value << 8) | *byte as usize);
+    *offset += size;
+    Ok(value)
+}
+
+fn sha256d(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(bytes)).into()
+}
+
+fn hash160(bytes: &[u8]) -> Vec<u8> {
+    Ripemd160::digest(Sha256::digest(bytes)).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose, Engine as _};
+
+    const ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const WITNESS: &str = "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(
+            hex::encode(message_hash("")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(message_hash("Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let witness = general_purpose::STANDARD.decode(WITNESS).unwrap();
+
+        assert_eq!(verify("bc", ADDRESS, "Hello World", &witness), Ok(()));
+        assert_eq!(
+            verify("bc", ADDRESS, "Hello", &witness),
+            Err(ProofError::InvalidSignature)
+        );
+        assert_eq!(
+            verify("ltc", ADDRESS, "Hello World", &witness),
+            Err(ProofError::InvalidAddress)
+        );
+    }
+}
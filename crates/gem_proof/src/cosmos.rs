@@ -0,0 +1,115 @@
+use base64::{engine::general_purpose, Engine as _};
+use gem_hash::keccak::keccak256;
+use k256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+use primitives::Chain;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::ProofError;
+
+// ADR-036 signArbitrary, signature is r || s over the amino sign doc
+pub fn verify(
+    chain: Chain,
+    address: &str,
+    message: &str,
+    public_key: &[u8],
+    signature: &[u8],
+) -> Result<(), ProofError> {
+    let (hrp, data) = bech32::decode(address).map_err(|_| ProofError::InvalidAddress)?;
+    if Some(hrp.to_lowercase().as_str()) != address_prefix(chain) {
+        return Err(ProofError::InvalidAddress);
+    }
+
+    let key =
+        VerifyingKey::from_sec1_bytes(public_key).map_err(|_| ProofError::InvalidPublicKey)?;
+    let sign_doc = sign_doc(address, message);
+    // injective uses ethereum style keys
+    let (signer, digest) = match chain {
+        Chain::Injective => {
+            let point = key.to_encoded_point(false);
+            (
+                keccak256(&point.as_bytes()[1..])[12..].to_vec(),
+                keccak256(sign_doc.as_bytes()),
+            )
+        }
+        _ => {
+            let point = key.to_encoded_point(true);
+            (
+                Ripemd160::digest(Sha256::digest(point.as_bytes())).to_vec(),
+                Sha256::digest(sign_doc.as_bytes()).into(),
+            )
+        }
+    };
+    if signer != data {
+        return Err(ProofError::AddressMismatch);
+    }
+
+    let signature = Signature::from_slice(signature).map_err(|_| ProofError::InvalidSignature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    key.verify_prehash(&digest, &signature)
+        .map_err(|_| ProofError::InvalidSignature)
+}
+
+// keys are sorted, matching the canonical amino json encoding
+pub fn sign_doc(signer: &str, message: &str) -> String {
+    format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        general_purpose::STANDARD.encode(message),
+        signer
+    )
+}
+
+fn address_prefix(chain: Chain) -> Option<&'static str> {
+    match chain {
+        Chain::Cosmos => Some("cosmos"),
+        Chain::Osmosis => Some("osmo"),
+        Chain::Celestia => Some("celestia"),
+        Chain::Thorchain => Some("thor"),
+        Chain::Injective => Some("inj"),
+        Chain::Noble => Some("noble"),
+        Chain::Sei => Some("sei"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "cosmos1thklh702txwafc72d2qtxv7ywt7sk0mfjt997f";
+
+    fn decode(value: &str) -> Vec<u8> {
+        general_purpose::STANDARD.decode(value).unwrap()
+    }
+
+    #[test]
+    fn test_verify() {
+        let public_key = decode("Aly98GRuXbTqo5jzZfLqeg49QZt+AzDjnOkr3e3KxPm8");
+        let signature = decode("UjL3J9aCQLH6iz7tHVyZI2t35kJNY3UTNsuZtFlZgOkrVIqaYMiiVvXVH2J9YagDuC9qNnMGxMZ1+a6YuZjCIg==");
+
+        assert_eq!(
+            verify(
+                Chain::Cosmos,
+                ADDRESS,
+                "Hello World",
+                &public_key,
+                &signature
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify(Chain::Cosmos, ADDRESS, "Hello", &public_key, &signature),
+            Err(ProofError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(
+                Chain::Osmosis,
+                ADDRESS,
+                "Hello World",
+                &public_key,
+                &signature
+            ),
+            Err(ProofError::InvalidAddress)
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ed25519_dalek::{Signature, VerifyingKey};
+use gem_hash::sha3::sha3_256;
+
+use crate::ProofError;
+
+const SUI_SIGNATURE_FLAG: u8 = 0x00;
+const SUI_PERSONAL_MESSAGE_INTENT: [u8; 3] = [3, 0, 0];
+const APTOS_SIGNATURE_SCHEME: u8 = 0x00;
+const APTOS_MESSAGE_PREFIX: &str = "APTOS\n";
+
+// signMessage, the address is the public key
+pub fn verify_solana(address: &str, message: &str, signature: &[u8]) -> Result<(), ProofError> {
+    let public_key = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| ProofError::InvalidAddress)?;
+    verify_signature(&public_key, message.as_bytes(), signature)
+}
+
+// signPersonalMessage, serialized as flag || signature || public key
+pub fn verify_sui(address: &str, message: &str, signature: &[u8]) -> Result<(), ProofError> {
+    if signature.len() != 97 || signature[0] != SUI_SIGNATURE_FLAG {
+        return Err(ProofError::InvalidSignature);
+    }
+    let public_key = &signature[65..];
+    if normalize_address(&sui_address(public_key)) != normalize_address(address) {
+        return Err(ProofError::AddressMismatch);
+    }
+    verify_signature(public_key, &sui_message_digest(message), &signature[1..65])
+}
+
+// signMessage, wallets wrap the message with a prefix and a nonce
+pub fn verify_aptos(
+    address: &str,
+    message: &str,
+    full_message: &str,
+    public_key: &[u8],
+    signature: &[u8],
+) -> Result<(), ProofError> {
+    if !full_message.starts_with(APTOS_MESSAGE_PREFIX)
+        || !full_message.contains(&format!("\nmessage: {}\nnonce: ", message))
+    {
+        return Err(ProofError::InvalidMessage);
+    }
+    if normalize_address(&aptos_address(public_key)) != normalize_address(address) {
+        return Err(ProofError::AddressMismatch);
+    }
+    verify_signature(public_key, full_message.as_bytes(), signature)
+}
+
+pub(crate) fn verify_signature(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), ProofError> {
+    let public_key: [u8; 32] = public_key
+        .try_into()
+        .map_err(|_| ProofError::InvalidPublicKey)?;
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| ProofError::InvalidSignature)?;
+    let public_key =
+        VerifyingKey::from_bytes(&public_key).map_err(|_| ProofError::InvalidPublicKey)?;
+    public_key
+        .verify_strict(message, &Signature::from_bytes(&signature))
+        .map_err(|_| ProofError::InvalidSignature)
+}
+
+fn sui_message_digest(message: &str) -> Vec<u8> {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(SUI_PERSONAL_MESSAGE_INTENT);
+    hasher.update(uleb128(message.len()));
+    hasher.update(message.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+fn sui_address(public_key: &[u8]) -> String {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([SUI_SIGNATURE_FLAG]);
+    hasher.update(public_key);
+    hex::encode(hasher.finalize())
+}
+
+fn aptos_address(public_key: &[u8]) -> String {
+    let bytes = [public_key, &[APTOS_SIGNATURE_SCHEME]].concat();
+    hex::encode(sha3_256(&bytes))
+}
+
+// both chains allow short addresses with the leading zeros removed
+fn normalize_address(address: &str) -> String {
+    format!("{:0>64}", address.trim_start_matches("0x").to_lowercase())
+}
+
+fn uleb128(mut value: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose, Engine as _};
+
+    fn decode(value: &str) -> Vec<u8> {
+        general_purpose::STANDARD.decode(value).unwrap()
+    }
+
+    #[test]
+    fn test_verify_solana() {
+        let address = "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9";
+        let signature = decode("FbQVsrLSTieyNjLnBjy/ErYWnPI5trrBf5tE9s3cUWT5JZCJNSY6FTJdQymIiVBytjyN/0U90GRdzK3EhYuiAQ==");
+
+        assert_eq!(verify_solana(address, "Hello World", &signature), Ok(()));
+        assert_eq!(
+            verify_solana(address, "Hello", &signature),
+            Err(ProofError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_verify_sui() {
+        let address = "0x7799ea80594c35644321148485238c7a7a1c6549809e1795e6747c6d4da2504c";
+        let signature = decode("AEc8XwTkZJdfTx9cp9VUcoAN/QJ7s5/yk2M58TooVwIc3cxIbAxdRqZEY9iIcUvbV3Kq2aWIoRkUjFeBoJXncA6BOXcOqH0XX1ajVGbDTH7My42KkbTuN6Jd9g9bj8mzlA==");
+
+        assert_eq!(verify_sui(address, "Hello World", &signature), Ok(()));
+        assert_eq!(
+            verify_sui(address, "Hello", &signature),
+            Err(ProofError::InvalidSignature)
+        );
+        assert_eq!(
+            verify_sui("0x1", "Hello World", &signature),
+            Err(ProofError::AddressMismatch)
+        );
+    }
+
+    #[test]
+    fn test_verify_aptos() {
+        let address = "0xf4f9450e6f0b72ba78becf56a39f8bd322295548fdd52b29bc4aebd58dd0223d";
+        let public_key = decode("7UkoxijRwsbq6QM4kFmVYSlZJzpcY/k2NsFGFKyHN9E=");
+        let signature = decode("/kA7MCUlNRLI3ECzTRQkhoem+NH00+t3bfvPmY1Q8ETm/n9feDtIvkMWtXsm4CnM3n2RpbcLa8SL5vtt2fBxDA==");
+        let full_message = "APTOS\nmessage: Hello World\nnonce: 1";
+
+        assert_eq!(
+            verify_aptos(
+                address,
+                "Hello World",
+                full_message,
+                &public_key,
+                &signature
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify_aptos(address, "Hello", full_message, &public_key, &signature),
+            Err(ProofError::InvalidMessage)
+        );
+    }
+
+    #[test]
+    fn test_uleb128() {
+        assert_eq!(uleb128(11), vec![11]);
+        assert_eq!(uleb128(300), vec![0xac, 0x02]);
+    }
+}
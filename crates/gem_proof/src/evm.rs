@@ -0,0 +1,74 @@
+use gem_hash::keccak::keccak256;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+use crate::ProofError;
+
+// EIP-191 personal_sign
+pub fn verify(address: &str, message: &str, signature: &str) -> Result<(), ProofError> {
+    let signature = hex::decode(signature.trim_start_matches("0x"))
+        .map_err(|_| ProofError::InvalidSignature)?;
+    if signature.len() != 65 {
+        return Err(ProofError::InvalidSignature);
+    }
+    let recovered = recover_address(&message_hash(message), &signature)?;
+    if !recovered.eq_ignore_ascii_case(address) {
+        return Err(ProofError::AddressMismatch);
+    }
+    Ok(())
+}
+
+pub fn message_hash(message: &str) -> [u8; 32] {
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    keccak256(prefixed.as_bytes())
+}
+
+fn recover_address(hash: &[u8; 32], signature: &[u8]) -> Result<String, ProofError> {
+    let v = match signature[64] {
+        0 | 1 => signature[64],
+        27 | 28 => signature[64] - 27,
+        _ => return Err(ProofError::InvalidSignature),
+    };
+    let mut signature =
+        Signature::from_slice(&signature[..64]).map_err(|_| ProofError::InvalidSignature)?;
+    let mut recovery_id = RecoveryId::from_byte(v).ok_or(ProofError::InvalidSignature)?;
+    // high s values are valid on chain, flip the parity when normalizing
+    if let Some(normalized) = signature.normalize_s() {
+        signature = normalized;
+        recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    }
+    let key = VerifyingKey::recover_from_prehash(hash, &signature, recovery_id)
+        .map_err(|_| ProofError::InvalidSignature)?;
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    Ok(format!("0x{}", hex::encode(&hash[12..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(
+            hex::encode(message_hash("Some data")),
+            "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655"
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let address = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+        assert_eq!(verify(address, "Some data", SIGNATURE), Ok(()));
+        assert_eq!(
+            verify(address, "Other data", SIGNATURE),
+            Err(ProofError::AddressMismatch)
+        );
+        assert_eq!(
+            verify(address, "Some data", "0x1234"),
+            Err(ProofError::InvalidSignature)
+        );
+    }
+}
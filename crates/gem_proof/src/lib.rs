@@ -0,0 +1,144 @@
+pub mod bitcoin;
+pub mod cosmos;
+pub mod ed25519;
+pub mod evm;
+pub mod ton;
+
+use base64::{engine::general_purpose, Engine as _};
+use primitives::{Chain, ChainType, SubscriptionProof};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ProofError {
+    #[error("ownership proofs are not supported for {0}")]
+    Unsupported(String),
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("invalid public key")]
+    InvalidPublicKey,
+    #[error("invalid address")]
+    InvalidAddress,
+    #[error("signed message does not match")]
+    InvalidMessage,
+    #[error("signer does not match address")]
+    AddressMismatch,
+}
+
+// message the wallet signs with the key of the subscribed address
+pub fn subscription_message(device_id: &str, chain: Chain, address: &str) -> String {
+    format!(
+        "Gem Wallet\nSubscribe device {} to {} on {}",
+        device_id,
+        address,
+        chain.as_ref()
+    )
+}
+
+pub fn verify_ownership(
+    chain: Chain,
+    address: &str,
+    message: &str,
+    proof: &SubscriptionProof,
+) -> Result<(), ProofError> {
+    match chain.chain_type() {
+        ChainType::Ethereum => evm::verify(address, message, &proof.signature),
+        ChainType::Bitcoin => match chain {
+            Chain::Bitcoin => bitcoin::verify(
+                "bc",
+                address,
+                message,
+                &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+            ),
+            Chain::Litecoin => bitcoin::verify(
+                "ltc",
+                address,
+                message,
+                &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+            ),
+            _ => Err(ProofError::Unsupported(chain.as_ref().to_string())),
+        },
+        ChainType::Solana => ed25519::verify_solana(
+            address,
+            message,
+            &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+        ),
+        ChainType::Sui => ed25519::verify_sui(
+            address,
+            message,
+            &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+        ),
+        ChainType::Aptos => ed25519::verify_aptos(
+            address,
+            message,
+            proof.message.as_deref().ok_or(ProofError::InvalidMessage)?,
+            &decode_base64(
+                proof
+                    .public_key
+                    .as_deref()
+                    .ok_or(ProofError::InvalidPublicKey)?,
+                ProofError::InvalidPublicKey,
+            )?,
+            &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+        ),
+        ChainType::Cosmos => cosmos::verify(
+            chain,
+            address,
+            message,
+            &decode_base64(
+                proof
+                    .public_key
+                    .as_deref()
+                    .ok_or(ProofError::InvalidPublicKey)?,
+                ProofError::InvalidPublicKey,
+            )?,
+            &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+        ),
+        ChainType::Ton => ton::verify(
+            address,
+            message,
+            proof.domain.as_deref().ok_or(ProofError::InvalidMessage)?,
+            proof.timestamp.ok_or(ProofError::InvalidMessage)?,
+            proof
+                .state_init
+                .as_deref()
+                .ok_or(ProofError::InvalidPublicKey)?,
+            &decode_base64(&proof.signature, ProofError::InvalidSignature)?,
+        ),
+        ChainType::Tron | ChainType::Xrp | ChainType::Near => {
+            Err(ProofError::Unsupported(chain.as_ref().to_string()))
+        }
+    }
+}
+
+fn decode_base64(value: &str, error: ProofError) -> Result<Vec<u8>, ProofError> {
+    general_purpose::STANDARD.decode(value).map_err(|_| error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_message() {
+        assert_eq!(
+            subscription_message("device", Chain::Ethereum, "0x123"),
+            "Gem Wallet\nSubscribe device device to 0x123 on ethereum"
+        );
+    }
+
+    #[test]
+    fn test_verify_ownership_unsupported() {
+        let proof = SubscriptionProof {
+            signature: "".to_string(),
+            public_key: None,
+            message: None,
+            domain: None,
+            timestamp: None,
+            state_init: None,
+        };
+        assert_eq!(
+            verify_ownership(Chain::Tron, "address", "message", &proof),
+            Err(ProofError::Unsupported("tron".to_string()))
+        );
+    }
+}
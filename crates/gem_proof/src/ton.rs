@@ -0,0 +1,163 @@
+use std::str::FromStr;
+
+use gem_ton::{
+    cell::{BagOfCells, Cell},
+    TonAddress,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{ed25519::verify_signature, ProofError};
+
+const TON_PROOF_PREFIX: &str = "ton-proof-item-v2/";
+const TON_CONNECT_PREFIX: &str = "ton-connect";
+
+// ton connect ton_proof with the subscription message as payload, the key is read from the wallet state init
+pub fn verify(
+    address: &str,
+    message: &str,
+    domain: &str,
+    timestamp: i64,
+    state_init: &str,
+    signature: &[u8],
+) -> Result<(), ProofError> {
+    let address = TonAddress::from_str(address).map_err(|_| ProofError::InvalidAddress)?;
+    let state_init = BagOfCells::parse_base64(state_init)
+        .and_then(|x| x.single_root().cloned())
+        .map_err(|_| ProofError::InvalidPublicKey)?;
+    let hash = state_init
+        .cell_hash()
+        .map_err(|_| ProofError::InvalidPublicKey)?;
+    if hash != address.hash_part {
+        return Err(ProofError::AddressMismatch);
+    }
+    let public_key = wallet_public_key(&state_init)?;
+
+    let message = proof_message(&address, domain, timestamp, message);
+    let digest = Sha256::digest(
+        [
+            &[0xff, 0xff],
+            TON_CONNECT_PREFIX.as_bytes(),
+            Sha256::digest(message).as_slice(),
+        ]
+        .concat(),
+    );
+    verify_signature(&public_key, &digest, signature)
+}
+
+fn proof_message(address: &TonAddress, domain: &str, timestamp: i64, payload: &str) -> Vec<u8> {
+    [
+        TON_PROOF_PREFIX.as_bytes(),
+        &address.workchain.to_be_bytes(),
+        &address.hash_part,
+        &(domain.len() as u32).to_le_bytes(),
+        domain.as_bytes(),
+        &(timestamp as u64).to_le_bytes(),
+        payload.as_bytes(),
+    ]
+    .concat()
+}
+
+// standard wallets (v1 to v5) keep the key after the seqno and wallet id, the data length tells the version apart
+fn wallet_public_key(state_init: &Cell) -> Result<Vec<u8>, ProofError> {
+    // no split depth, no special, code and data
+    if state_init.bit_len != 5 || state_init.data != [0b0011_0000] {
+        return Err(ProofError::InvalidPublicKey);
+    }
+    let data = state_init
+        .reference(1)
+        .map_err(|_| ProofError::InvalidPublicKey)?;
+    let offset = match data.bit_len {
+        288 => 32,
+        320 | 321 => 64,
+        322 => 65,
+        _ => return Err(ProofError::InvalidPublicKey),
+    };
+    let mut parser = data.parser();
+    parser
+        .skip_bits(offset)
+        .and_then(|_| parser.load_bytes(32))
+        .map_err(|_| ProofError::InvalidPublicKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose, Engine as _};
+
+    const ADDRESS: &str = "0:04ab2d551e0199bebcce74b457abdd52df00b0afa410a6d63fbedeae9005db9c";
+    const STATE_INIT: &str =
+        "te6ccgEBAwEAMwACATQBAgAC/wBRAAAAACmpoxeKiOPddAnxlf1S2y08ul1yymcJvx2UEhvzdIgBtA9vXEA=";
+
+    fn signature() -> Vec<u8> {
+        general_purpose::STANDARD.decode("TiELRugm+dq+aopHFEseOTOPxjzU+YaxUPN0lxMyi0vLMqg/qamK7MvVP7NmEsA4TVysf0+93gtmOEevc+NABw==").unwrap()
+    }
+
+    fn message(address: &str) -> String {
+        format!("Gem Wallet\nSubscribe device device to {} on ton", address)
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(
+            verify(
+                ADDRESS,
+                &message(ADDRESS),
+                "gemwallet.com",
+                1718000000,
+                STATE_INIT,
+                &signature()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify(
+                ADDRESS,
+                &message(ADDRESS),
+                "gemwallet.com",
+                1718000001,
+                STATE_INIT,
+                &signature()
+            ),
+            Err(ProofError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(
+                ADDRESS,
+                &message("0:00"),
+                "gemwallet.com",
+                1718000000,
+                STATE_INIT,
+                &signature()
+            ),
+            Err(ProofError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_verify_address_mismatch() {
+        let address = "0:14ab2d551e0199bebcce74b457abdd52df00b0afa410a6d63fbedeae9005db9c";
+
+        assert_eq!(
+            verify(
+                address,
+                &message(address),
+                "gemwallet.com",
+                1718000000,
+                STATE_INIT,
+                &signature()
+            ),
+            Err(ProofError::AddressMismatch)
+        );
+    }
+
+    #[test]
+    fn test_wallet_public_key() {
+        let state_init = BagOfCells::parse_base64(STATE_INIT).unwrap();
+
+        assert_eq!(
+            general_purpose::STANDARD
+                .encode(wallet_public_key(state_init.single_root().unwrap()).unwrap()),
+            "iojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SIAbQPb1w="
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::AssetId;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBalance {
+    pub asset_id: AssetId,
+    pub balance: String,
+}
+
+impl AssetBalance {
+    pub fn new(asset_id: AssetId, balance: String) -> Self {
+        Self { asset_id, balance }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalances {
+    pub native: AssetBalance,
+    pub staked: Option<AssetBalance>,
+    pub tokens: Vec<AssetBalance>,
+}
@@ -45,6 +45,7 @@ pub struct Charts {
     pub prices: Vec<ChartValue>,
     pub market_caps: Vec<ChartValue>,
     pub total_volumes: Vec<ChartValue>,
+    pub candles: Vec<ChartCandle>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +56,17 @@ pub struct ChartValue {
     pub value: f32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct ChartCandle {
+    pub timestamp: i32,
+    pub open: f32,
+    pub high: f32,
+    pub low: f32,
+    pub close: f32,
+}
+
 impl PartialEq for ChartValue {
     fn eq(&self, other: &Self) -> bool {
         self.timestamp == other.timestamp && self.value == other.value
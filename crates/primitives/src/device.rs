@@ -15,4 +15,19 @@ pub struct Device {
     pub currency: String,
     pub is_push_enabled: bool,
     pub subscriptions_version: i32,
+    // ECDSA P-256 key bound on registration, device scoped requests are signed with it
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+// rotation is signed with the current key, the signature here proves ownership of the new one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable, Equatable")]
+#[serde(rename_all = "camelCase")]
+pub struct DevicePublicKey {
+    pub public_key: String,
+    pub signature: String,
+    // challenge pushed to the device, required to bind the first key of a device registered without one
+    #[serde(default)]
+    pub challenge: Option<String>,
 }
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString};
+use typeshare::typeshare;
+
+use crate::Chain;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumString, AsRefStr, PartialEq)]
+#[typeshare(swift = "Equatable, Codable, CaseIterable")]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum FeePriority {
+    Slow,
+    Normal,
+    Fast,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRate {
+    pub priority: FeePriority,
+    // evm: expected gas price in wei (next base fee + priority fee)
+    // bitcoin: sat/vB, solana: micro lamports per compute unit
+    // cosmos: gas price in the fee denom
+    pub value: String,
+    // evm: priority fee per gas in wei
+    pub priority_fee: Option<String>,
+}
+
+impl FeeRate {
+    pub fn new(priority: FeePriority, value: String) -> Self {
+        Self {
+            priority,
+            value,
+            priority_fee: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    pub chain: Chain,
+    pub rates: Vec<FeeRate>,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+use typeshare::typeshare;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, AsRefStr, EnumString)]
+#[typeshare(swift = "Equatable, Codable, CaseIterable")]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum FiatPaymentMethod {
+    Card,
+    ApplePay,
+    GooglePay,
+    BankTransfer,
+}
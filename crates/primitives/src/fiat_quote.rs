@@ -1,4 +1,4 @@
-use crate::fiat_provider::FiatProvider;
+use crate::{fiat_provider::FiatProvider, FiatPaymentMethod};
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
@@ -11,10 +11,49 @@ pub struct FiatQuote {
     pub fiat_currency: String,
     pub crypto_amount: f64,
     pub redirect_url: String,
+    // fees are in fiat_currency and already included in fiat_amount
+    pub fee_provider: f64,
+    pub fee_network: f64,
+    pub fee_partner: f64,
+    // fiat price of one unit of the asset before fees
+    pub exchange_rate: f64,
+    pub payment_method: Option<FiatPaymentMethod>,
+}
+
+impl FiatQuote {
+    // fiat paid or received per unit of the asset with all fees applied, infinite without a crypto amount
+    pub fn effective_rate(&self) -> f64 {
+        if self.crypto_amount > 0.0 {
+            self.fiat_amount / self.crypto_amount
+        } else {
+            f64::INFINITY
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[typeshare()]
 pub struct FiatQuotes {
     pub quotes: Vec<FiatQuote>,
+    pub excluded: Vec<FiatProviderExclusion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct FiatProviderExclusion {
+    pub provider: FiatProvider,
+    pub reason: FiatProviderExclusionReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable")]
+#[serde(rename_all = "camelCase")]
+pub enum FiatProviderExclusionReason {
+    UnsupportedAsset,
+    UnsupportedCountry,
+    UnsupportedCurrency,
+    UnsupportedPaymentMethod,
+    AmountBelowMinimum,
+    AmountAboveMaximum,
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use crate::FiatPaymentMethod;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[typeshare()]
 #[serde(rename_all = "camelCase")]
@@ -11,4 +13,17 @@ pub struct FiatBuyRequest {
     pub fiat_currency: String,
     pub fiat_amount: f64,
     pub wallet_address: String,
+    pub payment_method: Option<FiatPaymentMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare()]
+#[serde(rename_all = "camelCase")]
+pub struct FiatSellRequest {
+    pub asset_id: String,
+    #[typeshare(skip)]
+    pub ip_address: String,
+    pub fiat_currency: String,
+    pub crypto_amount: f64,
+    pub wallet_address: String,
 }
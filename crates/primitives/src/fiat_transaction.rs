@@ -1,15 +1,18 @@
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, EnumString};
+use typeshare::typeshare;
 
 use crate::AssetId;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[typeshare(swift = "Equatable, Codable")]
 #[serde(rename_all = "camelCase")]
 pub struct FiatTransaction {
     pub asset_id: Option<AssetId>,
     pub provider_id: String,
     pub provider_transaction_id: String,
     pub status: FiatTransactionStatus,
+    pub transaction_type: FiatTransactionType,
     pub symbol: String,
     pub fiat_amount: f64,
     pub fiat_currency: String,
@@ -20,7 +23,8 @@ pub struct FiatTransaction {
     pub fee_partner: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, AsRefStr, EnumString)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, AsRefStr, EnumString)]
+#[typeshare(swift = "Equatable, Codable")]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
 pub enum FiatTransactionStatus {
@@ -29,3 +33,12 @@ pub enum FiatTransactionStatus {
     Failed,
     Unknown,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, AsRefStr, EnumString)]
+#[typeshare(swift = "Equatable, Codable")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum FiatTransactionType {
+    Buy,
+    Sell,
+}
@@ -13,50 +13,82 @@ pub use self::name::NameProvider;
 pub mod node;
 pub mod price;
 pub use self::price::{Price, PriceFull};
+pub mod price_alert;
+pub use self::price_alert::{PriceAlert, PriceAlertType};
 pub mod asset;
 pub mod config;
 pub mod currency;
 pub use self::asset::Asset;
 pub mod asset_id;
 pub use self::asset_id::AssetId;
+pub mod asset_balance;
+pub use self::asset_balance::{AddressBalances, AssetBalance};
 pub mod asset_score;
 pub use self::asset_score::AssetScore;
 pub mod asset_type;
 pub use self::asset_type::{AssetSubtype, AssetType};
 pub mod asset_price;
-pub use self::asset_price::{AssetMarket, AssetPrice, ChartPeriod, ChartValue, Charts};
+pub use self::asset_price::{
+    AssetMarket, AssetPrice, ChartCandle, ChartPeriod, ChartValue, Charts,
+};
 pub mod asset_details;
 pub use self::asset_details::{AssetDetails, AssetFull, AssetLinks, AssetMarketPrice};
 pub mod fiat_assets;
 pub mod fiat_quote;
-pub use self::fiat_quote::FiatQuote;
+pub use self::fiat_quote::{
+    FiatProviderExclusion, FiatProviderExclusionReason, FiatQuote, FiatQuotes,
+};
+pub mod fiat_payment_method;
+pub use self::fiat_payment_method::FiatPaymentMethod;
 pub mod fiat_transaction;
-pub use self::fiat_transaction::{FiatTransaction, FiatTransactionStatus};
+pub use self::fiat_transaction::{FiatTransaction, FiatTransactionStatus, FiatTransactionType};
 pub mod tokenlist;
 pub use self::fiat_assets::FiatAsset;
 pub use self::fiat_assets::FiatAssets;
 pub mod fiat_provider;
 pub use self::fiat_provider::FiatProviderName;
 pub mod fiat_quote_request;
-pub use self::fiat_quote_request::FiatBuyRequest;
+pub use self::fiat_quote_request::{FiatBuyRequest, FiatSellRequest};
 pub mod fiat_rate;
 pub use self::fiat_rate::FiatRate;
 pub mod platform;
 pub use self::platform::Platform;
+pub mod portfolio;
+pub use self::portfolio::{Portfolio, PortfolioAsset};
 pub mod device;
-pub use self::device::Device;
+pub use self::device::{Device, DevicePublicKey};
 pub mod transaction;
 pub use self::transaction::Transaction;
 pub use self::transaction::TransactionsFetchOption;
+pub use self::transaction::{
+    TransactionPendingRequest, TransactionStreamEvent, TransactionsCursor, TransactionsPage,
+};
+pub mod transactions_export;
+pub use self::transactions_export::TransactionsExportFormat;
 pub mod transaction_type;
 pub use self::transaction_type::TransactionType;
 pub mod transaction_state;
 pub use self::transaction_state::TransactionState;
+pub mod fee_rate;
+pub use self::fee_rate::{FeeEstimate, FeePriority, FeeRate};
+pub mod transaction_simulation;
+pub use self::transaction_simulation::{
+    SimulationApproval, SimulationBalanceChange, SimulationWarning, SimulationWarningType,
+    TransactionSimulation, TransactionSimulationRequest,
+};
+pub mod transaction_broadcast;
+pub use self::transaction_broadcast::{
+    TransactionBroadcast, TransactionBroadcastError, TransactionBroadcastErrorCode,
+    TransactionBroadcastResult,
+};
 pub mod transaction_direction;
 pub use self::transaction_direction::TransactionDirection;
 pub mod subscription;
 pub mod transaction_utxo;
-pub use self::subscription::Subscription;
+pub use self::subscription::{
+    Subscription, SubscriptionPreferences, SubscriptionProof, SubscriptionsSync,
+    SubscriptionsSyncResult,
+};
 pub mod big_int_hex;
 pub use self::big_int_hex::BigIntHex;
 pub use self::big_int_hex::BigIntValue;
@@ -66,16 +98,19 @@ pub mod utxo;
 pub use self::utxo::UTXO;
 pub mod push_notification;
 pub use self::push_notification::PushNotification;
+pub use self::push_notification::PushNotificationDeviceKeyChallenge;
+pub use self::push_notification::PushNotificationFiatTransaction;
+pub use self::push_notification::PushNotificationPriceAlert;
 pub use self::push_notification::PushNotificationTypes;
 pub mod scan;
 pub use self::scan::ScanAddress;
 pub mod swap;
 pub use self::swap::{
-    SwapMode, SwapProvider, SwapQuote, SwapQuoteData, SwapQuoteProtocolRequest, SwapQuoteRequest,
-    SwapQuoteResult,
+    SwapApprovalData, SwapMode, SwapProvider, SwapQuote, SwapQuoteData, SwapQuoteProtocolRequest,
+    SwapQuoteRequest, SwapQuoteResult,
 };
 pub mod transaction_metadata_types;
-pub use self::transaction_metadata_types::TransactionSwapMetadata;
+pub use self::transaction_metadata_types::{TransactionFiatMetadata, TransactionSwapMetadata};
 pub mod number_formatter;
 pub use self::number_formatter::NumberFormatter;
 pub mod wallet_connect;
@@ -15,6 +15,13 @@ impl NumberFormatter {
         let decimal = Self::big_decimal_value(value, decimals as u32)?;
         Some(decimal.to_string())
     }
+
+    pub fn value_from_amount(amount: &str, decimals: u32) -> Option<String> {
+        let mut decimal = BigDecimal::from_str(amount).ok()?;
+        let exp = BigInt::from(10).pow(decimals);
+        decimal = (decimal * BigDecimal::from(exp)).with_scale(0);
+        Some(decimal.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -54,4 +61,19 @@ mod tests {
         let result = NumberFormatter::value("1640000000000000", 18).unwrap();
         assert_eq!(result, "0.00164");
     }
+
+    #[test]
+    fn test_value_from_amount() {
+        let result = NumberFormatter::value_from_amount("123.456", 3).unwrap();
+        assert_eq!(result, "123456");
+
+        let result = NumberFormatter::value_from_amount("0.00164", 18).unwrap();
+        assert_eq!(result, "1640000000000000");
+
+        let result = NumberFormatter::value_from_amount("1.23456789", 6).unwrap();
+        assert_eq!(result, "1234567");
+
+        let result = NumberFormatter::value_from_amount("abc", 2);
+        assert_eq!(result, None);
+    }
 }
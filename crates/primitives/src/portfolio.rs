@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::ChartValue;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Portfolio {
+    pub currency: String,
+    pub values: Vec<ChartValue>,
+    pub assets: Vec<PortfolioAsset>,
+}
+
+// balances are reconstructed from indexed transactions, values are in the portfolio currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioAsset {
+    pub asset_id: String,
+    pub balance: f64,
+    pub value: f64,
+    pub cost_basis: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+}
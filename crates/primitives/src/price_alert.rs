@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+use typeshare::typeshare;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, AsRefStr, EnumString)]
+#[typeshare(swift = "Equatable, Codable, CaseIterable")]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum PriceAlertType {
+    PriceAbove,
+    PriceBelow,
+    PricePercentChange24h,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable, Hashable")]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAlert {
+    pub asset_id: String,
+    pub alert_type: PriceAlertType,
+    // price in USD for PriceAbove and PriceBelow, absolute percent for PricePercentChange24h
+    pub value: f64,
+}
@@ -1,4 +1,4 @@
-use crate::Transaction;
+use crate::{FiatTransaction, PriceAlert, Transaction};
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
@@ -7,6 +7,9 @@ use typeshare::typeshare;
 #[serde(rename_all = "lowercase")]
 pub enum PushNotificationTypes {
     Transaction,
+    FiatTransaction,
+    PriceAlert,
+    DeviceKeyChallenge,
 }
 
 #[typeshare(swift = "Equatable, Codable")]
@@ -16,3 +19,27 @@ pub struct PushNotification {
     pub notification_type: PushNotificationTypes,
     pub data: Transaction,
 }
+
+#[typeshare(swift = "Equatable, Codable")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushNotificationFiatTransaction {
+    #[serde(rename = "type")]
+    pub notification_type: PushNotificationTypes,
+    pub data: FiatTransaction,
+}
+
+#[typeshare(swift = "Equatable, Codable")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushNotificationPriceAlert {
+    #[serde(rename = "type")]
+    pub notification_type: PushNotificationTypes,
+    pub data: PriceAlert,
+}
+
+#[typeshare(swift = "Equatable, Codable")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushNotificationDeviceKeyChallenge {
+    #[serde(rename = "type")]
+    pub notification_type: PushNotificationTypes,
+    pub data: String,
+}
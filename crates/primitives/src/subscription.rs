@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
 use crate::chain::Chain;
+use crate::{AssetId, TransactionDirection};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[typeshare(swift = "Equatable, Codable, Hashable")]
@@ -9,4 +10,125 @@ pub struct Subscription {
     pub wallet_index: i32,
     pub chain: Chain,
     pub address: String,
+    // subscriptions without a proof are accepted but rate limited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<SubscriptionProof>,
+    // stored preferences are kept when none are sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferences: Option<SubscriptionPreferences>,
+}
+
+// full desired set of subscriptions, based on the last synced version
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable, Hashable")]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionsSync {
+    pub version: i32,
+    pub subscriptions: Vec<Subscription>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable, Hashable")]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionsSyncResult {
+    pub version: i32,
+    pub added: i32,
+    pub removed: i32,
+    pub subscriptions: Vec<Subscription>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable, Hashable")]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionPreferences {
+    // address is watched, not controlled by the wallet
+    pub is_watch_only: bool,
+    pub mute_incoming: bool,
+    pub mute_outgoing: bool,
+    // minimum transfer amount in asset units
+    pub min_amount: Option<f64>,
+    // token asset ids to notify about, empty notifies about every token
+    pub asset_ids: Vec<String>,
+}
+
+impl SubscriptionPreferences {
+    pub fn is_direction_enabled(&self, direction: &TransactionDirection) -> bool {
+        match direction {
+            TransactionDirection::Incoming => !self.mute_incoming,
+            TransactionDirection::Outgoing => !self.mute_outgoing,
+            TransactionDirection::SelfTransfer => !(self.mute_incoming && self.mute_outgoing),
+        }
+    }
+
+    pub fn is_asset_enabled(&self, asset_id: &AssetId) -> bool {
+        asset_id.token_id.is_none()
+            || self.asset_ids.is_empty()
+            || self.asset_ids.contains(&asset_id.to_string())
+    }
+
+    pub fn is_amount_enabled(&self, amount: f64) -> bool {
+        match self.min_amount {
+            Some(min_amount) => amount >= min_amount,
+            None => true,
+        }
+    }
+}
+
+// signature of the subscription message with the key of the subscribed address
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable, Hashable")]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionProof {
+    // hex for evm chains, base64 for every other chain
+    pub signature: String,
+    // base64, needed when the key can not be recovered from the signature or the address
+    pub public_key: Option<String>,
+    // full signed message, for wallets that wrap the subscription message
+    pub message: Option<String>,
+    // ton connect proofs, the domain and time the wallet signed and its base64 state init
+    pub domain: Option<String>,
+    pub timestamp: Option<i64>,
+    pub state_init: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_direction_enabled() {
+        let preferences = SubscriptionPreferences {
+            mute_incoming: true,
+            ..SubscriptionPreferences::default()
+        };
+        assert!(!preferences.is_direction_enabled(&TransactionDirection::Incoming));
+        assert!(preferences.is_direction_enabled(&TransactionDirection::Outgoing));
+        assert!(preferences.is_direction_enabled(&TransactionDirection::SelfTransfer));
+    }
+
+    #[test]
+    fn test_is_asset_enabled() {
+        let token = AssetId::from(
+            Chain::Ethereum,
+            Some("0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string()),
+        );
+        let preferences = SubscriptionPreferences {
+            asset_ids: vec!["ethereum_0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string()],
+            ..SubscriptionPreferences::default()
+        };
+        assert!(preferences.is_asset_enabled(&AssetId::from_chain(Chain::Ethereum)));
+        assert!(!preferences.is_asset_enabled(&token));
+        assert!(SubscriptionPreferences::default().is_asset_enabled(&token));
+    }
+
+    #[test]
+    fn test_is_amount_enabled() {
+        let preferences = SubscriptionPreferences {
+            min_amount: Some(0.5),
+            ..SubscriptionPreferences::default()
+        };
+        assert!(preferences.is_amount_enabled(0.5));
+        assert!(!preferences.is_amount_enabled(0.1));
+        assert!(SubscriptionPreferences::default().is_amount_enabled(0.0));
+    }
 }
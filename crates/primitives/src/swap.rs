@@ -4,7 +4,7 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::{AssetId, ChainType};
+use crate::{erc2612::ERC2612PermitMessage, AssetId, ChainType};
 
 #[typeshare(swift = "Equatable, Codable")]
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -61,6 +61,7 @@ pub struct SwapQuote {
     pub fee_percent: f32,
     pub provider: SwapProvider,
     pub data: Option<SwapQuoteData>,
+    pub approval: Option<SwapApprovalData>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -96,3 +97,14 @@ impl SwapQuoteData {
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[typeshare(swift = "Equatable, Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct SwapApprovalData {
+    pub token: String,
+    pub spender: String,
+    pub value: String,
+    pub data: String,
+    pub permit: Option<ERC2612PermitMessage>,
+}
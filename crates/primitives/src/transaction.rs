@@ -16,6 +16,98 @@ pub struct TransactionsFetchOption {
     pub wallet_index: i32,
     pub asset_id: Option<String>,
     pub from_timestamp: Option<u32>,
+    pub to_timestamp: Option<u32>,
+    pub transaction_type: Option<TransactionType>,
+    pub direction: Option<TransactionDirection>,
+    pub state: Option<TransactionState>,
+    // counterparty, matched against from and to
+    pub address: Option<String>,
+    // inclusive bounds in base units of the transaction asset
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl TransactionsFetchOption {
+    pub fn new(wallet_index: i32) -> Self {
+        Self {
+            wallet_index,
+            asset_id: None,
+            from_timestamp: None,
+            to_timestamp: None,
+            transaction_type: None,
+            direction: None,
+            state: None,
+            address: None,
+            min_value: None,
+            max_value: None,
+            cursor: None,
+            limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsPage {
+    pub transactions: Vec<Transaction>,
+    // absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+// a just broadcasted transaction to track, details are loaded from the node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionPendingRequest {
+    pub chain: Chain,
+    pub hash: String,
+    pub from: String,
+}
+
+// position after a transaction, ordered by a timestamp and id.
+// pages use created_at, streams use the storage time so updated transactions are resumed too
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionsCursor {
+    pub timestamp: i64,
+    pub id: String,
+}
+
+impl TransactionsCursor {
+    pub fn new(timestamp: DateTime<Utc>, id: String) -> Self {
+        Self {
+            timestamp: timestamp.timestamp_micros(),
+            id,
+        }
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let (timestamp, id) = value.split_once('_')?;
+        if id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            timestamp: timestamp.parse().ok()?,
+            id: id.to_string(),
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.timestamp, self.id)
+    }
+
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_micros(self.timestamp)
+    }
+}
+
+// published by the parser once a transaction is stored, cursor is in storage order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionStreamEvent {
+    pub cursor: String,
+    pub transaction: Transaction,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -297,3 +389,23 @@ impl Transaction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transactions_cursor() {
+        let created_at = DateTime::from_timestamp_micros(1718000000123456).unwrap();
+        let cursor = TransactionsCursor::new(created_at, "ethereum_0x1".to_string());
+
+        assert_eq!(cursor.encode(), "1718000000123456_ethereum_0x1");
+        assert_eq!(
+            TransactionsCursor::decode(&cursor.encode()),
+            Some(cursor.clone())
+        );
+        assert_eq!(cursor.datetime(), Some(created_at));
+        assert_eq!(TransactionsCursor::decode("ethereum_0x1"), None);
+        assert_eq!(TransactionsCursor::decode("1718000000123456_"), None);
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString};
+use typeshare::typeshare;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBroadcast {
+    // signed payload in the chain's wire encoding (hex, base64 or json)
+    pub data: String,
+    // optional sender address, used to register the hash for tracking
+    pub from: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBroadcastResult {
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBroadcastError {
+    pub code: TransactionBroadcastErrorCode,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumString, AsRefStr, PartialEq)]
+#[typeshare(swift = "Equatable, Codable, CaseIterable")]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum TransactionBroadcastErrorCode {
+    InsufficientFunds,
+    FeeTooLow,
+    InvalidNonce,
+    AlreadyKnown,
+    Expired,
+    InvalidSignature,
+    InvalidTransaction,
+    NodeUnavailable,
+    Unknown,
+}
+
+impl TransactionBroadcastErrorCode {
+    // node error messages differ per chain and client, match on known fragments.
+    // only for rejections, transport and http status errors are classified before
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let matches = |values: &[&str]| values.iter().any(|x| message.contains(x));
+
+        if matches(&[
+            "error sending request",
+            "error decoding response body",
+            "connection refused",
+            "timed out",
+            "request timeout",
+            "networking or low-level protocol error",
+            "service unavailable",
+            "bad gateway",
+            "too many requests",
+        ]) {
+            Self::NodeUnavailable
+        } else if matches(&[
+            "already known",
+            "already in mempool",
+            "already exists",
+            "already processed",
+            "alreadyprocessed",
+            "tefalready",
+        ]) {
+            Self::AlreadyKnown
+        } else if matches(&[
+            "underpriced",
+            "fee too low",
+            "min relay fee",
+            "insufficient fee",
+            "less than block base fee",
+            "gas price too low",
+            "telinsuf_fee_p",
+        ]) {
+            Self::FeeTooLow
+        } else if matches(&[
+            "insufficient funds",
+            "insufficient balance",
+            "insufficient lamports",
+            "insufficientfundsforfee",
+            "not enough balance",
+            "balance is not sufficient",
+            "tecunfunded",
+        ]) {
+            Self::InsufficientFunds
+        } else if matches(&[
+            "nonce too low",
+            "nonce too high",
+            "sequence mismatch",
+            "sequence_number_too_old",
+            "missingorspent",
+            "missing inputs",
+            "tefpast_seq",
+        ]) {
+            Self::InvalidNonce
+        } else if matches(&["blockhash not found", "expired", "tefmax_ledger"]) {
+            Self::Expired
+        } else if matches(&[
+            "invalid signature",
+            "signature verification failed",
+            "signature failure",
+            "sigverify",
+            "bad signature",
+        ]) {
+            Self::InvalidSignature
+        } else if matches(&[
+            "invalid transaction",
+            "failed to deserialize",
+            "failed to decode",
+            "could not be decoded",
+            "malformed",
+            "rlp:",
+            "invalid params",
+        ]) {
+            Self::InvalidTransaction
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_message() {
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message(
+                "insufficient funds for gas * price + value"
+            ),
+            TransactionBroadcastErrorCode::InsufficientFunds
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message("replacement transaction underpriced"),
+            TransactionBroadcastErrorCode::FeeTooLow
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message("nonce too low"),
+            TransactionBroadcastErrorCode::InvalidNonce
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message("bad-txns-inputs-missingorspent"),
+            TransactionBroadcastErrorCode::InvalidNonce
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message("already known"),
+            TransactionBroadcastErrorCode::AlreadyKnown
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message(
+                "Transaction simulation failed: Blockhash not found"
+            ),
+            TransactionBroadcastErrorCode::Expired
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message(
+                "error sending request for url (https://node)"
+            ),
+            TransactionBroadcastErrorCode::NodeUnavailable
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message(
+                "error decoding response body: expected value at line 1 column 1"
+            ),
+            TransactionBroadcastErrorCode::NodeUnavailable
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message("rlp: expected input list"),
+            TransactionBroadcastErrorCode::InvalidTransaction
+        );
+        assert_eq!(
+            TransactionBroadcastErrorCode::from_message("something else"),
+            TransactionBroadcastErrorCode::Unknown
+        );
+    }
+}
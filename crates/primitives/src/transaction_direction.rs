@@ -10,3 +10,14 @@ pub enum TransactionDirection {
     Outgoing,
     Incoming,
 }
+
+impl TransactionDirection {
+    pub fn new(value: &str) -> Option<Self> {
+        match value {
+            "self" => Some(Self::SelfTransfer),
+            "outgoing" => Some(Self::Outgoing),
+            "incoming" => Some(Self::Incoming),
+            _ => None,
+        }
+    }
+}
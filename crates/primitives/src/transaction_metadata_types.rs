@@ -12,3 +12,16 @@ pub struct TransactionSwapMetadata {
     pub to_asset: AssetId,
     pub to_value: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFiatMetadata {
+    pub provider_id: String,
+    pub provider_transaction_id: String,
+    pub fiat_amount: f64,
+    pub fiat_currency: String,
+    pub fee_provider: f64,
+    pub fee_network: f64,
+    pub fee_partner: f64,
+}
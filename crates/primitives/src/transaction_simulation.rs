@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString};
+use typeshare::typeshare;
+
+use crate::{AssetId, Chain};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulationRequest {
+    pub chain: Chain,
+    pub from: String,
+    // evm only, recipient or contract
+    pub to: Option<String>,
+    // evm only, native value in wei
+    pub value: Option<String>,
+    // evm: hex call data, solana: base64 unsigned transaction
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulation {
+    pub success: bool,
+    pub error: Option<String>,
+    // changes for the sender, negative values are outgoing. network fees are only included on solana
+    pub balance_changes: Vec<SimulationBalanceChange>,
+    pub approvals: Vec<SimulationApproval>,
+    // addresses receiving assets or approvals
+    pub counterparties: Vec<String>,
+    // contract called by the transaction
+    pub contract: Option<String>,
+    pub warnings: Vec<SimulationWarning>,
+}
+
+impl TransactionSimulation {
+    pub fn failed(error: String) -> Self {
+        Self {
+            success: false,
+            error: Some(error),
+            balance_changes: vec![],
+            approvals: vec![],
+            counterparties: vec![],
+            contract: None,
+            warnings: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationBalanceChange {
+    pub asset_id: AssetId,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationApproval {
+    pub asset_id: AssetId,
+    pub spender: String,
+    // amount, token id for a single nft, empty for all nfts of a collection
+    pub value: String,
+    pub is_unlimited: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumString, AsRefStr, PartialEq)]
+#[typeshare(swift = "Equatable, Codable, CaseIterable")]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum SimulationWarningType {
+    SimulationFailed,
+    UnlimitedApproval,
+    FraudulentAddress,
+    UnverifiedContract,
+    // the node could not trace the call, only that it does not revert is known.
+    // balance changes and approvals can be incomplete
+    SimulationUnavailable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare(swift = "Codable")]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationWarning {
+    pub warning_type: SimulationWarningType,
+    pub address: Option<String>,
+}
@@ -1,4 +1,4 @@
-use std::env;
+use std::{collections::HashMap, env};
 
 use config::{Config, ConfigError, Environment, File};
 use serde::Deserialize;
@@ -17,6 +17,7 @@ pub struct Settings {
     pub banxa: Banxa,
     pub kado: Kado,
     pub coingecko: CoinGecko,
+    pub coinmarketcap: CoinMarketCap,
     pub pricer: Pricer,
     pub charter: Charter,
     pub name: Name,
@@ -27,12 +28,27 @@ pub struct Settings {
     pub daemon: Daemon,
     pub pusher: Pusher,
     pub swap: Swap,
+    pub api: Api,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Fiat {
     pub timeout: u64,
+    pub geoip: FiatGeoIP,
+    pub routing: FiatRouting,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct FiatGeoIP {
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct FiatRouting {
+    pub ttl: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -64,24 +80,28 @@ pub struct Key {
 #[allow(unused)]
 pub struct MoonPay {
     pub key: Key,
+    pub webhook: KeySecret,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Transak {
     pub key: KeyPublic,
+    pub webhook: KeySecret,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Mercuryo {
     pub key: Key,
+    pub webhook: KeySecret,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Ramp {
     pub key: KeyPublic,
+    pub webhook: KeyPublic,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -95,6 +115,14 @@ pub struct Banxa {
 #[allow(unused)]
 pub struct Kado {
     pub key: KeySecret,
+    pub webhook: KadoWebhook,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct KadoWebhook {
+    // kado webhooks carry no signature, accepting them is an explicit opt in
+    pub unverified: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -103,17 +131,48 @@ pub struct CoinGecko {
     pub key: KeySecret,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct CoinMarketCap {
+    pub key: KeySecret,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Pricer {
     pub timer: u64,
     pub outdated: u64,
+    pub sources: PricerSources,
+    pub alerts: PricerAlerts,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct PricerSources {
+    pub priority: Vec<String>,
+    pub max_age: u64,
+    pub max_deviation: f64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct PricerAlerts {
+    pub cooldown: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Charter {
     pub timer: u64,
+    pub backfill: CharterBackfill,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct CharterBackfill {
+    pub days: u64,
+    pub limit: usize,
+    pub retry: i64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -208,6 +267,15 @@ pub struct Chain {
 pub struct Parser {
     pub timeout: u64,
     pub retry: u64,
+    pub pending: ParserPending,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct ParserPending {
+    pub timeout: u64,
+    // dropped transactions are marked as failed after it
+    pub expiry: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -216,6 +284,45 @@ pub struct Daemon {
     pub service: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct Api {
+    pub device_auth: ApiDeviceAuth,
+    pub subscriptions: ApiSubscriptions,
+    pub fees: ApiFees,
+    pub simulation: ApiSimulation,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct ApiDeviceAuth {
+    // devices registered without a public key are rejected once required
+    pub required: bool,
+    pub window: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct ApiSubscriptions {
+    // subscriptions added without an ownership proof within the window
+    pub unverified_limit: i64,
+    pub unverified_window: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct ApiFees {
+    pub ttl: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct ApiSimulation {
+    // chain to node url with debug_traceCall enabled, chains without it use the default node
+    #[serde(default)]
+    pub nodes: HashMap<String, String>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[allow(unused)]
 pub struct Pusher {
@@ -236,6 +343,22 @@ pub struct Swap {
     pub jupiter: SwapProvider,
     pub thorchain: SwapProvider,
     pub aftermath: SwapProvider,
+    pub cache: SwapCache,
+    pub limit: SwapLimit,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct SwapCache {
+    pub ttl: i64,
+    pub stale_ttl: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(unused)]
+pub struct SwapLimit {
+    pub capacity: u32,
+    pub refill: f64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -1,14 +1,19 @@
 use core::str;
+use std::time::Duration;
 
 use gem_chain_rpc::{
-    AptosClient, BitcoinClient, ChainProvider, CosmosClient, EthereumClient, NearClient,
-    SolanaClient, SuiClient, TonClient, TronClient, XRPClient,
+    AptosClient, BalanceProvider, BitcoinClient, ChainProvider, CosmosClient, EthereumClient,
+    FeeProvider, NearClient, SimulationProvider, SolanaClient, SuiClient, TonClient,
+    TransactionBroadcastProvider, TransactionStateProvider, TronClient, XRPClient,
 };
-use primitives::Chain;
+use primitives::{Chain, ChainType};
 use reqwest_middleware::ClientBuilder;
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use settings::Settings;
 
+// a node that does not answer in time is skipped for the next one
+const BROADCAST_TIMEOUT: Duration = Duration::from_secs(15);
+
 pub struct ProviderFactory {}
 
 impl ProviderFactory {
@@ -61,6 +66,103 @@ impl ProviderFactory {
         }
     }
 
+    pub fn new_balance_provider(chain: Chain, url: &str) -> Box<dyn BalanceProvider> {
+        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(5);
+        let client = ClientBuilder::new(reqwest::Client::new())
+            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+            .build();
+        let url = url.to_string();
+
+        match chain.chain_type() {
+            ChainType::Bitcoin => Box::new(BitcoinClient::new(chain, client, url)),
+            ChainType::Ethereum => Box::new(EthereumClient::new(chain, url)),
+            ChainType::Cosmos => Box::new(CosmosClient::new(chain, client, url)),
+            ChainType::Solana => Box::new(SolanaClient::new(url)),
+            ChainType::Ton => Box::new(TonClient::new(client, url)),
+            ChainType::Tron => Box::new(TronClient::new(client, url)),
+            ChainType::Aptos => Box::new(AptosClient::new(client, url)),
+            ChainType::Sui => Box::new(SuiClient::new(url)),
+            ChainType::Xrp => Box::new(XRPClient::new(client, url)),
+            ChainType::Near => Box::new(NearClient::new(url)),
+        }
+    }
+
+    // chains with dynamic fees, other chains use fixed fees in the app
+    pub fn new_fee_provider(chain: Chain, url: &str) -> Option<Box<dyn FeeProvider>> {
+        let client = ClientBuilder::new(reqwest::Client::new()).build();
+        let url = url.to_string();
+
+        match chain.chain_type() {
+            ChainType::Bitcoin => Some(Box::new(BitcoinClient::new(chain, client, url))),
+            ChainType::Ethereum => Some(Box::new(EthereumClient::new(chain, url))),
+            ChainType::Cosmos if CosmosClient::default_gas_price(chain).is_some() => {
+                Some(Box::new(CosmosClient::new(chain, client, url)))
+            }
+            ChainType::Solana => Some(Box::new(SolanaClient::new(url))),
+            _ => None,
+        }
+    }
+
+    // evm simulations need a node with the debug namespace, see api.simulation.nodes
+    pub fn new_simulation_provider(chain: Chain, url: &str) -> Option<Box<dyn SimulationProvider>> {
+        let url = url.to_string();
+
+        match chain.chain_type() {
+            ChainType::Ethereum => Some(Box::new(EthereumClient::new(chain, url))),
+            ChainType::Solana => Some(Box::new(SolanaClient::new(url))),
+            _ => None,
+        }
+    }
+
+    pub fn simulation_url(chain: Chain, settings: &Settings) -> String {
+        settings
+            .api
+            .simulation
+            .nodes
+            .get(chain.as_ref())
+            .cloned()
+            .unwrap_or_else(|| Self::url(chain, settings).to_string())
+    }
+
+    // no retry middleware, a signed payload is relayed to the next node instead
+    pub fn new_broadcast_provider(
+        chain: Chain,
+        url: &str,
+    ) -> Box<dyn TransactionBroadcastProvider> {
+        let client = reqwest::Client::builder()
+            .timeout(BROADCAST_TIMEOUT)
+            .build()
+            .unwrap();
+        let client = ClientBuilder::new(client).build();
+        let url = url.to_string();
+
+        match chain.chain_type() {
+            ChainType::Bitcoin => Box::new(BitcoinClient::new(chain, client, url)),
+            ChainType::Ethereum => Box::new(EthereumClient::new(chain, url)),
+            ChainType::Cosmos => Box::new(CosmosClient::new(chain, client, url)),
+            ChainType::Solana => Box::new(SolanaClient::new(url)),
+            ChainType::Ton => Box::new(TonClient::new(client, url)),
+            ChainType::Tron => Box::new(TronClient::new(client, url)),
+            ChainType::Aptos => Box::new(AptosClient::new(client, url)),
+            ChainType::Sui => Box::new(SuiClient::new(url)),
+            ChainType::Xrp => Box::new(XRPClient::new(client, url)),
+            ChainType::Near => Box::new(NearClient::new(url)),
+        }
+    }
+
+    // chains without one can not register pending transactions. solana nodes only return
+    // transactions once confirmed, the parser picks them up right after
+    pub fn new_transaction_state_provider(
+        chain: Chain,
+        url: &str,
+    ) -> Option<Box<dyn TransactionStateProvider>> {
+        let url = url.to_string();
+        match chain.chain_type() {
+            ChainType::Ethereum => Some(Box::new(EthereumClient::new(chain, url))),
+            _ => None,
+        }
+    }
+
     pub fn url(chain: Chain, settings: &Settings) -> &str {
         match chain {
             Chain::Bitcoin => settings.chains.bitcoin.url.as_str(),
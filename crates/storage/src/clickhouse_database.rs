@@ -1,13 +1,20 @@
 use clickhouse::{error::Result, Client};
 
-use crate::models::{CreateChart, GetChart};
+use crate::models::{ChartClose, ChartCoverage, CreateChart, CreateFiatRate, GetChart};
 
 pub struct ClickhouseDatabase {
     client: Client,
 }
 
 pub const CREATE_TABLES: &str = include_str!("./clickhouse_migration.sql");
+pub const MIGRATE_CHARTS_REPLACING: &str =
+    include_str!("./clickhouse_migration_charts_replacing.sql");
 pub const CHARTS_TABLE_NAME: &str = "charts";
+pub const CHARTS_HOURLY_TABLE_NAME: &str = "charts_hourly";
+pub const CHARTS_DAILY_TABLE_NAME: &str = "charts_daily";
+pub const FIAT_RATES_TABLE_NAME: &str = "fiat_rates";
+// rates are rolled up to daily values, look back far enough to find one for the oldest point
+const FIAT_RATES_LOOKBACK_MINUTES: i32 = 2 * 1440;
 
 //TODO: Migrate to storage crate
 impl ClickhouseDatabase {
@@ -16,8 +23,41 @@ impl ClickhouseDatabase {
         Self { client }
     }
 
+    // clickhouse only accepts a single statement per query.
+    // ttl changes only apply to new merges instead of rewriting every part on startup
     pub async fn migrations(&self) -> Result<()> {
-        self.client.query(CREATE_TABLES).execute().await
+        let client = self
+            .client
+            .clone()
+            .with_option("materialize_ttl_after_modify", "0");
+        Self::execute(&client, CREATE_TABLES).await?;
+
+        // tables created before deduplication, views are recreated on the second run
+        if self.get_table_engine(CHARTS_TABLE_NAME).await?.as_deref() != Some("ReplacingMergeTree")
+        {
+            Self::execute(&client, MIGRATE_CHARTS_REPLACING).await?;
+            Self::execute(&client, CREATE_TABLES).await?;
+        }
+        Ok(())
+    }
+
+    async fn execute(client: &Client, statements: &str) -> Result<()> {
+        for statement in statements.split(';').filter(|x| !x.trim().is_empty()) {
+            client.query(statement).execute().await?;
+        }
+        Ok(())
+    }
+
+    async fn get_table_engine(&self, table: &str) -> Result<Option<String>> {
+        let engines = self
+            .client
+            .query(
+                "SELECT engine FROM system.tables WHERE database = currentDatabase() AND name = ?",
+            )
+            .bind(table)
+            .fetch_all::<String>()
+            .await?;
+        Ok(engines.into_iter().next())
     }
 
     pub async fn add_charts(&self, charts: Vec<CreateChart>) -> Result<usize> {
@@ -33,33 +73,206 @@ impl ClickhouseDatabase {
         Ok(charts.len())
     }
 
+    // days with at least one point per coin, today included
+    pub async fn get_charts_coverage(&self, days: u64) -> Result<Vec<ChartCoverage>> {
+        let vec = self
+            .client
+            .query(
+                "
+            SELECT
+                coin_id,
+                groupUniqArray(toUInt32(date)) as dates
+            FROM
+                charts_daily
+            WHERE
+                date >= subtractDays (toStartOfDay(now()), ?)
+            group BY coin_id
+        ",
+            )
+            .bind(days)
+            .fetch_all::<ChartCoverage>()
+            .await?;
+        Ok(vec)
+    }
+
+    pub async fn add_fiat_rates(&self, rates: Vec<CreateFiatRate>) -> Result<usize> {
+        let mut inserter = self
+            .client
+            .inserter(FIAT_RATES_TABLE_NAME)?
+            .with_max_entries(500);
+
+        for rate in rates.clone() {
+            inserter.write(&rate).await?;
+        }
+        inserter.end().await?;
+        Ok(rates.len())
+    }
+
+    // candles from raw points, used for short periods
     pub async fn get_charts(
         &self,
         coin_id: &str,
         period: &str,
         period_limit: i32,
+        currency: &str,
+        default_rate: f64,
     ) -> Result<Vec<GetChart>> {
-        let vec = self
-            .client
-            .query(
-                "
+        let candles = "
             SELECT
-                avg(price) as price,
-                toStartOfInterval(ts, INTERVAL ?) as date
+                ? as symbol,
+                toStartOfInterval(ts, INTERVAL ?) as interval_date,
+                toFloat64(argMin(price, ts)) as open,
+                toFloat64(max(price)) as high,
+                toFloat64(min(price)) as low,
+                toFloat64(argMax(price, ts)) as close,
+                toFloat64(argMax(market_cap, ts)) as market_cap,
+                toFloat64(argMax(volume, ts)) as volume
             FROM
                 charts
             WHERE
                 coin_id = ?
                 AND ts >= subtractMinutes (now(), ?)
-            group BY (coin_id, date)
-            ORDER BY date ASC
+            group BY (coin_id, interval_date)
+        ";
+        let vec = self
+            .client
+            .query(&Self::charts_in_currency_query(candles))
+            .bind(default_rate)
+            .bind(currency)
+            .bind(period)
+            .bind(coin_id)
+            .bind(period_limit)
+            .bind(currency)
+            .bind(period_limit + FIAT_RATES_LOOKBACK_MINUTES)
+            .fetch_all::<GetChart>()
+            .await?;
+        Ok(vec)
+    }
+
+    // candles merged from one of the aggregated tables, CHARTS_HOURLY_TABLE_NAME or CHARTS_DAILY_TABLE_NAME
+    pub async fn get_charts_aggregated(
+        &self,
+        table: &str,
+        coin_id: &str,
+        period: &str,
+        period_limit: i32,
+        currency: &str,
+        default_rate: f64,
+    ) -> Result<Vec<GetChart>> {
+        let candles = format!(
+            "
+            SELECT
+                ? as symbol,
+                toStartOfInterval(date, INTERVAL ?) as interval_date,
+                toFloat64(argMinMerge(open)) as open,
+                toFloat64(max(high)) as high,
+                toFloat64(min(low)) as low,
+                toFloat64(argMaxMerge(close)) as close,
+                toFloat64(argMaxMerge(market_cap)) as market_cap,
+                toFloat64(argMaxMerge(volume)) as volume
+            FROM
+                {}
+            WHERE
+                coin_id = ?
+                AND date >= subtractMinutes (now(), ?)
+            group BY (coin_id, interval_date)
         ",
-            )
+            table
+        );
+        let vec = self
+            .client
+            .query(&Self::charts_in_currency_query(&candles))
+            .bind(default_rate)
+            .bind(currency)
             .bind(period)
             .bind(coin_id)
             .bind(period_limit)
+            .bind(currency)
+            .bind(period_limit + FIAT_RATES_LOOKBACK_MINUTES)
             .fetch_all::<GetChart>()
             .await?;
         Ok(vec)
     }
+
+    // daily closes of every coin over the whole history, converted like get_charts_aggregated
+    pub async fn get_charts_daily_closes(
+        &self,
+        coin_ids: Vec<String>,
+        currency: &str,
+        default_rate: f64,
+    ) -> Result<Vec<ChartClose>> {
+        let query = format!(
+            "
+            SELECT
+                c.coin_id as coin_id,
+                c.date as date,
+                c.close * if(r.rate > 0, r.rate, ?) as close
+            FROM (
+                SELECT
+                    coin_id,
+                    ? as symbol,
+                    date,
+                    toFloat64(argMaxMerge(close)) as close
+                FROM
+                    {}
+                WHERE
+                    has(?, coin_id)
+                group BY (coin_id, date)
+            ) as c
+            ASOF LEFT JOIN (
+                SELECT symbol, ts, rate
+                FROM fiat_rates
+                WHERE symbol = ?
+            ) as r ON c.symbol = r.symbol AND c.date >= r.ts
+            ORDER BY coin_id, date ASC
+        ",
+            CHARTS_DAILY_TABLE_NAME
+        );
+        let vec = self
+            .client
+            .query(&query)
+            .bind(default_rate)
+            .bind(currency)
+            .bind(coin_ids)
+            .bind(currency)
+            .fetch_all::<ChartClose>()
+            .await?;
+        Ok(vec)
+    }
+
+    // converts every candle with the latest rate known at its timestamp,
+    // candles older than the first stored rate fall back to default_rate
+    fn charts_in_currency_query(candles: &str) -> String {
+        format!(
+            "
+            SELECT
+                candle_date as date,
+                candle_open * multiplier as open,
+                candle_high * multiplier as high,
+                candle_low * multiplier as low,
+                candle_close * multiplier as close,
+                candle_market_cap * multiplier as market_cap,
+                candle_volume * multiplier as volume
+            FROM (
+                SELECT
+                    c.interval_date as candle_date,
+                    c.open as candle_open,
+                    c.high as candle_high,
+                    c.low as candle_low,
+                    c.close as candle_close,
+                    c.market_cap as candle_market_cap,
+                    c.volume as candle_volume,
+                    if(r.rate > 0, r.rate, ?) as multiplier
+                FROM ({}) as c
+                ASOF LEFT JOIN (
+                    SELECT symbol, ts, rate
+                    FROM fiat_rates
+                    WHERE symbol = ? AND ts >= subtractMinutes (now(), ?)
+                ) as r ON c.symbol = r.symbol AND c.interval_date >= r.ts
+            )
+            ORDER BY date ASC
+        ",
+            candles
+        )
+    }
 }
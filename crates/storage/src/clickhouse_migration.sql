@@ -1,14 +1,129 @@
+-- points written more than once for the same coin and second are deduplicated on merge
 CREATE TABLE IF NOT EXISTS charts
 (
     coin_id            LowCardinality(String),
     price              Float32 CODEC(ZSTD(1)),
+    market_cap         Float32 CODEC(ZSTD(1)),
+    volume             Float32 CODEC(ZSTD(1)),
     ts                 DateTime CODEC(DoubleDelta, ZSTD(1))
 )
-ENGINE = MergeTree 
+ENGINE = ReplacingMergeTree
 PARTITION BY toYYYYMM(ts)
-ORDER BY (coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts))
-TTL 
-ts + INTERVAL 1 HOUR GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts) SET price = avg(price),
-ts + INTERVAL 1 DAY GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts) SET price = avg(price),
-ts + INTERVAL 1 WEEK GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts) SET price = avg(price),
-ts + INTERVAL 1 MONTH GROUP BY coin_id, toStartOfDay(ts) SET price = avg(price);
\ No newline at end of file
+ORDER BY (coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts), ts)
+TTL
+ts + INTERVAL 1 HOUR GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 DAY GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 WEEK GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 MONTH GROUP BY coin_id, toStartOfDay(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume);
+
+ALTER TABLE charts ADD COLUMN IF NOT EXISTS market_cap Float32 CODEC(ZSTD(1)) AFTER price;
+
+ALTER TABLE charts ADD COLUMN IF NOT EXISTS volume Float32 CODEC(ZSTD(1)) AFTER market_cap;
+
+-- tables created before market caps and volumes were added only averaged the price, rolled up rows applied on next merges
+ALTER TABLE charts MODIFY TTL
+ts + INTERVAL 1 HOUR GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 DAY GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 WEEK GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 MONTH GROUP BY coin_id, toStartOfDay(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume);
+
+-- candles are aggregated on insert, raw points are averaged by the TTL above
+CREATE TABLE IF NOT EXISTS charts_hourly
+(
+    coin_id            LowCardinality(String),
+    date               DateTime CODEC(DoubleDelta, ZSTD(1)),
+    open               AggregateFunction(argMin, Float32, DateTime),
+    high               SimpleAggregateFunction(max, Float32),
+    low                SimpleAggregateFunction(min, Float32),
+    close              AggregateFunction(argMax, Float32, DateTime),
+    market_cap         AggregateFunction(argMax, Float32, DateTime),
+    volume             AggregateFunction(argMax, Float32, DateTime)
+)
+ENGINE = AggregatingMergeTree
+PARTITION BY toYYYYMM(date)
+ORDER BY (coin_id, date);
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS charts_hourly_mv TO charts_hourly AS
+SELECT
+    coin_id,
+    toStartOfHour(ts) AS date,
+    argMinState(price, ts) AS open,
+    max(price) AS high,
+    min(price) AS low,
+    argMaxState(price, ts) AS close,
+    argMaxState(market_cap, ts) AS market_cap,
+    argMaxState(volume, ts) AS volume
+FROM charts
+GROUP BY coin_id, date;
+
+-- backfill points written before the view existed. candle states are idempotent, so the hour overlapping
+-- the view is safely aggregated twice and later runs only reprocess that hour
+INSERT INTO charts_hourly
+SELECT
+    coin_id,
+    toStartOfHour(ts) AS date,
+    argMinState(price, ts) AS open,
+    max(price) AS high,
+    min(price) AS low,
+    argMaxState(price, ts) AS close,
+    argMaxState(market_cap, ts) AS market_cap,
+    argMaxState(volume, ts) AS volume
+FROM charts
+WHERE ts < (SELECT if(count() = 0, now(), min(date)) FROM charts_hourly) + INTERVAL 1 HOUR
+GROUP BY coin_id, date;
+
+CREATE TABLE IF NOT EXISTS charts_daily
+(
+    coin_id            LowCardinality(String),
+    date               DateTime CODEC(DoubleDelta, ZSTD(1)),
+    open               AggregateFunction(argMin, Float32, DateTime),
+    high               SimpleAggregateFunction(max, Float32),
+    low                SimpleAggregateFunction(min, Float32),
+    close              AggregateFunction(argMax, Float32, DateTime),
+    market_cap         AggregateFunction(argMax, Float32, DateTime),
+    volume             AggregateFunction(argMax, Float32, DateTime)
+)
+ENGINE = AggregatingMergeTree
+PARTITION BY toYear(date)
+ORDER BY (coin_id, date);
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS charts_daily_mv TO charts_daily AS
+SELECT
+    coin_id,
+    toDateTime(toStartOfDay(ts)) AS date,
+    argMinState(price, ts) AS open,
+    max(price) AS high,
+    min(price) AS low,
+    argMaxState(price, ts) AS close,
+    argMaxState(market_cap, ts) AS market_cap,
+    argMaxState(volume, ts) AS volume
+FROM charts
+GROUP BY coin_id, date;
+
+INSERT INTO charts_daily
+SELECT
+    coin_id,
+    toDateTime(toStartOfDay(ts)) AS date,
+    argMinState(price, ts) AS open,
+    max(price) AS high,
+    min(price) AS low,
+    argMaxState(price, ts) AS close,
+    argMaxState(market_cap, ts) AS market_cap,
+    argMaxState(volume, ts) AS volume
+FROM charts
+WHERE ts < (SELECT if(count() = 0, now(), min(date)) FROM charts_daily) + INTERVAL 1 DAY
+GROUP BY coin_id, date;
+
+-- rates are units of currency per 1 USD
+CREATE TABLE IF NOT EXISTS fiat_rates
+(
+    symbol             LowCardinality(String),
+    rate               Float64 CODEC(ZSTD(1)),
+    ts                 DateTime CODEC(DoubleDelta, ZSTD(1))
+)
+ENGINE = MergeTree
+PARTITION BY toYYYYMM(ts)
+ORDER BY (symbol, toStartOfDay(ts), toStartOfHour(ts))
+TTL
+ts + INTERVAL 1 DAY GROUP BY symbol, toStartOfDay(ts), toStartOfHour(ts) SET rate = avg(rate),
+ts + INTERVAL 1 MONTH GROUP BY symbol, toStartOfDay(ts) SET rate = avg(rate);
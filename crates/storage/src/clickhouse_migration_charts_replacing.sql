@@ -0,0 +1,31 @@
+-- charts created as a MergeTree kept every duplicate point, the engine can't be altered in place.
+-- views read from charts are dropped before the swap and recreated by the main migration
+DROP VIEW IF EXISTS charts_hourly_mv;
+
+DROP VIEW IF EXISTS charts_daily_mv;
+
+DROP TABLE IF EXISTS charts_replacing;
+
+CREATE TABLE charts_replacing
+(
+    coin_id            LowCardinality(String),
+    price              Float32 CODEC(ZSTD(1)),
+    market_cap         Float32 CODEC(ZSTD(1)),
+    volume             Float32 CODEC(ZSTD(1)),
+    ts                 DateTime CODEC(DoubleDelta, ZSTD(1))
+)
+ENGINE = ReplacingMergeTree
+PARTITION BY toYYYYMM(ts)
+ORDER BY (coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts), ts)
+TTL
+ts + INTERVAL 1 HOUR GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts), toStartOfFiveMinute(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 DAY GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts), toStartOfFifteenMinutes(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 WEEK GROUP BY coin_id, toStartOfDay(ts), toStartOfHour(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume),
+ts + INTERVAL 1 MONTH GROUP BY coin_id, toStartOfDay(ts) SET price = avg(price), market_cap = avg(market_cap), volume = avg(volume);
+
+INSERT INTO charts_replacing SELECT coin_id, price, market_cap, volume, ts FROM charts;
+
+-- atomic rename, requires the default Atomic database engine
+EXCHANGE TABLES charts AND charts_replacing;
+
+DROP TABLE charts_replacing;
@@ -3,15 +3,22 @@ use crate::models::*;
 use crate::schema::{devices, fiat_providers, prices_assets, transactions_addresses};
 use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use diesel::associations::HasTable;
+use diesel::dsl::sql;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
+use diesel::sql_types::{Array, Bool, Integer, Text};
 use diesel::{upsert::excluded, Connection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use primitives::chain::Chain;
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/migrations");
-use primitives::{AssetType, TransactionsFetchOption};
+use primitives::{
+    AssetType, TransactionDirection, TransactionState, TransactionsCursor, TransactionsFetchOption,
+};
 
-use self::price::PriceAsset;
+use self::price::{PriceAsset, PriceSource};
+
+const TRANSACTIONS_NUMERIC_VALUE: &str =
+    "CASE WHEN transactions.value ~ '^[0-9]+$' THEN CAST(transactions.value AS NUMERIC) END";
 
 pub struct DatabaseClient {
     connection: PgConnection,
@@ -30,6 +37,16 @@ impl DatabaseClient {
         nodes.select(Node::as_select()).load(&mut self.connection)
     }
 
+    pub fn get_nodes_active(&mut self, _chain: &str) -> Result<Vec<Node>, diesel::result::Error> {
+        use crate::schema::nodes::dsl::*;
+        nodes
+            .filter(chain.eq(_chain))
+            .filter(status.eq("active"))
+            .order(priority.desc())
+            .select(Node::as_select())
+            .load(&mut self.connection)
+    }
+
     pub fn get_nodes_version(&mut self) -> Result<i32, diesel::result::Error> {
         let version = self
             .get_nodes()?
@@ -116,6 +133,58 @@ impl DatabaseClient {
             .execute(&mut self.connection)
     }
 
+    pub fn get_fiat_transaction(
+        &mut self,
+        _provider_id: &str,
+        _provider_transaction_id: &str,
+    ) -> Result<GetFiatTransaction, diesel::result::Error> {
+        use crate::schema::fiat_transactions::dsl::*;
+        fiat_transactions
+            .filter(provider_id.eq(_provider_id))
+            .filter(provider_transaction_id.eq(_provider_transaction_id))
+            .select(GetFiatTransaction::as_select())
+            .first(&mut self.connection)
+    }
+
+    // fiat transactions with a known asset and address, not yet linked to an on-chain transaction
+    pub fn get_fiat_transactions_unlinked(
+        &mut self,
+        _status: &str,
+    ) -> Result<Vec<GetFiatTransaction>, diesel::result::Error> {
+        use crate::schema::fiat_transactions::dsl::*;
+        fiat_transactions
+            .filter(status.eq(_status))
+            .filter(transaction_id.is_null())
+            .filter(asset_id.is_not_null())
+            .filter(address.is_not_null())
+            .order(created_at.asc())
+            .select(GetFiatTransaction::as_select())
+            .load(&mut self.connection)
+    }
+
+    pub fn set_fiat_transaction_link(
+        &mut self,
+        fiat_transaction_id: i32,
+        _transaction_id: &str,
+        _metadata: serde_json::Value,
+    ) -> Result<bool, diesel::result::Error> {
+        self.connection
+            .build_transaction()
+            .read_write()
+            .run::<_, diesel::result::Error, _>(|conn: &mut PgConnection| {
+                use crate::schema::{fiat_transactions, transactions};
+                diesel::update(fiat_transactions::table.find(fiat_transaction_id))
+                    .set(fiat_transactions::transaction_id.eq(_transaction_id))
+                    .execute(conn)?;
+
+                diesel::update(transactions::table.find(_transaction_id))
+                    .set(transactions::metadata.eq(_metadata))
+                    .execute(conn)?;
+
+                Ok(true)
+            })
+    }
+
     pub fn get_fiat_assets(&mut self) -> Result<Vec<FiatAsset>, diesel::result::Error> {
         use crate::schema::fiat_assets::dsl::*;
         fiat_assets
@@ -191,6 +260,22 @@ impl DatabaseClient {
             .execute(&mut self.connection)
     }
 
+    pub fn set_prices_sources(
+        &mut self,
+        values: Vec<PriceSource>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::prices_sources::dsl::*;
+        diesel::insert_into(prices_sources)
+            .values(&values)
+            .on_conflict((price_id, source))
+            .do_update()
+            .set((
+                price.eq(excluded(price)),
+                last_updated_at.eq(excluded(last_updated_at)),
+            ))
+            .execute(&mut self.connection)
+    }
+
     pub fn get_prices(&mut self) -> Result<Vec<Price>, diesel::result::Error> {
         use crate::schema::prices::dsl::*;
         prices.select(Price::as_select()).load(&mut self.connection)
@@ -212,6 +297,17 @@ impl DatabaseClient {
             .first(&mut self.connection)
     }
 
+    pub fn get_prices_assets_for_asset_ids(
+        &mut self,
+        ids: Vec<String>,
+    ) -> Result<Vec<PriceAsset>, diesel::result::Error> {
+        use crate::schema::prices_assets::dsl::*;
+        prices_assets
+            .filter(asset_id.eq_any(ids))
+            .select(PriceAsset::as_select())
+            .load(&mut self.connection)
+    }
+
     pub fn get_prices_id_for_asset_id(
         &mut self,
         id: &str,
@@ -312,6 +408,43 @@ impl DatabaseClient {
             .get_result(&mut self.connection)
     }
 
+    pub fn get_device_public_key(
+        &mut self,
+        _device_id: &str,
+    ) -> Result<Option<String>, diesel::result::Error> {
+        use crate::schema::devices::dsl::*;
+        devices
+            .filter(device_id.eq(_device_id))
+            .select(public_key)
+            .first(&mut self.connection)
+    }
+
+    pub fn update_device_public_key(
+        &mut self,
+        _device_id: &str,
+        value: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::devices::dsl::*;
+        diesel::update(devices)
+            .filter(device_id.eq(_device_id))
+            .set(public_key.eq(value))
+            .execute(&mut self.connection)
+    }
+
+    // 0 when the device already has a key
+    pub fn bind_device_public_key(
+        &mut self,
+        _device_id: &str,
+        value: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::devices::dsl::*;
+        diesel::update(devices)
+            .filter(device_id.eq(_device_id))
+            .filter(public_key.is_null())
+            .set(public_key.eq(value))
+            .execute(&mut self.connection)
+    }
+
     pub fn delete_device(&mut self, _device_id: &str) -> Result<usize, diesel::result::Error> {
         use crate::schema::devices::dsl::*;
         diesel::delete(devices.filter(device_id.eq(_device_id))).execute(&mut self.connection)
@@ -421,18 +554,80 @@ impl DatabaseClient {
             .load(&mut self.connection)
     }
 
-    pub fn delete_subscription(
+    pub fn delete_subscriptions(
         &mut self,
-        subscription: Subscription,
+        values: Vec<Subscription>,
     ) -> Result<usize, diesel::result::Error> {
-        use crate::schema::subscriptions::dsl::*;
-        diesel::delete(
-            subscriptions
-                .filter(device_id.eq(subscription.device_id))
-                .filter(chain.eq(subscription.chain))
-                .filter(address.eq(subscription.address)),
+        Self::delete_subscriptions_by_key(&mut self.connection, values)
+    }
+
+    // single statement, diesel has no tuple IN so the keys are passed as parallel arrays
+    fn delete_subscriptions_by_key(
+        conn: &mut PgConnection,
+        values: Vec<Subscription>,
+    ) -> Result<usize, diesel::result::Error> {
+        if values.is_empty() {
+            return Ok(0);
+        }
+        diesel::sql_query(
+            "DELETE FROM subscriptions WHERE (device_id, wallet_index, chain, address) IN \
+             (SELECT * FROM UNNEST($1::integer[], $2::integer[], $3::text[], $4::text[]))",
         )
-        .execute(&mut self.connection)
+        .bind::<Array<Integer>, _>(values.iter().map(|x| x.device_id).collect::<Vec<_>>())
+        .bind::<Array<Integer>, _>(values.iter().map(|x| x.wallet_index).collect::<Vec<_>>())
+        .bind::<Array<Text>, _>(values.iter().map(|x| x.chain.clone()).collect::<Vec<_>>())
+        .bind::<Array<Text>, _>(values.iter().map(|x| x.address.clone()).collect::<Vec<_>>())
+        .execute(conn)
+    }
+
+    // replaces the subscriptions of a device with the desired set when the version matches,
+    // returns None when the device was synced in the meantime
+    pub fn sync_subscriptions(
+        &mut self,
+        _device_id: i32,
+        version: i32,
+        values: Vec<Subscription>,
+    ) -> Result<Option<SubscriptionsSync>, diesel::result::Error> {
+        self.connection
+            .build_transaction()
+            .read_write()
+            .run::<_, diesel::result::Error, _>(|conn: &mut PgConnection| {
+                use crate::schema::subscriptions::dsl::*;
+                let current_version = devices::table
+                    .find(_device_id)
+                    .select(devices::subscriptions_version)
+                    .for_update()
+                    .first::<i32>(conn)?;
+                if current_version != version {
+                    return Ok(None);
+                }
+
+                let existing = subscriptions
+                    .filter(device_id.eq(_device_id))
+                    .select(Subscription::as_select())
+                    .load(conn)?;
+                let (removed, added) = subscriptions_diff(existing, &values);
+                let removed = Self::delete_subscriptions_by_key(conn, removed)?;
+                if !values.is_empty() {
+                    Self::upsert_subscriptions(conn, values)?;
+                }
+
+                let version = diesel::update(devices::table.find(_device_id))
+                    .set(devices::subscriptions_version.eq(version + 1))
+                    .returning(devices::subscriptions_version)
+                    .get_result::<i32>(conn)?;
+                let result = subscriptions
+                    .filter(device_id.eq(_device_id))
+                    .select(Subscription::as_select())
+                    .load(conn)?;
+
+                Ok(Some(SubscriptionsSync {
+                    version,
+                    added,
+                    removed,
+                    subscriptions: result,
+                }))
+            })
     }
 
     // distinct_on is used to only select once subscription per user device
@@ -468,27 +663,130 @@ impl DatabaseClient {
 
     pub fn add_subscriptions(
         &mut self,
-        _subscriptions: Vec<Subscription>,
+        values: Vec<Subscription>,
+    ) -> Result<usize, diesel::result::Error> {
+        Self::upsert_subscriptions(&mut self.connection, values)
+    }
+
+    fn upsert_subscriptions(
+        conn: &mut PgConnection,
+        values: Vec<Subscription>,
     ) -> Result<usize, diesel::result::Error> {
         use crate::schema::subscriptions::dsl::*;
+        // a verified subscription stays verified when added again without a proof,
+        // preferences are replaced, callers carry over the stored ones when none were sent
         diesel::insert_into(subscriptions)
-            .values(&_subscriptions)
+            .values(&values)
+            .on_conflict((device_id, wallet_index, chain, address))
+            .do_update()
+            .set((
+                verified.eq(verified.or(excluded(verified))),
+                is_watch_only.eq(excluded(is_watch_only)),
+                mute_incoming.eq(excluded(mute_incoming)),
+                mute_outgoing.eq(excluded(mute_outgoing)),
+                min_amount.eq(excluded(min_amount)),
+                asset_ids.eq(excluded(asset_ids)),
+            ))
+            .execute(conn)
+    }
+
+    pub fn count_subscriptions_unverified(
+        &mut self,
+        _device_id: i32,
+        since: NaiveDateTime,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::subscriptions::dsl::*;
+        subscriptions
+            .filter(device_id.eq(_device_id))
+            .filter(verified.eq(false))
+            .filter(created_at.ge(since))
+            .count()
+            .get_result(&mut self.connection)
+    }
+
+    pub fn get_price_alerts_by_device_id(
+        &mut self,
+        _device_id: &str,
+    ) -> Result<Vec<GetPriceAlert>, diesel::result::Error> {
+        use crate::schema::price_alerts::dsl::*;
+        price_alerts
+            .inner_join(devices::table)
+            .filter(devices::device_id.eq(_device_id))
+            .select(GetPriceAlert::as_select())
+            .load(&mut self.connection)
+    }
+
+    pub fn add_price_alerts(
+        &mut self,
+        values: Vec<PriceAlert>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::price_alerts::dsl::*;
+        diesel::insert_into(price_alerts)
+            .values(&values)
             .on_conflict_do_nothing()
             .execute(&mut self.connection)
     }
 
+    pub fn delete_price_alerts(&mut self, ids: Vec<i32>) -> Result<usize, diesel::result::Error> {
+        use crate::schema::price_alerts::dsl::*;
+        diesel::delete(price_alerts.filter(id.eq_any(ids))).execute(&mut self.connection)
+    }
+
+    // alerts for devices with push enabled, along with the current price and asset symbol
+    pub fn get_price_alerts_with_prices(
+        &mut self,
+    ) -> Result<Vec<(GetPriceAlert, Device, Price, String)>, diesel::result::Error> {
+        use crate::schema::{assets, price_alerts, prices};
+        price_alerts::table
+            .inner_join(devices::table)
+            .inner_join(assets::table)
+            .inner_join(prices_assets::table.on(prices_assets::asset_id.eq(price_alerts::asset_id)))
+            .inner_join(prices::table.on(prices::id.eq(prices_assets::price_id)))
+            .filter(devices::is_push_enabled.eq(true))
+            .select((
+                GetPriceAlert::as_select(),
+                Device::as_select(),
+                Price::as_select(),
+                assets::symbol,
+            ))
+            .load(&mut self.connection)
+    }
+
+    pub fn set_price_alerts_notified(
+        &mut self,
+        ids: Vec<i32>,
+        notified_at: NaiveDateTime,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::price_alerts::dsl::*;
+        diesel::update(price_alerts.filter(id.eq_any(ids)))
+            .set((last_notified_at.eq(notified_at), is_triggered.eq(true)))
+            .execute(&mut self.connection)
+    }
+
+    // alerts whose condition no longer holds can notify again
+    pub fn set_price_alerts_rearmed(
+        &mut self,
+        ids: Vec<i32>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::price_alerts::dsl::*;
+        diesel::update(price_alerts.filter(id.eq_any(ids)))
+            .set(is_triggered.eq(false))
+            .execute(&mut self.connection)
+    }
+
     pub fn add_transactions(
         &mut self,
         transactions_values: Vec<Transaction>,
         addresses_values: Vec<TransactionAddresses>,
-    ) -> Result<bool, diesel::result::Error> {
+    ) -> Result<Vec<(String, NaiveDateTime)>, diesel::result::Error> {
         return self
             .connection
             .build_transaction()
             .read_write()
             .run::<_, diesel::result::Error, _>(|conn: &mut PgConnection| {
                 use crate::schema::transactions::dsl::*;
-                let _ = diesel::insert_into(transactions::table())
+                // storage time of every upserted transaction, the order streams resume in
+                let stored = diesel::insert_into(transactions::table())
                     .values(transactions_values)
                     .on_conflict((chain, hash))
                     .do_update()
@@ -498,9 +796,11 @@ impl DatabaseClient {
                         fee.eq(excluded(fee)),
                         fee_asset_id.eq(excluded(fee_asset_id)),
                         memo.eq(excluded(memo)),
+                        state.eq(excluded(state)),
                         updated_at.eq(excluded(updated_at)),
                     ))
-                    .execute(conn);
+                    .returning((id, updated_at))
+                    .get_results::<(String, NaiveDateTime)>(conn)?;
 
                 use crate::schema::transactions_addresses::dsl::*;
                 let _ = diesel::insert_into(transactions_addresses::table())
@@ -513,10 +813,71 @@ impl DatabaseClient {
                     .do_nothing()
                     .execute(conn);
 
-                Ok(true)
+                Ok(stored)
             });
     }
 
+    // a transaction registered right after broadcast, tracked until it has a final state
+    pub fn add_transaction_pending(
+        &mut self,
+        transaction: Transaction,
+        addresses_values: Vec<TransactionAddresses>,
+        pending: TransactionPending,
+    ) -> Result<usize, diesel::result::Error> {
+        self.connection
+            .build_transaction()
+            .read_write()
+            .run::<_, diesel::result::Error, _>(|conn: &mut PgConnection| {
+                use crate::schema::{transactions, transactions_pending};
+                diesel::insert_into(transactions::table)
+                    .values(&transaction)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                diesel::insert_into(transactions_addresses::table)
+                    .values(&addresses_values)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                diesel::insert_into(transactions_pending::table)
+                    .values(&pending)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+    }
+
+    pub fn get_transactions_pending(
+        &mut self,
+        _chain: Chain,
+    ) -> Result<Vec<(GetTransactionPending, Transaction)>, diesel::result::Error> {
+        use crate::schema::{transactions, transactions_pending};
+        transactions_pending::table
+            .inner_join(transactions::table)
+            .filter(transactions_pending::chain.eq(_chain.as_ref()))
+            .order(transactions_pending::created_at.asc())
+            .select((GetTransactionPending::as_select(), Transaction::as_select()))
+            .load(&mut self.connection)
+    }
+
+    pub fn delete_transaction_pending(
+        &mut self,
+        _transaction_id: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::transactions_pending::dsl::*;
+        diesel::delete(transactions_pending.find(_transaction_id)).execute(&mut self.connection)
+    }
+
+    // returns the storage time of the update
+    pub fn update_transaction_state(
+        &mut self,
+        _transaction_id: &str,
+        _state: TransactionState,
+    ) -> Result<NaiveDateTime, diesel::result::Error> {
+        use crate::schema::transactions::dsl::*;
+        diesel::update(transactions.find(_transaction_id))
+            .set(state.eq(_state.to_string()))
+            .returning(updated_at)
+            .get_result(&mut self.connection)
+    }
+
     pub fn get_transactions_by_device_id(
         &mut self,
         _device_id: &str,
@@ -526,11 +887,15 @@ impl DatabaseClient {
     ) -> Result<Vec<Transaction>, diesel::result::Error> {
         use crate::schema::transactions::dsl::*;
 
-        let mut query = transactions
-            .into_boxed()
-            .inner_join(transactions_addresses::table)
-            .filter(transactions_addresses::chain_id.eq_any(chains.clone()))
-            .filter(transactions_addresses::address.eq_any(addresses));
+        // subquery instead of a join, transactions between own addresses are returned once
+        let mut query = transactions.into_boxed().filter(
+            id.eq_any(
+                transactions_addresses::table
+                    .select(transactions_addresses::transaction_id)
+                    .filter(transactions_addresses::chain_id.eq_any(chains))
+                    .filter(transactions_addresses::address.eq_any(addresses.clone())),
+            ),
+        );
 
         if let Some(_asset_id) = options.asset_id {
             query = query.filter(asset_id.eq(_asset_id));
@@ -543,12 +908,120 @@ impl DatabaseClient {
             query = query.filter(created_at.gt(datetime));
         }
 
+        if let Some(to_timestamp) = options.to_timestamp {
+            let datetime = DateTime::from_timestamp(to_timestamp.into(), 0)
+                .unwrap()
+                .naive_utc();
+            query = query.filter(created_at.le(datetime));
+        }
+
+        if let Some(transaction_type) = options.transaction_type {
+            query = query.filter(kind.eq(transaction_type.as_ref().to_string()));
+        }
+
+        if let Some(transaction_state) = options.state {
+            query = query.filter(state.eq(transaction_state.to_string()));
+        }
+
+        // same rules as Transaction::as_primitive, utxo directions are only resolved after loading
+        if let Some(direction) = options.direction {
+            query = match direction {
+                TransactionDirection::Outgoing => {
+                    query.filter(from_address.eq_any(addresses.clone()))
+                }
+                TransactionDirection::Incoming => query
+                    .filter(
+                        from_address
+                            .ne_all(addresses.clone())
+                            .or(from_address.is_null()),
+                    )
+                    .filter(to_address.eq_any(addresses.clone())),
+                TransactionDirection::SelfTransfer => query
+                    .filter(
+                        from_address
+                            .ne_all(addresses.clone())
+                            .or(from_address.is_null()),
+                    )
+                    .filter(to_address.ne_all(addresses).or(to_address.is_null())),
+            };
+        }
+
+        if let Some(address) = options.address {
+            query = query.filter(from_address.eq(address.clone()).or(to_address.eq(address)));
+        }
+
+        // values are stored as text, non numeric values never match a range
+        if let Some(min_value) = options.min_value {
+            query = query.filter(
+                sql::<Bool>(TRANSACTIONS_NUMERIC_VALUE)
+                    .sql(" >= CAST(")
+                    .bind::<Text, _>(min_value)
+                    .sql(" AS NUMERIC)"),
+            );
+        }
+
+        if let Some(max_value) = options.max_value {
+            query = query.filter(
+                sql::<Bool>(TRANSACTIONS_NUMERIC_VALUE)
+                    .sql(" <= CAST(")
+                    .bind::<Text, _>(max_value)
+                    .sql(" AS NUMERIC)"),
+            );
+        }
+
+        if let Some(cursor) = options
+            .cursor
+            .as_deref()
+            .and_then(TransactionsCursor::decode)
+        {
+            if let Some(cursor_created_at) = cursor.datetime() {
+                let cursor_created_at = cursor_created_at.naive_utc();
+                query = query.filter(
+                    created_at
+                        .lt(cursor_created_at)
+                        .or(created_at.eq(cursor_created_at).and(id.lt(cursor.id))),
+                );
+            }
+        }
+
+        if let Some(limit) = options.limit {
+            query = query.limit(limit as i64);
+        }
+
         query
-            .order(created_at.desc())
+            .order((created_at.desc(), id.desc()))
             .select(Transaction::as_select())
             .load(&mut self.connection)
     }
 
+    // transactions of the addresses stored or updated after the cursor, in storage order
+    pub fn get_transactions_updated_after(
+        &mut self,
+        addresses: Vec<String>,
+        chains: Vec<String>,
+        cursor: &TransactionsCursor,
+    ) -> Result<Vec<(Transaction, NaiveDateTime)>, diesel::result::Error> {
+        use crate::schema::transactions::dsl::*;
+        let cursor_updated_at = cursor.datetime().unwrap_or_default().naive_utc();
+        transactions
+            .filter(
+                id.eq_any(
+                    transactions_addresses::table
+                        .select(transactions_addresses::transaction_id)
+                        .filter(transactions_addresses::chain_id.eq_any(chains))
+                        .filter(transactions_addresses::address.eq_any(addresses)),
+                ),
+            )
+            .filter(
+                updated_at.gt(cursor_updated_at).or(updated_at
+                    .eq(cursor_updated_at)
+                    .and(id.gt(cursor.id.clone()))),
+            )
+            .order((updated_at.asc(), id.asc()))
+            .select((Transaction::as_select(), updated_at))
+            .load(&mut self.connection)
+    }
+
     pub fn get_transactions_by_hash(
         &mut self,
         _hash: &str,
@@ -561,6 +1034,27 @@ impl DatabaseClient {
             .load(&mut self.connection)
     }
 
+    // incoming transfers without metadata, candidates to be matched with fiat purchases
+    pub fn get_transactions_incoming_transfers(
+        &mut self,
+        address: &str,
+        _asset_id: &str,
+        from_created_at: NaiveDateTime,
+        to_created_at: NaiveDateTime,
+    ) -> Result<Vec<Transaction>, diesel::result::Error> {
+        use crate::schema::transactions::dsl::*;
+        transactions
+            .filter(to_address.eq(address))
+            .filter(asset_id.eq(_asset_id))
+            .filter(kind.eq(primitives::TransactionType::Transfer.as_ref()))
+            .filter(metadata.is_null())
+            .filter(created_at.ge(from_created_at))
+            .filter(created_at.le(to_created_at))
+            .order(created_at.asc())
+            .select(Transaction::as_select())
+            .load(&mut self.connection)
+    }
+
     pub fn get_asset(&mut self, asset_id: &str) -> Result<Asset, diesel::result::Error> {
         use crate::schema::assets::dsl::*;
         assets
@@ -589,6 +1083,18 @@ impl DatabaseClient {
             .load(&mut self.connection)
     }
 
+    // disabled assets included
+    pub fn get_assets_all(
+        &mut self,
+        asset_ids: Vec<String>,
+    ) -> Result<Vec<Asset>, diesel::result::Error> {
+        use crate::schema::assets::dsl::*;
+        assets
+            .filter(id.eq_any(asset_ids))
+            .select(Asset::as_select())
+            .load(&mut self.connection)
+    }
+
     pub fn get_assets_list(&mut self) -> Result<Vec<Asset>, diesel::result::Error> {
         use crate::schema::assets::dsl::*;
         assets
@@ -719,6 +1225,7 @@ impl DatabaseClient {
             .execute(&mut self.connection)
     }
 
+    // evm addresses arrive checksummed or lowercase, match regardless of case
     pub fn get_scan_address(
         &mut self,
         _chain: Chain,
@@ -727,7 +1234,11 @@ impl DatabaseClient {
         use crate::schema::scan_addresses::dsl::*;
         scan_addresses
             .filter(chain.eq(_chain.as_ref()))
-            .filter(address.eq(value))
+            .filter(
+                sql::<Bool>("lower(address) = lower(")
+                    .bind::<Text, _>(value.to_string())
+                    .sql(")"),
+            )
             .select(ScanAddress::as_select())
             .first(&mut self.connection)
     }
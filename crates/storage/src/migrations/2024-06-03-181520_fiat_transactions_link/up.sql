@@ -0,0 +1,3 @@
+ALTER TABLE fiat_transactions ADD COLUMN transaction_id VARCHAR(256) REFERENCES transactions (id) ON DELETE SET NULL;
+
+CREATE INDEX fiat_transactions_transaction_id_idx ON fiat_transactions (transaction_id);
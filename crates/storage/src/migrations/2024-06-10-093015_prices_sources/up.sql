@@ -0,0 +1,12 @@
+CREATE TABLE prices_sources (
+    price_id VARCHAR(256) NOT NULL REFERENCES prices (id) ON DELETE CASCADE,
+    source VARCHAR(32) NOT NULL,
+    price float NOT NULL DEFAULT 0,
+    last_updated_at timestamp NOT NULL,
+    updated_at timestamp NOT NULL default current_timestamp,
+    created_at timestamp NOT NULL default current_timestamp,
+
+    PRIMARY KEY (price_id, source)
+);
+
+SELECT diesel_manage_updated_at('prices_sources');
@@ -0,0 +1,15 @@
+CREATE TABLE price_alerts (
+    id SERIAL PRIMARY KEY,
+    device_id INTEGER NOT NULL REFERENCES devices (id) ON DELETE CASCADE,
+    asset_id VARCHAR(128) NOT NULL REFERENCES assets (id) ON DELETE CASCADE,
+    alert_type VARCHAR(32) NOT NULL,
+    value float NOT NULL,
+    last_notified_at timestamp,
+    updated_at timestamp NOT NULL default current_timestamp,
+    created_at timestamp NOT NULL default current_timestamp,
+    UNIQUE(device_id, asset_id, alert_type, value)
+);
+
+SELECT diesel_manage_updated_at('price_alerts');
+
+CREATE INDEX price_alerts_asset_id_idx ON price_alerts (asset_id);
@@ -0,0 +1 @@
+ALTER TABLE subscriptions ADD COLUMN verified BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,5 @@
+ALTER TABLE subscriptions DROP COLUMN is_watch_only;
+ALTER TABLE subscriptions DROP COLUMN mute_incoming;
+ALTER TABLE subscriptions DROP COLUMN mute_outgoing;
+ALTER TABLE subscriptions DROP COLUMN min_amount;
+ALTER TABLE subscriptions DROP COLUMN asset_ids;
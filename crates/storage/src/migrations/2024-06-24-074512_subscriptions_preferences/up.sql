@@ -0,0 +1,5 @@
+ALTER TABLE subscriptions ADD COLUMN is_watch_only BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE subscriptions ADD COLUMN mute_incoming BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE subscriptions ADD COLUMN mute_outgoing BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE subscriptions ADD COLUMN min_amount DOUBLE PRECISION;
+ALTER TABLE subscriptions ADD COLUMN asset_ids JSONB NOT NULL DEFAULT '[]';
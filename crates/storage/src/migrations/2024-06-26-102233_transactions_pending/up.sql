@@ -0,0 +1,12 @@
+CREATE TABLE transactions_pending (
+    transaction_id VARCHAR(256) PRIMARY KEY REFERENCES transactions (id) ON DELETE CASCADE,
+    device_id INTEGER NOT NULL REFERENCES devices (id) ON DELETE CASCADE,
+    wallet_index INTEGER NOT NULL,
+    chain VARCHAR NOT NULL REFERENCES chains (id) ON DELETE CASCADE,
+    updated_at timestamp NOT NULL DEFAULT current_timestamp,
+    created_at timestamp NOT NULL DEFAULT current_timestamp
+);
+
+SELECT diesel_manage_updated_at('transactions_pending');
+
+CREATE INDEX transactions_pending_chain_idx ON transactions_pending (chain);
@@ -0,0 +1 @@
+ALTER TABLE fiat_transactions DROP COLUMN transaction_type;
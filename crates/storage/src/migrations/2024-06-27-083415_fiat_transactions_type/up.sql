@@ -0,0 +1 @@
+ALTER TABLE fiat_transactions ADD COLUMN transaction_type VARCHAR(32) NOT NULL DEFAULT 'buy';
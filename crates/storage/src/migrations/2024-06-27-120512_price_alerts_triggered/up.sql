@@ -0,0 +1 @@
+ALTER TABLE price_alerts ADD COLUMN is_triggered BOOLEAN NOT NULL DEFAULT false;
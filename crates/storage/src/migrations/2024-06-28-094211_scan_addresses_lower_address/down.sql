@@ -0,0 +1 @@
+DROP INDEX scan_addresses_address_lower_idx;
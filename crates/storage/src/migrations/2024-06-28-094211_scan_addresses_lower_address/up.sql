@@ -0,0 +1 @@
+CREATE INDEX scan_addresses_address_lower_idx ON scan_addresses (chain, lower(address));
@@ -3,13 +3,41 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Row, Clone)]
 pub struct GetChart {
-    pub price: f64,
     pub date: i32,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub market_cap: f64,
+    pub volume: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Row, Clone)]
 pub struct CreateChart {
     pub coin_id: String,
     pub price: f32,
+    pub market_cap: f32,
+    pub volume: f32,
+    pub ts: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Row, Clone)]
+pub struct ChartClose {
+    pub coin_id: String,
+    pub date: u32,
+    pub close: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Row, Clone)]
+pub struct ChartCoverage {
+    pub coin_id: String,
+    // start of each day, unix seconds
+    pub dates: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Row, Clone)]
+pub struct CreateFiatRate {
+    pub symbol: String,
+    pub rate: f64,
     pub ts: u32,
 }
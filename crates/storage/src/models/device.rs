@@ -14,6 +14,7 @@ pub struct Device {
     pub is_push_enabled: bool,
     pub version: String,
     pub subscriptions_version: i32,
+    pub public_key: Option<String>,
 }
 
 #[derive(Debug, Queryable, Selectable, Serialize, Deserialize, Insertable, AsChangeset, Clone)]
@@ -27,7 +28,8 @@ pub struct UpdateDevice {
     pub currency: String,
     pub is_push_enabled: bool,
     pub version: String,
-    pub subscriptions_version: i32,
+    pub subscriptions_version: Option<i32>,
+    pub public_key: Option<String>,
 }
 
 impl Device {
@@ -42,6 +44,7 @@ impl Device {
             is_push_enabled: self.is_push_enabled,
             version: self.version.clone(),
             subscriptions_version: self.subscriptions_version,
+            public_key: self.public_key.clone(),
         }
     }
 }
@@ -56,7 +59,8 @@ impl UpdateDevice {
             currency: device.currency,
             is_push_enabled: device.is_push_enabled,
             version: device.version,
-            subscriptions_version: device.subscriptions_version,
+            subscriptions_version: Some(device.subscriptions_version),
+            public_key: device.public_key,
         }
     }
 }
@@ -1,4 +1,8 @@
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use primitives::{AssetId, FiatTransactionStatus, FiatTransactionType};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Queryable, Selectable, Insertable, AsChangeset, Serialize, Deserialize, Clone)]
@@ -85,6 +89,7 @@ pub struct FiatTransaction {
     pub fee_network: f64,
     pub fee_partner: f64,
     pub fee_provider: f64,
+    pub transaction_type: String,
 }
 
 impl FiatTransaction {
@@ -102,6 +107,51 @@ impl FiatTransaction {
             fee_provider: transaction.fee_provider,
             fee_network: transaction.fee_network,
             fee_partner: transaction.fee_partner,
+            transaction_type: transaction.transaction_type.as_ref().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::fiat_transactions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GetFiatTransaction {
+    pub id: i32,
+    pub asset_id: Option<String>,
+    pub symbol: String,
+    pub provider_id: String,
+    pub provider_transaction_id: String,
+    pub status: String,
+    pub fiat_amount: f64,
+    pub fiat_currency: String,
+    pub address: Option<String>,
+    pub transaction_hash: Option<String>,
+    pub fee_network: f64,
+    pub fee_partner: f64,
+    pub fee_provider: f64,
+    pub transaction_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub transaction_type: String,
+}
+
+impl GetFiatTransaction {
+    pub fn as_primitive(&self) -> primitives::FiatTransaction {
+        primitives::FiatTransaction {
+            asset_id: self.asset_id.clone().and_then(|x| AssetId::new(&x)),
+            provider_id: self.provider_id.clone(),
+            provider_transaction_id: self.provider_transaction_id.clone(),
+            status: FiatTransactionStatus::from_str(&self.status)
+                .unwrap_or(FiatTransactionStatus::Unknown),
+            transaction_type: FiatTransactionType::from_str(&self.transaction_type)
+                .unwrap_or(FiatTransactionType::Buy),
+            symbol: self.symbol.clone(),
+            fiat_amount: self.fiat_amount,
+            fiat_currency: self.fiat_currency.clone(),
+            transaction_hash: self.transaction_hash.clone(),
+            address: self.address.clone(),
+            fee_provider: self.fee_provider,
+            fee_network: self.fee_network,
+            fee_partner: self.fee_partner,
         }
     }
 }
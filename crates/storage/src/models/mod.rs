@@ -7,26 +7,30 @@ pub mod fiat;
 pub mod node;
 pub mod parser_state;
 pub mod price;
+pub mod price_alert;
 pub mod scan;
 pub mod subscription;
 pub mod tokenlist;
 pub mod transaction;
 pub mod transaction_addresses;
+pub mod transaction_pending;
 pub mod version;
 
 pub use self::asset::Asset;
 pub use self::asset_type::AssetType;
 pub use self::chain::Chain;
-pub use self::chart::{CreateChart, GetChart};
+pub use self::chart::{ChartClose, ChartCoverage, CreateChart, CreateFiatRate, GetChart};
 pub use self::device::Device;
 pub use self::device::UpdateDevice;
-pub use self::fiat::{FiatAsset, FiatProvider, FiatRate, FiatTransaction};
+pub use self::fiat::{FiatAsset, FiatProvider, FiatRate, FiatTransaction, GetFiatTransaction};
 pub use self::node::Node;
 pub use self::parser_state::ParserState;
 pub use self::price::Price;
+pub use self::price_alert::{GetPriceAlert, PriceAlert};
 pub use self::scan::ScanAddress;
-pub use self::subscription::Subscription;
+pub use self::subscription::{subscriptions_diff, Subscription, SubscriptionsSync};
 pub use self::tokenlist::TokenList;
 pub use self::transaction::Transaction;
 pub use self::transaction_addresses::TransactionAddresses;
+pub use self::transaction_pending::{GetTransactionPending, TransactionPending};
 pub use self::version::Version;
@@ -63,6 +63,16 @@ pub struct PriceAsset {
     pub price_id: String,
 }
 
+#[derive(Debug, Queryable, Selectable, Serialize, Deserialize, Insertable, AsChangeset, Clone)]
+#[diesel(table_name = crate::schema::prices_sources)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PriceSource {
+    pub price_id: String,
+    pub source: String,
+    pub price: f64,
+    pub last_updated_at: NaiveDateTime,
+}
+
 impl PartialEq for PriceAsset {
     fn eq(&self, other: &Self) -> bool {
         self.asset_id == other.asset_id && self.price_id == other.price_id
@@ -142,6 +152,8 @@ impl Price {
         CreateChart {
             coin_id: self.id.clone(),
             price: self.price as f32,
+            market_cap: self.market_cap as f32,
+            volume: self.total_volume as f32,
             ts: self
                 .last_updated_at
                 .unwrap_or_default()
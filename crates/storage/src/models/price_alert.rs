@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Queryable, Selectable, Serialize, Deserialize, Insertable, AsChangeset, Clone)]
+#[diesel(table_name = crate::schema::price_alerts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PriceAlert {
+    pub device_id: i32,
+    pub asset_id: String,
+    pub alert_type: String,
+    pub value: f64,
+    pub last_notified_at: Option<NaiveDateTime>,
+}
+
+impl PriceAlert {
+    pub fn from_primitive(price_alert: primitives::PriceAlert, device_id: i32) -> Self {
+        Self {
+            device_id,
+            asset_id: price_alert.asset_id,
+            alert_type: price_alert.alert_type.as_ref().to_string(),
+            value: price_alert.value,
+            last_notified_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[diesel(table_name = crate::schema::price_alerts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GetPriceAlert {
+    pub id: i32,
+    pub device_id: i32,
+    pub asset_id: String,
+    pub alert_type: String,
+    pub value: f64,
+    pub last_notified_at: Option<NaiveDateTime>,
+    // set once notified, cleared when the condition no longer holds
+    pub is_triggered: bool,
+}
+
+impl GetPriceAlert {
+    // alerts with an unknown type are skipped
+    pub fn as_primitive(&self) -> Option<primitives::PriceAlert> {
+        Some(primitives::PriceAlert {
+            asset_id: self.asset_id.clone(),
+            alert_type: primitives::PriceAlertType::from_str(self.alert_type.as_ref()).ok()?,
+            value: self.value,
+        })
+    }
+}
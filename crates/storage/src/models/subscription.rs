@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::str::FromStr;
 
 use diesel::prelude::*;
@@ -11,23 +12,144 @@ pub struct Subscription {
     pub wallet_index: i32,
     pub chain: String,
     pub address: String,
+    pub verified: bool,
+    pub is_watch_only: bool,
+    pub mute_incoming: bool,
+    pub mute_outgoing: bool,
+    pub min_amount: Option<f64>,
+    pub asset_ids: serde_json::Value,
+}
+
+// outcome of replacing the subscriptions of a device with a desired set
+#[derive(Debug, Clone)]
+pub struct SubscriptionsSync {
+    pub version: i32,
+    pub added: usize,
+    pub removed: usize,
+    pub subscriptions: Vec<Subscription>,
 }
 
 impl Subscription {
+    pub fn key(&self) -> (i32, String, String) {
+        (self.wallet_index, self.chain.clone(), self.address.clone())
+    }
+
     pub fn as_primitive(&self) -> primitives::Subscription {
         primitives::Subscription {
             wallet_index: self.wallet_index,
             chain: primitives::Chain::from_str(self.chain.as_ref()).unwrap(),
             address: self.address.clone(),
+            proof: None,
+            preferences: Some(self.preferences()),
+        }
+    }
+
+    pub fn preferences(&self) -> primitives::SubscriptionPreferences {
+        primitives::SubscriptionPreferences {
+            is_watch_only: self.is_watch_only,
+            mute_incoming: self.mute_incoming,
+            mute_outgoing: self.mute_outgoing,
+            min_amount: self.min_amount,
+            asset_ids: serde_json::from_value(self.asset_ids.clone()).unwrap_or_default(),
         }
     }
 
+    // preferences of a stored subscription, for upserts that were sent without any
+    pub fn with_preferences_of(self, other: &Subscription) -> Self {
+        Self {
+            is_watch_only: other.is_watch_only,
+            mute_incoming: other.mute_incoming,
+            mute_outgoing: other.mute_outgoing,
+            min_amount: other.min_amount,
+            asset_ids: other.asset_ids.clone(),
+            ..self
+        }
+    }
+
+    // new subscriptions without preferences get the defaults
     pub fn from_primitive(subscription: primitives::Subscription, device_id: i32) -> Self {
+        let preferences = subscription.preferences.unwrap_or_default();
         Self {
             device_id,
             wallet_index: subscription.wallet_index,
             chain: subscription.chain.as_ref().to_string(),
             address: subscription.address.to_string(),
+            verified: false,
+            is_watch_only: preferences.is_watch_only,
+            mute_incoming: preferences.mute_incoming,
+            mute_outgoing: preferences.mute_outgoing,
+            min_amount: preferences.min_amount,
+            asset_ids: serde_json::json!(preferences.asset_ids),
+        }
+    }
+}
+
+// current subscriptions missing from the desired set, and the number of desired ones not stored yet
+pub fn subscriptions_diff(
+    current: Vec<Subscription>,
+    desired: &[Subscription],
+) -> (Vec<Subscription>, usize) {
+    let desired_keys = desired.iter().map(|x| x.key()).collect::<HashSet<_>>();
+    let current_keys = current.iter().map(|x| x.key()).collect::<HashSet<_>>();
+    let added = desired_keys.difference(&current_keys).count();
+    let removed = current
+        .into_iter()
+        .filter(|x| !desired_keys.contains(&x.key()))
+        .collect();
+    (removed, added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(chain: &str, address: &str) -> Subscription {
+        Subscription {
+            device_id: 1,
+            wallet_index: 1,
+            chain: chain.to_string(),
+            address: address.to_string(),
+            verified: false,
+            is_watch_only: false,
+            mute_incoming: false,
+            mute_outgoing: false,
+            min_amount: None,
+            asset_ids: serde_json::json!([]),
         }
     }
+
+    fn subscriptions() -> Vec<Subscription> {
+        vec![
+            subscription("ethereum", "0x1"),
+            subscription("bitcoin", "bc1"),
+        ]
+    }
+
+    #[test]
+    fn test_subscriptions_diff() {
+        let current = subscriptions();
+        let mut desired = current[1..].to_vec();
+        desired.push(Subscription {
+            wallet_index: 2,
+            ..current[0].clone()
+        });
+
+        let (removed, added) = subscriptions_diff(current.clone(), &desired);
+
+        assert_eq!(added, 1);
+        assert_eq!(
+            removed.iter().map(|x| x.key()).collect::<Vec<_>>(),
+            vec![current[0].key()]
+        );
+    }
+
+    #[test]
+    fn test_subscriptions_diff_unchanged() {
+        let current = subscriptions();
+
+        let (removed, added) = subscriptions_diff(current.clone(), &current);
+
+        assert!(removed.is_empty());
+        assert_eq!(added, 0);
+    }
 }
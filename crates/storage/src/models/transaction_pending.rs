@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Queryable, Selectable, Insertable, Serialize, Deserialize, Clone)]
+#[diesel(table_name = crate::schema::transactions_pending)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TransactionPending {
+    pub transaction_id: String,
+    pub device_id: i32,
+    pub wallet_index: i32,
+    pub chain: String,
+}
+
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::transactions_pending)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GetTransactionPending {
+    pub transaction_id: String,
+    pub device_id: i32,
+    pub wallet_index: i32,
+    pub chain: String,
+    pub created_at: NaiveDateTime,
+}
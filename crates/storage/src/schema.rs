@@ -95,6 +95,8 @@ diesel::table! {
         subscriptions_version -> Int4,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        #[max_length = 256]
+        public_key -> Nullable<Varchar>,
     }
 }
 
@@ -168,6 +170,10 @@ diesel::table! {
         fee_partner -> Float8,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        #[max_length = 256]
+        transaction_id -> Nullable<Varchar>,
+        #[max_length = 32]
+        transaction_type -> Varchar,
     }
 }
 
@@ -197,6 +203,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    price_alerts (id) {
+        id -> Int4,
+        device_id -> Int4,
+        #[max_length = 128]
+        asset_id -> Varchar,
+        #[max_length = 32]
+        alert_type -> Varchar,
+        value -> Float8,
+        last_notified_at -> Nullable<Timestamp>,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+        is_triggered -> Bool,
+    }
+}
+
 diesel::table! {
     prices (id) {
         #[max_length = 256]
@@ -226,6 +248,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    prices_sources (price_id, source) {
+        #[max_length = 256]
+        price_id -> Varchar,
+        #[max_length = 32]
+        source -> Varchar,
+        price -> Float8,
+        last_updated_at -> Timestamp,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     scan_addresses (id) {
         id -> Int4,
@@ -255,6 +290,12 @@ diesel::table! {
         address -> Varchar,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        verified -> Bool,
+        is_watch_only -> Bool,
+        mute_incoming -> Bool,
+        mute_outgoing -> Bool,
+        min_amount -> Nullable<Float8>,
+        asset_ids -> Jsonb,
     }
 }
 
@@ -331,6 +372,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    transactions_pending (transaction_id) {
+        #[max_length = 256]
+        transaction_id -> Varchar,
+        device_id -> Int4,
+        wallet_index -> Int4,
+        chain -> Varchar,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     versions (id) {
         id -> Int4,
@@ -348,9 +401,13 @@ diesel::joinable!(fiat_assets -> assets (asset_id));
 diesel::joinable!(fiat_assets -> fiat_providers (provider));
 diesel::joinable!(fiat_transactions -> assets (asset_id));
 diesel::joinable!(fiat_transactions -> fiat_providers (provider_id));
+diesel::joinable!(fiat_transactions -> transactions (transaction_id));
 diesel::joinable!(nodes -> chains (chain));
 diesel::joinable!(parser_state -> chains (chain));
+diesel::joinable!(price_alerts -> assets (asset_id));
+diesel::joinable!(price_alerts -> devices (device_id));
 diesel::joinable!(prices_assets -> prices (price_id));
+diesel::joinable!(prices_sources -> prices (price_id));
 diesel::joinable!(scan_addresses -> chains (chain));
 diesel::joinable!(subscriptions -> chains (chain));
 diesel::joinable!(subscriptions -> devices (device_id));
@@ -360,6 +417,9 @@ diesel::joinable!(transactions -> chains (chain));
 diesel::joinable!(transactions_addresses -> assets (asset_id));
 diesel::joinable!(transactions_addresses -> chains (chain_id));
 diesel::joinable!(transactions_addresses -> transactions (transaction_id));
+diesel::joinable!(transactions_pending -> chains (chain));
+diesel::joinable!(transactions_pending -> devices (device_id));
+diesel::joinable!(transactions_pending -> transactions (transaction_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     assets,
@@ -373,13 +433,16 @@ diesel::allow_tables_to_appear_in_same_query!(
     fiat_transactions,
     nodes,
     parser_state,
+    price_alerts,
     prices,
     prices_assets,
+    prices_sources,
     scan_addresses,
     subscriptions,
     subscriptions_addresses_exclude,
     tokenlists,
     transactions,
     transactions_addresses,
+    transactions_pending,
     versions,
 );
@@ -1,9 +1,20 @@
-use std::str::FromStr;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use gem_evm::address::EthereumAddress;
-use primitives::{AssetId, Chain, ChainType, SwapQuote, SwapQuoteProtocolRequest};
+use gem_chain_rpc::EthereumClient;
+use gem_evm::{address::EthereumAddress, erc20, erc2612};
+use num_bigint::BigUint;
+use primitives::{
+    eip712::{eip712_domain_types, EIP712Domain},
+    erc2612::{erc2612_permit_types, ERC2612Permit, ERC2612PermitMessage, ERC2612Types},
+    AssetId, Chain, ChainType, SwapApprovalData, SwapQuote, SwapQuoteProtocolRequest,
+};
 
-use super::model::{QuoteRequest, SwapResult, Tokenlist};
+use super::model::{QuoteRequest, SpenderResult, SwapResult, Tokenlist};
 
 pub struct OneInchClient {
     api_url: String,
@@ -12,14 +23,31 @@ pub struct OneInchClient {
     fee_referral_address: String,
     version: String,
     client: reqwest::Client,
+    nodes: HashMap<Chain, EthereumClient>,
+    // router address per network id, it only changes with the api version
+    spenders: Mutex<HashMap<String, String>>,
 }
 
 const NATIVE_ADDRESS: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+const PERMIT_PRIMARY_TYPE: &str = "Permit";
+const PERMIT_DEFAULT_VERSION: &str = "1";
+const PERMIT_DEADLINE: u64 = 30 * 60; // 30 minutes
 pub const PROVIDER_NAME: &str = "1inch";
 
 impl OneInchClient {
-    pub fn new(api_url: String, api_key: String, fee: f64, fee_referral_address: String) -> Self {
+    pub fn new(
+        api_url: String,
+        api_key: String,
+        fee: f64,
+        fee_referral_address: String,
+        nodes: HashMap<Chain, String>,
+    ) -> Self {
         let client = reqwest::Client::builder().build().unwrap();
+        let nodes = nodes
+            .into_iter()
+            .filter(|(_, url)| !url.is_empty())
+            .map(|(chain, url)| (chain, EthereumClient::new(chain, url)))
+            .collect();
 
         Self {
             client,
@@ -28,6 +56,8 @@ impl OneInchClient {
             fee,
             fee_referral_address,
             version: "v5.2".to_string(),
+            nodes,
+            spenders: Mutex::new(HashMap::new()),
         }
     }
 
@@ -106,6 +136,7 @@ impl OneInchClient {
             self.get_swap_quote(quote_request, network_id).await?
         };
         let data = swap_quote.tx.map(|value| value.get_data());
+        let approval = self.get_approval(&quote, network_id).await?;
 
         let quote = SwapQuote {
             chain_type: ChainType::Ethereum,
@@ -114,10 +145,148 @@ impl OneInchClient {
             fee_percent: self.fee as f32,
             provider: PROVIDER_NAME.into(),
             data,
+            approval,
         };
         Ok(quote)
     }
 
+    pub async fn get_approval(
+        &self,
+        quote: &SwapQuoteProtocolRequest,
+        network_id: &str,
+    ) -> Result<Option<SwapApprovalData>, Box<dyn std::error::Error + Send + Sync>> {
+        let token = match quote.from_asset.token_id.clone() {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        let chain = quote.from_asset.chain;
+        // without a node or spender the allowance is unknown, the quote is still usable
+        let Some(node) = self.nodes.get(&chain) else {
+            return Ok(None);
+        };
+
+        let spender = match self.get_spender_address(network_id).await {
+            Ok(spender) => spender,
+            Err(err) => {
+                println!("oneinch spender error: {}, network: {}", err, network_id);
+                return Ok(None);
+            }
+        };
+
+        let call = erc20::encode_allowance(&quote.wallet_address, &spender)?;
+        let result = match node.call(&token, &call).await {
+            Ok(result) => result,
+            Err(err) => {
+                println!("oneinch allowance error: {}, token: {}", err, token);
+                return Ok(None);
+            }
+        };
+        let allowance = erc20::decode_allowance(&result)?;
+        if BigUint::from_str(&allowance)? >= BigUint::from_str(&quote.amount)? {
+            return Ok(None);
+        }
+
+        let data = erc20::encode_approve(&spender, &quote.amount)?;
+        let permit = self
+            .get_permit(
+                node,
+                chain,
+                &token,
+                &quote.wallet_address,
+                &spender,
+                &quote.amount,
+            )
+            .await
+            .ok();
+
+        Ok(Some(SwapApprovalData {
+            token,
+            spender,
+            value: quote.amount.clone(),
+            data: format!("0x{}", hex::encode(data)),
+            permit,
+        }))
+    }
+
+    // fails for tokens without ERC-2612 support, approve calldata is still returned in that case
+    async fn get_permit(
+        &self,
+        node: &EthereumClient,
+        chain: Chain,
+        token: &str,
+        owner: &str,
+        spender: &str,
+        value: &str,
+    ) -> Result<ERC2612PermitMessage, Box<dyn std::error::Error + Send + Sync>> {
+        let nonce =
+            erc2612::decode_nonces(&node.call(token, &erc2612::encode_nonces(owner)?).await?)?;
+        let name = erc20::decode_name(&node.call(token, &erc20::encode_name()).await?)?;
+        // version() is optional in ERC-2612, most tokens without it sign with version "1"
+        let version = node
+            .call(token, &erc2612::encode_version())
+            .await
+            .ok()
+            .and_then(|result| erc2612::decode_version(&result).ok())
+            .unwrap_or_else(|| PERMIT_DEFAULT_VERSION.to_string());
+        let deadline = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + PERMIT_DEADLINE;
+
+        Ok(ERC2612PermitMessage {
+            types: ERC2612Types {
+                eip712Domain: eip712_domain_types(),
+                permit: erc2612_permit_types(),
+            },
+            primary_type: PERMIT_PRIMARY_TYPE.to_string(),
+            domain: EIP712Domain {
+                name,
+                version,
+                chain_id: chain.network_id().parse()?,
+                verifying_contract: token.to_string(),
+            },
+            message: ERC2612Permit {
+                owner: owner.to_string(),
+                spender: spender.to_string(),
+                value: value.to_string(),
+                nonce,
+                deadline: deadline.to_string(),
+            },
+        })
+    }
+
+    async fn get_spender_address(
+        &self,
+        network_id: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(spender) = self.spenders.lock().unwrap().get(network_id) {
+            return Ok(spender.clone());
+        }
+        let spender = self.get_spender(network_id).await?.address;
+        let spender = EthereumAddress::from_str(&spender)?.to_checksum();
+        self.spenders
+            .lock()
+            .unwrap()
+            .insert(network_id.to_string(), spender.clone());
+        Ok(spender)
+    }
+
+    pub async fn get_spender(
+        &self,
+        network_id: &str,
+    ) -> Result<SpenderResult, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!(
+            "{}/swap/{}/{}/approve/spender",
+            self.api_url, self.version, network_id
+        );
+        Ok(self
+            .client
+            .get(&url)
+            .bearer_auth(self.api_key.as_str())
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<SpenderResult>()
+            .await?)
+    }
+
     pub async fn get_swap_quote(
         &self,
         request: QuoteRequest,
@@ -134,6 +303,7 @@ impl OneInchClient {
             .bearer_auth(self.api_key.as_str())
             .send()
             .await?
+            .error_for_status()?
             .json::<SwapResult>()
             .await?)
     }
@@ -151,6 +321,7 @@ impl OneInchClient {
             .bearer_auth(self.api_key.as_str())
             .send()
             .await?
+            .error_for_status()?
             .json::<SwapResult>()
             .await?)
     }
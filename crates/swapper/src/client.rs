@@ -1,5 +1,5 @@
-use primitives::{Chain, SwapQuote, SwapQuoteProtocolRequest};
-use swap_oneinch::OneInchClient;
+use primitives::{Chain, SwapProvider, SwapQuote, SwapQuoteProtocolRequest};
+use swap_oneinch::{client::PROVIDER_NAME as ONEINCH_PROVIDER_NAME, OneInchClient};
 use swap_provider::ProviderList;
 
 use crate::{JupiterClient, ThorchainSwapClient};
@@ -26,20 +26,16 @@ impl SwapperClient {
         }
     }
 
-    pub async fn get_quote(
-        &self,
-        quote: SwapQuoteProtocolRequest,
-    ) -> Result<SwapQuote, Box<dyn std::error::Error + Send + Sync>> {
-        let source_chain = quote.from_asset.chain;
-        let result = self
+    // Providers able to quote from the given chain, in order of preference
+    pub fn get_providers(&self, chain: Chain) -> Vec<SwapProvider> {
+        let mut providers = self
             .providers
             .iter()
-            .find(|x| x.supported_chains().contains(&source_chain));
-        if let Some(provider) = result {
-            return provider.get_quote(quote).await;
-        }
+            .filter(|x| x.supported_chains().contains(&chain))
+            .map(|x| x.provider())
+            .collect::<Vec<_>>();
 
-        match source_chain {
+        match chain {
             Chain::Ethereum
             | Chain::SmartChain
             | Chain::Optimism
@@ -54,10 +50,10 @@ impl SwapperClient {
             | Chain::ZkSync
             | Chain::Linea
             | Chain::Mantle
-            | Chain::Celo => self.oneinch.get_quote(quote).await,
-            Chain::Solana => self.jupiter.get_quote(quote).await,
+            | Chain::Celo => providers.push(ONEINCH_PROVIDER_NAME.into()),
+            Chain::Solana => providers.push(self.jupiter.provider()),
             Chain::Thorchain | Chain::Doge | Chain::Cosmos | Chain::Bitcoin | Chain::Litecoin => {
-                self.thorchain.get_quote(quote).await
+                providers.push(self.thorchain.provider())
             }
             Chain::Osmosis
             | Chain::Celestia
@@ -70,7 +66,42 @@ impl SwapperClient {
             | Chain::OpBNB
             | Chain::Noble
             | Chain::Sei
-            | Chain::Near => todo!(),
+            | Chain::Near => {}
+        }
+        providers
+    }
+
+    pub async fn get_quote(
+        &self,
+        quote: SwapQuoteProtocolRequest,
+    ) -> Result<SwapQuote, Box<dyn std::error::Error + Send + Sync>> {
+        let source_chain = quote.from_asset.chain;
+        let provider = self
+            .get_providers(source_chain)
+            .into_iter()
+            .next()
+            .ok_or(format!("swap is not supported on chain: {}", source_chain))?;
+        self.get_quote_by_provider(&provider, quote).await
+    }
+
+    pub async fn get_quote_by_provider(
+        &self,
+        provider: &SwapProvider,
+        quote: SwapQuoteProtocolRequest,
+    ) -> Result<SwapQuote, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(provider) = self
+            .providers
+            .iter()
+            .find(|x| x.provider().name == provider.name)
+        {
+            return provider.get_quote(quote).await;
+        }
+
+        match provider.name.as_str() {
+            ONEINCH_PROVIDER_NAME => self.oneinch.get_quote(quote).await,
+            name if name == self.jupiter.provider().name => self.jupiter.get_quote(quote).await,
+            name if name == self.thorchain.provider().name => self.thorchain.get_quote(quote).await,
+            name => Err(format!("unknown swap provider: {}", name).into()),
         }
     }
 }
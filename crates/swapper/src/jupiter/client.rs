@@ -72,6 +72,7 @@ impl JupiterClient {
             fee_percent: self.fee as f32,
             provider: self.provider(),
             data,
+            approval: None,
         };
         Ok(quote)
     }
@@ -108,6 +109,7 @@ impl JupiterClient {
             .query(&request)
             .send()
             .await?
+            .error_for_status()?
             .json::<QuoteResponse>()
             .await?)
     }
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 pub mod client;
 pub use self::client::SwapperClient;
+use primitives::Chain;
 use swap_aftermath::provider::AftermathProvider;
 use swap_oneinch::OneInchClient;
 use swap_provider::ProviderList;
@@ -7,12 +10,15 @@ pub mod jupiter;
 pub use self::jupiter::JupiterClient;
 pub mod thorswap;
 pub use self::thorswap::ThorchainSwapClient;
+pub mod rate_limiter;
+pub use self::rate_limiter::{is_throttled_error, RateLimiter};
 
 pub struct SwapperConfiguration {
     pub oneinch: SwapperClientConfiguration,
     pub jupiter: SwapperClientConfiguration,
     pub thorchain: SwapperClientConfiguration,
     pub aftermath: SwapperClientConfiguration,
+    pub nodes: HashMap<Chain, String>,
 }
 
 pub struct SwapperClientConfiguration {
@@ -33,6 +39,7 @@ impl Swapper {
             configuration.oneinch.key,
             configuration.oneinch.fee_percent,
             configuration.oneinch.fee_address,
+            configuration.nodes,
         );
         let jupiter_client = JupiterClient::new(
             configuration.jupiter.url,
@@ -0,0 +1,105 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+// Token bucket per provider, refilled continuously at `refill_rate` tokens per second.
+pub struct RateLimiter {
+    capacity: f64,
+    refill_rate: f64,
+    buckets: HashMap<String, TokenBucket>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, refill_rate: f64) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_rate,
+            buckets: HashMap::new(),
+        }
+    }
+
+    pub fn try_acquire(&mut self, key: &str) -> bool {
+        self.try_acquire_at(key, Instant::now())
+    }
+
+    // Drain the bucket when the provider itself reports throttling
+    pub fn throttle(&mut self, key: &str) {
+        self.throttle_at(key, Instant::now())
+    }
+
+    fn try_acquire_at(&mut self, key: &str, now: Instant) -> bool {
+        let bucket = self.refill(key, now);
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+
+    fn throttle_at(&mut self, key: &str, now: Instant) {
+        self.refill(key, now).tokens = 0.0;
+    }
+
+    fn refill(&mut self, key: &str, now: Instant) -> &mut TokenBucket {
+        let capacity = self.capacity;
+        let refill_rate = self.refill_rate;
+        let bucket = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: capacity,
+                updated_at: now,
+            });
+        let elapsed = now
+            .checked_duration_since(bucket.updated_at)
+            .unwrap_or(Duration::ZERO);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * refill_rate).min(capacity);
+        bucket.updated_at = now;
+        bucket
+    }
+}
+
+// Providers surface http errors through error_for_status, 429 is their throttling signal
+pub fn is_throttled_error(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(|x| x.status())
+        .is_some_and(|x| x == reqwest::StatusCode::TOO_MANY_REQUESTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_acquire() {
+        let mut limiter = RateLimiter::new(2, 1.0);
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire_at("1inch", now));
+        assert!(limiter.try_acquire_at("1inch", now));
+        assert!(!limiter.try_acquire_at("1inch", now));
+        assert!(limiter.try_acquire_at("Jupiter", now));
+
+        assert!(!limiter.try_acquire_at("1inch", now + Duration::from_millis(500)));
+        assert!(limiter.try_acquire_at("1inch", now + Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_throttle() {
+        let mut limiter = RateLimiter::new(5, 2.0);
+        let now = Instant::now();
+
+        limiter.throttle_at("1inch", now);
+
+        assert!(!limiter.try_acquire_at("1inch", now));
+        assert!(limiter.try_acquire_at("1inch", now + Duration::from_millis(500)));
+        assert!(!limiter.try_acquire_at("1inch", now + Duration::from_millis(500)));
+    }
+}
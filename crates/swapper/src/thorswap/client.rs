@@ -89,6 +89,7 @@ impl ThorchainSwapClient {
             fee_percent: self.fee as f32,
             provider: self.provider(),
             data,
+            approval: None,
         };
         Ok(quote)
     }
@@ -104,6 +105,7 @@ impl ThorchainSwapClient {
             .query(&request)
             .send()
             .await?
+            .error_for_status()?
             .json::<QuoteResponse>()
             .await?)
     }